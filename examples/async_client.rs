@@ -0,0 +1,68 @@
+use graph_rs::futures::{Future, Stream};
+use graph_rs::prelude::*;
+
+// The async client uses the same request builders as the blocking client
+// but returns futures instead of responses. Delta requests return a stream
+// of each page and downloads are written to the file as the body is read.
+
+static ACCESS_TOKEN: &str = "ACCESS_TOKEN";
+static ITEM_ID: &str = "ITEM_ID";
+
+fn main() {
+    get_drive_item();
+    delta();
+    download();
+}
+
+fn get_drive_item() {
+    let client = Graph::new_async(ACCESS_TOKEN);
+
+    let future = client
+        .v1()
+        .me()
+        .drive()
+        .get_item(ITEM_ID)
+        .send()
+        .map(|response| println!("{:#?}", response.value()))
+        .map_err(|err| println!("{:#?}", err));
+
+    tokio::run(future);
+}
+
+fn delta() {
+    let client = Graph::new_async(ACCESS_TOKEN);
+
+    // Each next link is called when the stream is polled
+    // for the next page.
+    let future = client
+        .v1()
+        .me()
+        .drive()
+        .delta()
+        .send()
+        .for_each(|response| {
+            println!("{:#?}", response.value());
+            Ok(())
+        })
+        .map_err(|err| println!("{:#?}", err));
+
+    tokio::run(future);
+}
+
+fn download() {
+    let client = Graph::new_async(ACCESS_TOKEN);
+
+    let mut download = client
+        .v1()
+        .me()
+        .drive()
+        .download(ITEM_ID, "./examples/example_files");
+    download.overwrite_existing_file(true);
+
+    let future = download
+        .send()
+        .map(|path_buf| println!("{:#?}", path_buf))
+        .map_err(|err| println!("{:#?}", err));
+
+    tokio::run(future);
+}
@@ -55,5 +55,5 @@ fn copy_item() {
     // Wait a few seconds before checking the progress (assuming the file or
     // folder size is small here).
     thread::sleep(Duration::from_secs(5));
-    println!("{:#?}", &response.async_job_status());
+    println!("{:#?}", &response.async_job_status(&graph));
 }
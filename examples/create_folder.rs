@@ -18,7 +18,7 @@ fn create_new_folder() {
     let drive = Graph::try_from(&oauth).unwrap();
     let folder: HashMap<String, serde_json::Value> = HashMap::new();
 
-    let drive_item: GraphResponse<serde_json::Value> = drive
+    let drive_item: GraphResponse<DriveItem> = drive
         .v1()
         .me()
         .drive()
@@ -133,7 +133,8 @@ fn delta_my_type() {
         delta_link: Some("https://example.com/delta".into()),
     };
 
-    let recv: Option<Receiver<Delta<serde_json::Value>>> = my_type.delta(ACCESS_TOKEN);
+    let client = Graph::new(ACCESS_TOKEN);
+    let recv: Option<Receiver<Delta<serde_json::Value>>> = my_type.delta(&client);
 
     if let Some(recv) = recv {
         loop {
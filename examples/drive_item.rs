@@ -18,8 +18,7 @@ fn main() {
 }
 
 fn drive_root(graph: &mut Graph) {
-    let drive_item: GraphResponse<serde_json::Value> =
-        graph.v1().me().drive().root().send().unwrap();
+    let drive_item: GraphResponse<DriveItem> = graph.v1().me().drive().root().send().unwrap();
     println!("{:#?}", drive_item);
 }
 
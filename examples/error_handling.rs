@@ -11,6 +11,9 @@ When an error is returned from Microsoft graph a GraphFailure::GraphError(GraphE
 returned which you can use to get the request id and message for debugging. The inner error
 of GraphError also typically provides better info on the cause of the error.
 
+Other failures of a request, such as transport and parse failures, are wrapped in
+GraphFailure::WithClientRequestId so match on e.failure() to get the kind of failure.
+
 Since parts of the error are wrapped in other structs and Options you can use
 the convenience methods on GraphError making it easier to retrieve error information:
 
@@ -29,7 +32,7 @@ fn main() {
     let response = client.v1().me().get().send();
 
     if let Err(e) = response {
-        match e {
+        match e.failure() {
             GraphFailure::GraphError(err) => {
                 // Basic error information from the HTTP Response
                 println!("Description: {:#?}", err.error_info);
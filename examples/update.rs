@@ -21,7 +21,7 @@ fn update() {
     // Fields that are not included will not be changed.
     let value = serde_json::json!({ "name": DRIVE_FILE_NEW_NAME });
 
-    let updated: GraphResponse<serde_json::Value> = graph
+    let updated: GraphResponse<DriveItem> = graph
         .v1()
         .me()
         .drive()
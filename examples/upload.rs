@@ -25,7 +25,7 @@ fn main() {
 // Uploading a file using the drive id and parent id.
 fn upload_file() {
     let graph = Graph::new(ACCESS_TOKEN);
-    let drive_item: GraphResponse<serde_json::Value> = graph
+    let drive_item: GraphResponse<DriveItem> = graph
         .v1()
         .me()
         .drive()
@@ -39,7 +39,7 @@ fn upload_file() {
 fn upload_new() {
     let graph = Graph::new(ACCESS_TOKEN);
 
-    let drive_item: GraphResponse<serde_json::Value> = graph
+    let drive_item: GraphResponse<DriveItem> = graph
         .v1()
         .me()
         .drive()
@@ -56,7 +56,7 @@ fn sites_upload_new() {
     // Get the latest metadata for the root drive folder items.
     let graph = Graph::new(ACCESS_TOKEN);
 
-    let drive_item: GraphResponse<serde_json::Value> = graph
+    let drive_item: GraphResponse<DriveItem> = graph
         .v1()
         .sites(RESOURCE_ID)
         .drive()
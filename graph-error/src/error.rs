@@ -43,6 +43,39 @@ pub struct GraphError {
     pub error_message: ErrorMessage,
 }
 
+/// The error for a 412 Precondition Failed response.
+///
+/// The resource was changed after the ETag sent in an If-Match
+/// or If-None-Match header was read.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PreconditionFailed {
+    pub error: GraphError,
+    /// The ETag of the current version of the resource, if it was returned.
+    pub etag: Option<String>,
+    /// The current version of the resource, if it was returned
+    /// in the body of the response.
+    pub current: Option<serde_json::Value>,
+}
+
+impl PreconditionFailed {
+    pub fn new(error: GraphError, body: &str) -> PreconditionFailed {
+        let etag = error
+            .headers
+            .as_ref()
+            .and_then(|headers| headers.header_map().get(reqwest::header::ETAG))
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string());
+        let current = serde_json::from_str::<serde_json::Value>(body)
+            .ok()
+            .filter(|value| value.is_object() && value.get("error").is_none());
+        PreconditionFailed {
+            error,
+            etag,
+            current,
+        }
+    }
+}
+
 impl GraphError {
     pub fn new(
         headers: Option<GraphHeaders>,
@@ -102,6 +135,11 @@ impl GraphError {
             .clone()
     }
 
+    /// The client-request-id of the request that returned the error.
+    pub fn client_request_id(&self) -> Option<String> {
+        self.headers.as_ref()?.client_request_id()
+    }
+
     pub fn date(&self) -> Option<String> {
         self.error_message
             .error
@@ -144,7 +182,11 @@ impl std::fmt::Display for GraphError {
             f,
             "\nError Code: {:#?}\nError Message: {:#?}",
             &self.code, &self.error_info
-        )
+        )?;
+        if let Some(id) = self.client_request_id() {
+            write!(f, "\nClient Request Id: {}", id)?;
+        }
+        Ok(())
     }
 }
 
@@ -180,6 +222,7 @@ pub enum ErrorType {
     InternalServerError,
     NotImplemented,
     ServiceUnavailable,
+    GatewayTimeout,
     InsufficientStorage,
     BandwidthLimitExceeded,
     UnknownError,
@@ -208,6 +251,7 @@ impl ErrorType {
             ErrorType::InternalServerError => "There was an internal server error while processing the request.",
             ErrorType::NotImplemented => "The requested feature isn’t implemented.",
             ErrorType::ServiceUnavailable => "The service is temporarily unavailable. You may repeat the request after a delay. There may be a Retry-After header.",
+            ErrorType::GatewayTimeout => "The server, while acting as a gateway, did not receive a timely response. You may repeat the request after a delay.",
             ErrorType::InsufficientStorage => "The maximum storage quota has been reached.",
             ErrorType::BandwidthLimitExceeded => "Your app has been throttled for exceeding the maximum bandwidth cap. Your app can retry the request again after more time has elapsed.",
             ErrorType::UnknownError => "Unknown error or failure",
@@ -234,6 +278,7 @@ impl ErrorType {
             500 => Some(ErrorType::InternalServerError),
             501 => Some(ErrorType::NotImplemented),
             503 => Some(ErrorType::ServiceUnavailable),
+            504 => Some(ErrorType::GatewayTimeout),
             507 => Some(ErrorType::InsufficientStorage),
             509 => Some(ErrorType::BandwidthLimitExceeded),
             _ => None,
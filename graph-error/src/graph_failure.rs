@@ -1,4 +1,4 @@
-use crate::error::GraphError;
+use crate::error::{GraphError, PreconditionFailed};
 use crate::internal::GraphRsError;
 use crate::GraphResult;
 use from_as::FromAsError;
@@ -31,6 +31,7 @@ pub enum GraphFailure {
     SerdeYamlError(serde_yaml::Error),
     DecodeError(base64::DecodeError),
     GraphError(Box<GraphError>),
+    PreconditionFailed(Box<PreconditionFailed>),
     RecvError(mpsc::RecvError),
     BorrowMutError(BorrowMutError),
     UrlParseError(url::ParseError),
@@ -39,6 +40,15 @@ pub enum GraphFailure {
     HyperInvalidUri(hyper::http::uri::InvalidUri),
     FromAsFileError(FromAsError),
     GraphRsError(GraphRsError),
+    /// A failure that is not from an error response, such as a transport,
+    /// IO or parse failure, with the client-request-id of the request.
+    ///
+    /// Failures of sent requests are wrapped in this variant because most
+    /// of the other variants hold error types from other crates that cannot
+    /// carry the id. Match on `failure()` to match the wrapped failure.
+    /// Error responses are not wrapped: `GraphError` and
+    /// `PreconditionFailed` have the id of the response.
+    WithClientRequestId(String, Box<GraphFailure>),
 }
 
 impl GraphFailure {
@@ -59,6 +69,57 @@ impl GraphFailure {
         GraphFailure::internal(GraphRsError::InvalidOrMissing { msg: msg.into() })
     }
 
+    /// The failure for an error response with the body of the response.
+    /// A 412 status is returned as `GraphFailure::PreconditionFailed`.
+    pub fn from_graph_error(mut error: GraphError, body: &str) -> GraphFailure {
+        error.set_error_message(serde_json::from_str(body).unwrap_or_default());
+        if error.code == 412 {
+            GraphFailure::PreconditionFailed(Box::new(PreconditionFailed::new(error, body)))
+        } else {
+            GraphFailure::from(error)
+        }
+    }
+
+    /// The client-request-id of the request that failed.
+    pub fn client_request_id(&self) -> Option<String> {
+        match self {
+            GraphFailure::GraphError(err) => err.client_request_id(),
+            GraphFailure::PreconditionFailed(err) => err.error.client_request_id(),
+            GraphFailure::WithClientRequestId(id, _) => Some(id.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Attach the client-request-id of the request to a failure
+    /// that does not already have one.
+    pub fn with_client_request_id(self, id: &str) -> GraphFailure {
+        if self.client_request_id().is_some() {
+            self
+        } else {
+            GraphFailure::WithClientRequestId(id.to_string(), Box::new(self))
+        }
+    }
+
+    /// The failure without the client-request-id attached to it. Use this
+    /// to match on the kind of failure of a sent request.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// if let Err(err) = client.v1().me().get().send() {
+    ///     match err.failure() {
+    ///         GraphFailure::GraphError(err) => println!("{}", err.code),
+    ///         GraphFailure::ReqwestError(err) => println!("{}", err),
+    ///         _ => {},
+    ///     }
+    /// }
+    /// ```
+    pub fn failure(&self) -> &GraphFailure {
+        match self {
+            GraphFailure::WithClientRequestId(_, err) => err.failure(),
+            _ => self,
+        }
+    }
+
     pub fn from_response(r: &mut Response) -> Option<GraphFailure> {
         GraphFailure::try_from(r).ok()
     }
@@ -93,6 +154,9 @@ impl fmt::Display for GraphFailure {
             GraphFailure::SerdeYamlError(ref err) => write!(f, "Serde yaml error:\n{:#?}", err),
             GraphFailure::DecodeError(ref err) => write!(f, "Base64 decode error:\n{:#?}", err),
             GraphFailure::GraphError(ref err) => write!(f, "Graph error:\n{:#?}", err),
+            GraphFailure::PreconditionFailed(ref err) => {
+                write!(f, "Precondition failed:\n{:#?}", err)
+            },
             GraphFailure::RecvError(ref err) => write!(f, "Recv error:\n{:#?}", err),
             GraphFailure::BorrowMutError(ref err) => {
                 write!(f, "Borrow Mut Error error:\n{:#?}", err)
@@ -103,6 +167,9 @@ impl fmt::Display for GraphFailure {
             GraphFailure::HyperInvalidUri(ref err) => write!(f, "Hyper http error:\n{:#?}", err),
             GraphFailure::FromAsFileError(ref err) => write!(f, "Hyper http error:\n{:#?}", err),
             GraphFailure::GraphRsError(ref err) => write!(f, "Hyper http error:\n{:#?}", err),
+            GraphFailure::WithClientRequestId(ref id, ref err) => {
+                write!(f, "{}\nclient-request-id: {}", err, id)
+            },
         }
     }
 }
@@ -121,6 +188,7 @@ impl error::Error for GraphFailure {
             GraphFailure::DecodeError(ref err) => Some(err),
             GraphFailure::RecvError(ref err) => Some(err),
             GraphFailure::GraphError(_) => None,
+            GraphFailure::PreconditionFailed(_) => None,
             GraphFailure::BorrowMutError(ref err) => Some(err),
             GraphFailure::UrlParseError(ref err) => Some(err),
             GraphFailure::HyperError(ref err) => Some(err),
@@ -128,6 +196,7 @@ impl error::Error for GraphFailure {
             GraphFailure::HyperInvalidUri(ref err) => Some(err),
             GraphFailure::FromAsFileError(ref err) => Some(err),
             GraphFailure::GraphRsError(ref err) => Some(err),
+            GraphFailure::WithClientRequestId(_, ref err) => Some(err.as_ref()),
         }
     }
 }
@@ -41,6 +41,36 @@ pub struct GraphHeaders {
     header_map: HeaderMap,
 }
 
+impl GraphHeaders {
+    pub fn new(url: &str, status: u16, header_map: HeaderMap) -> GraphHeaders {
+        GraphHeaders {
+            url: url.to_string(),
+            status,
+            header_map,
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        self.url.as_str()
+    }
+
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    pub fn header_map(&self) -> &HeaderMap {
+        &self.header_map
+    }
+
+    /// The client-request-id sent with the request and returned by the API.
+    pub fn client_request_id(&self) -> Option<String> {
+        self.header_map
+            .get("client-request-id")
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string())
+    }
+}
+
 impl From<&mut reqwest::Response> for GraphHeaders {
     fn from(r: &mut reqwest::Response) -> Self {
         GraphHeaders {
@@ -18,6 +18,12 @@ pub enum GraphRsError {
     InvalidOrMissing { msg: String },
     #[snafu(display("Invalid file extension. Requires {} but found {}", requires, found))]
     InvalidFileExtension { requires: String, found: String },
+    #[snafu(display("Async job failed: {}", description))]
+    AsyncJobFailed { description: String },
+    #[snafu(display("Async job did not finish within {:?}", timeout))]
+    AsyncJobTimedOut { timeout: std::time::Duration },
+    #[snafu(display("Async job polling was cancelled"))]
+    AsyncJobCancelled,
 }
 
 impl AsRes for GraphRsError {
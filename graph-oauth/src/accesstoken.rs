@@ -363,6 +363,24 @@ impl AccessToken {
         true
     }
 
+    /// Check whether the access token is expired or will expire within
+    /// the given number of seconds.
+    ///
+    /// # Example
+    /// ```
+    /// # use graph_oauth::oauth::AccessToken;
+    ///
+    /// let access_token = AccessToken::new("Bearer", 3600, "Read.Write", "ASODFIUJ34KJ;LADSK");
+    /// assert!(!access_token.expires_within(60));
+    /// assert!(access_token.expires_within(7200));
+    /// ```
+    pub fn expires_within(&self, seconds: i64) -> bool {
+        match self.timestamp {
+            Some(timestamp) => timestamp - Utc::now() <= Duration::seconds(seconds),
+            None => true,
+        }
+    }
+
     // TODO: This should checked using the bearer token.
     /// Get the time left in seconds until the access token expires.
     /// See the HumanTime crate. If you just need to know if the access token
@@ -1,4 +1,5 @@
 use crate::accesstoken::AccessToken;
+use crate::cloud::AzureCloud;
 use crate::grants::{GrantRequest, GrantType};
 use crate::idtoken::IdToken;
 use crate::oautherror::OAuthError;
@@ -321,6 +322,23 @@ impl OAuth {
         self.insert(OAuthCredential::AuthorizeURL, value)
     }
 
+    /// Set the authorize, access token, refresh token and logout urls
+    /// using the authority of a cloud and a tenant such as common,
+    /// organizations, consumers or a tenant id.
+    ///
+    /// # Example
+    /// ```
+    /// # use graph_oauth::oauth::{AzureCloud, OAuth};
+    /// # let mut oauth = OAuth::new();
+    /// oauth.cloud(&AzureCloud::UsGovL4, "common");
+    /// ```
+    pub fn cloud(&mut self, cloud: &AzureCloud, tenant: &str) -> &mut OAuth {
+        self.authorize_url(cloud.authorize_url(tenant).as_str())
+            .access_token_url(cloud.token_url(tenant).as_str())
+            .refresh_token_url(cloud.token_url(tenant).as_str())
+            .logout_url(cloud.logout_url(tenant).as_str())
+    }
+
     /// Set the access token url of a request for OAuth
     ///
     /// # Example
@@ -1201,6 +1219,30 @@ impl<V: ToString> Extend<(OAuthCredential, V)> for OAuth {
 pub struct GrantSelector(OAuth);
 
 impl GrantSelector {
+    /// Create a new instance for a grant that requests access tokens.
+    /// Returns None for the token flow and implicit grants which only
+    /// return access tokens in the redirect after authorization.
+    ///
+    /// # Example
+    /// ```
+    /// # use graph_oauth::oauth::{GrantType, OAuth};
+    /// # let mut oauth = OAuth::new();
+    /// let grant = oauth.build().access_token_grant(GrantType::ClientCredentials);
+    /// assert!(grant.is_some());
+    /// ```
+    pub fn access_token_grant(self, grant: GrantType) -> Option<AccessTokenGrant> {
+        match grant {
+            GrantType::CodeFlow => Some(self.code_flow()),
+            GrantType::AuthorizationCode => Some(self.authorization_code_grant()),
+            GrantType::OpenId => Some(self.open_id_connect()),
+            GrantType::ClientCredentials => Some(self.client_credentials()),
+            GrantType::ResourceOwnerPasswordCredentials => {
+                Some(self.resource_owner_password_credentials())
+            },
+            GrantType::TokenFlow | GrantType::Implicit => None,
+        }
+    }
+
     /// Create a new instance for token flow.
     ///
     /// # See
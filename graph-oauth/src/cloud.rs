@@ -0,0 +1,109 @@
+/// The cloud, or environment, that requests are sent to.
+///
+/// The cloud sets the host of the Graph API and the authority used
+/// for OAuth and discovery. National clouds are separate from the
+/// global cloud and each has its own hosts. A custom cloud can be used
+/// to point both at another server such as a local stand-in for tests.
+///
+/// # Example
+/// ```
+/// use graph_oauth::oauth::AzureCloud;
+///
+/// let cloud = AzureCloud::China;
+/// assert_eq!("https://microsoftgraph.chinacloudapi.cn/v1.0", cloud.graph_url());
+/// assert_eq!(
+///     "https://login.chinacloudapi.cn/common/oauth2/v2.0/token",
+///     cloud.token_url("common")
+/// );
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum AzureCloud {
+    /// The global Microsoft cloud.
+    Global,
+    /// US Government L4.
+    UsGovL4,
+    /// US Government L5 (DOD).
+    UsGovL5,
+    /// China operated by 21Vianet.
+    China,
+    /// Germany.
+    Germany,
+    /// A custom Graph host and authority.
+    Custom {
+        graph_host: String,
+        authority_host: String,
+    },
+}
+
+impl AzureCloud {
+    /// A custom cloud. The hosts should not end with a slash.
+    pub fn custom(graph_host: &str, authority_host: &str) -> AzureCloud {
+        AzureCloud::Custom {
+            graph_host: graph_host.trim_end_matches('/').to_string(),
+            authority_host: authority_host.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub fn graph_host(&self) -> &str {
+        match self {
+            AzureCloud::Global => "https://graph.microsoft.com",
+            AzureCloud::UsGovL4 => "https://graph.microsoft.us",
+            AzureCloud::UsGovL5 => "https://dod-graph.microsoft.us",
+            AzureCloud::China => "https://microsoftgraph.chinacloudapi.cn",
+            AzureCloud::Germany => "https://graph.microsoft.de",
+            AzureCloud::Custom { graph_host, .. } => graph_host.as_str(),
+        }
+    }
+
+    pub fn authority_host(&self) -> &str {
+        match self {
+            AzureCloud::Global => "https://login.microsoftonline.com",
+            AzureCloud::UsGovL4 | AzureCloud::UsGovL5 => "https://login.microsoftonline.us",
+            AzureCloud::China => "https://login.chinacloudapi.cn",
+            AzureCloud::Germany => "https://login.microsoftonline.de",
+            AzureCloud::Custom { authority_host, .. } => authority_host.as_str(),
+        }
+    }
+
+    /// The url of the Graph v1.0 API.
+    pub fn graph_url(&self) -> String {
+        format!("{}/v1.0", self.graph_host())
+    }
+
+    /// The url of the Graph beta API.
+    pub fn graph_url_beta(&self) -> String {
+        format!("{}/beta", self.graph_host())
+    }
+
+    pub fn authorize_url(&self, tenant: &str) -> String {
+        format!("{}/{}/oauth2/v2.0/authorize", self.authority_host(), tenant)
+    }
+
+    pub fn token_url(&self, tenant: &str) -> String {
+        format!("{}/{}/oauth2/v2.0/token", self.authority_host(), tenant)
+    }
+
+    pub fn logout_url(&self, tenant: &str) -> String {
+        format!("{}/{}/oauth2/v2.0/logout", self.authority_host(), tenant)
+    }
+
+    /// The url of the OpenID configuration for a tenant.
+    pub fn openid_configuration_url(&self, tenant: &str) -> String {
+        format!(
+            "{}/{}/.well-known/openid-configuration",
+            self.authority_host(),
+            tenant
+        )
+    }
+
+    /// The url of the keys used to sign tokens.
+    pub fn discovery_keys_url(&self) -> String {
+        format!("{}/common/discovery/keys", self.authority_host())
+    }
+}
+
+impl Default for AzureCloud {
+    fn default() -> Self {
+        AzureCloud::Global
+    }
+}
@@ -1,3 +1,4 @@
+use crate::cloud::AzureCloud;
 use crate::oauth::wellknown::{Commons, WellKnown};
 use crate::oauth::{OAuth, OAuthError};
 use from_as::*;
@@ -47,87 +48,59 @@ pub struct MicrosoftSigningKeysV2 {
     pub rbac_url: String,
 }
 
+/// Requests the OpenID configuration of an authority.
+///
+/// `Cloud` uses the authority of a national or custom cloud
+/// for the given tenant.
 pub enum GraphDiscovery {
     V1,
     V2,
     Tenant(String),
+    Cloud(AzureCloud, String),
 }
 
 impl GraphDiscovery {
+    /// The url of the OpenID configuration.
+    pub fn url(&self) -> String {
+        match self {
+            GraphDiscovery::V1 => {
+                String::from("https://login.live.com/.well-known/openid-configuration")
+            },
+            GraphDiscovery::V2 => AzureCloud::Global.openid_configuration_url("common"),
+            GraphDiscovery::Tenant(tenant) => AzureCloud::Global.openid_configuration_url(tenant),
+            GraphDiscovery::Cloud(cloud, tenant) => cloud.openid_configuration_url(tenant),
+        }
+    }
+
     pub fn signing_keys<T>(self) -> Result<T, OAuthError>
     where
         T: serde::Serialize,
         for<'de> T: serde::Deserialize<'de>,
     {
-        match self {
-            GraphDiscovery::V1 => {
-                let t: T = Commons::signing_keys(
-                    "https://login.live.com/.well-known/openid-configuration",
-                )?;
-                Ok(t)
-            },
-            GraphDiscovery::V2 => {
-                let t: T = Commons::signing_keys(
-                    "https://login.microsoftonline.com/common/.well-known/openid-configuration",
-                )?;
-                Ok(t)
-            },
-            GraphDiscovery::Tenant(tenant) => {
-                let url = vec![
-                    "https://login.microsoftonline.com/",
-                    &tenant,
-                    "/.well-known/openid-configuration",
-                ];
-                let t: T = Commons::signing_keys(url.join("").as_str())?;
-                Ok(t)
-            },
-        }
+        let t: T = Commons::signing_keys(self.url().as_str())?;
+        Ok(t)
     }
 
     pub fn oauth(self) -> Result<OAuth, OAuthError> {
         let mut oauth = OAuth::new();
         match self {
             GraphDiscovery::V1 => {
-                let k: MicrosoftSigningKeysV1 = Commons::signing_keys(
-                    "https://login.live.com/.well-known/openid-configuration",
-                )?;
-
+                let k: MicrosoftSigningKeysV1 = self.signing_keys()?;
                 oauth
                     .authorize_url(k.authorization_endpoint.as_str())
                     .access_token_url(k.token_endpoint.as_str())
                     .refresh_token_url(k.token_endpoint.as_str())
                     .logout_url(k.end_session_endpoint.as_str());
-
-                Ok(oauth)
             },
-            GraphDiscovery::V2 => {
-                let k: MicrosoftSigningKeysV2 = Commons::signing_keys(
-                    "https://login.microsoftonline.com/common/.well-known/openid-configuration",
-                )?;
-
+            _ => {
+                let k: MicrosoftSigningKeysV2 = self.signing_keys()?;
                 oauth
                     .authorize_url(k.authorization_endpoint.as_str())
                     .access_token_url(k.token_endpoint.as_str())
                     .refresh_token_url(k.token_endpoint.as_str())
                     .logout_url(k.end_session_endpoint.as_str());
-
-                Ok(oauth)
-            },
-            GraphDiscovery::Tenant(tenant) => {
-                let url = vec![
-                    "https://login.microsoftonline.com/",
-                    &tenant,
-                    "/.well-known/openid-configuration",
-                ];
-                let k: MicrosoftSigningKeysV2 = Commons::signing_keys(url.join("").as_str())?;
-                oauth
-                    .authorize_url(k.authorization_endpoint.as_str())
-                    .access_token_url(k.token_endpoint.as_str())
-                    .refresh_token_url(k.token_endpoint.as_str())
-                    .logout_url(k.end_session_endpoint.as_str());
-
-                Ok(oauth)
             },
         }
+        Ok(oauth)
     }
 }
@@ -1,3 +1,4 @@
+use crate::cloud::AzureCloud;
 use graph_error::GraphFailure;
 use std::collections::HashMap;
 
@@ -44,8 +45,13 @@ pub struct JWTKeys {
 impl JWTKeys {
     #[allow(dead_code)]
     pub fn discovery() -> Result<JWTKeys, GraphFailure> {
+        JWTKeys::cloud_discovery(&AzureCloud::Global)
+    }
+
+    /// Get the signing keys from the authority of a cloud.
+    pub fn cloud_discovery(cloud: &AzureCloud) -> Result<JWTKeys, GraphFailure> {
         let client = reqwest::Client::builder().build()?;
-        let url = String::from("https://login.microsoftonline.com/common/discovery/keys");
+        let url = cloud.discovery_keys_url();
         let response = client.get(&url).send();
 
         match response {
@@ -73,17 +73,20 @@ extern crate strum_macros;
 
 mod accesstoken;
 mod auth;
+mod cloud;
 mod discovery;
 mod grants;
 mod idtoken;
 pub mod jwt;
 mod oautherror;
+mod tokenprovider;
 
 pub mod oauth {
     pub use crate::accesstoken::AccessToken;
     pub use crate::auth::GrantSelector;
     pub use crate::auth::OAuth;
     pub use crate::auth::OAuthCredential;
+    pub use crate::cloud::AzureCloud;
     pub use crate::discovery::graphdiscovery;
     pub use crate::discovery::jwtkeys;
     pub use crate::discovery::wellknown;
@@ -92,4 +95,6 @@ pub mod oauth {
     pub use crate::idtoken::IdToken;
     pub use crate::oautherror::OAuthError;
     pub use crate::strum::IntoEnumIterator;
+    pub use crate::tokenprovider::OAuthTokenProvider;
+    pub use crate::tokenprovider::TokenProvider;
 }
@@ -0,0 +1,114 @@
+use crate::accesstoken::AccessToken;
+use crate::auth::{OAuth, OAuthReq};
+use crate::grants::GrantType;
+use graph_error::GraphFailure;
+use std::sync::Mutex;
+
+/// Provides the bearer token used for each request.
+///
+/// The client asks the provider for a token before every request. When a
+/// request fails with 401 Unauthorized the client calls `refresh` and, if a
+/// new token is returned, sends the request once more.
+pub trait TokenProvider: Send + Sync {
+    /// The bearer token for the next request.
+    fn bearer_token(&self) -> OAuthReq<String>;
+
+    /// Get a new bearer token after the current one was rejected.
+    /// Returns None if the token cannot be refreshed.
+    fn refresh(&self) -> OAuthReq<Option<String>> {
+        Ok(None)
+    }
+}
+
+impl TokenProvider for String {
+    fn bearer_token(&self) -> OAuthReq<String> {
+        Ok(self.clone())
+    }
+}
+
+impl TokenProvider for AccessToken {
+    fn bearer_token(&self) -> OAuthReq<String> {
+        Ok(self.bearer_token().to_string())
+    }
+}
+
+/// A token provider that uses OAuth and a grant to request access tokens.
+///
+/// The access token stored in OAuth is used until it is about to expire.
+/// A new token is then requested using the refresh token when there is one,
+/// or an access token request for grants such as client credentials.
+///
+/// # Example
+/// ```rust,ignore
+/// let provider = OAuthTokenProvider::new(oauth, GrantType::AuthorizationCode);
+/// let client = Graph::with_token_provider(provider);
+/// ```
+pub struct OAuthTokenProvider {
+    oauth: Mutex<OAuth>,
+    grant: GrantType,
+    refresh_before: i64,
+}
+
+impl OAuthTokenProvider {
+    pub fn new(oauth: OAuth, grant: GrantType) -> OAuthTokenProvider {
+        OAuthTokenProvider {
+            oauth: Mutex::new(oauth),
+            grant,
+            refresh_before: 300,
+        }
+    }
+
+    /// Set how many seconds before the access token expires that a new
+    /// token is requested. The default is 300 seconds.
+    pub fn refresh_before(&mut self, seconds: i64) -> &mut OAuthTokenProvider {
+        self.refresh_before = seconds;
+        self
+    }
+
+    /// A copy of the OAuth instance including the current access token.
+    pub fn oauth(&self) -> OAuth {
+        self.oauth.lock().unwrap().clone()
+    }
+
+    fn request_token(&self, oauth: &mut OAuth) -> OAuthReq<AccessToken> {
+        let previous = oauth.get_refresh_token().ok();
+        let mut grant = oauth
+            .build()
+            .access_token_grant(self.grant)
+            .ok_or_else(|| GraphFailure::invalid("grant that can request access tokens"))?;
+
+        let mut access_token = if previous.is_some() {
+            grant.refresh_token().send()?
+        } else {
+            grant.access_token().send()?
+        };
+
+        // Keep the refresh token when a new one is not returned.
+        if access_token.clone().refresh_token().is_none() {
+            if let Some(refresh_token) = previous {
+                access_token.set_refresh_token(refresh_token.as_str());
+            }
+        }
+        oauth.access_token(access_token.clone());
+        Ok(access_token)
+    }
+}
+
+impl TokenProvider for OAuthTokenProvider {
+    fn bearer_token(&self) -> OAuthReq<String> {
+        let mut oauth = self.oauth.lock().unwrap();
+        if let Some(access_token) = oauth.get_access_token() {
+            if !access_token.expires_within(self.refresh_before) {
+                return Ok(access_token.bearer_token().to_string());
+            }
+        }
+        let access_token = self.request_token(&mut oauth)?;
+        Ok(access_token.bearer_token().to_string())
+    }
+
+    fn refresh(&self) -> OAuthReq<Option<String>> {
+        let mut oauth = self.oauth.lock().unwrap();
+        let access_token = self.request_token(&mut oauth)?;
+        Ok(Some(access_token.bearer_token().to_string()))
+    }
+}
@@ -1,44 +1,49 @@
-use crate::client::Graph;
-use crate::http::{GraphResponse, IntoResponse};
+use crate::http::{GraphResponse, IntoResponse, RequestClient};
 use crate::types::collection::Collection;
 use crate::types::content::Content;
 use reqwest::Method;
 
 register_client!(AttachmentRequest,);
 
-impl<'a> AttachmentRequest<'a> {
+impl<'a, Client> AttachmentRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( | get, serde_json::Value => "attachments/{{id}}" );
     get!( | content, GraphResponse<Content> => "attachments/{{id}}/$value" );
     delete!( | delete, GraphResponse<Content> => "attachments/{{id}}" );
 
-    pub fn calendars(&'a self) -> CalendarAttachmentRequest<'a> {
-        CalendarAttachmentRequest::new(self.client)
+    pub fn calendars(&self) -> CalendarAttachmentRequest<'a, Client> {
+        CalendarAttachmentRequest::new(&self.client)
     }
 
-    pub fn calendar_groups(&'a self) -> CalendarGroupAttachmentRequest<'a> {
-        CalendarGroupAttachmentRequest::new(self.client)
+    pub fn calendar_groups(&self) -> CalendarGroupAttachmentRequest<'a, Client> {
+        CalendarGroupAttachmentRequest::new(&self.client)
     }
 
-    pub fn mail_folder(&'a self) -> MailFolderMessageAttachmentRequest<'a> {
-        MailFolderMessageAttachmentRequest::new(self.client)
+    pub fn mail_folder(&self) -> MailFolderMessageAttachmentRequest<'a, Client> {
+        MailFolderMessageAttachmentRequest::new(&self.client)
     }
 
-    pub fn messages(&'a self) -> MailMessageAttachmentRequest<'a> {
-        MailMessageAttachmentRequest::new(self.client)
+    pub fn messages(&self) -> MailMessageAttachmentRequest<'a, Client> {
+        MailMessageAttachmentRequest::new(&self.client)
     }
 
-    pub fn thread_posts(&'a self) -> ThreadPostAttachmentRequest<'a> {
-        ThreadPostAttachmentRequest::new(self.client)
+    pub fn thread_posts(&self) -> ThreadPostAttachmentRequest<'a, Client> {
+        ThreadPostAttachmentRequest::new(&self.client)
     }
 
-    pub fn conversation_posts(&'a self) -> ThreadConvoPostAttachmentRequest<'a> {
-        ThreadConvoPostAttachmentRequest::new(self.client)
+    pub fn conversation_posts(&self) -> ThreadConvoPostAttachmentRequest<'a, Client> {
+        ThreadConvoPostAttachmentRequest::new(&self.client)
     }
 }
 
 register_client!(CalendarAttachmentRequest,);
 
-impl<'a> CalendarAttachmentRequest<'a> {
+impl<'a, Client> CalendarAttachmentRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( || get_default, serde_json::Value => "events/{{id}}/attachments/{{id}}" );
     get!( || default_content, GraphResponse<Content> => "events/{{id}}/attachments/{{id}}/$value" );
     delete!( || delete_default, GraphResponse<Content> => "events/{{id}}/attachments/{{id}}" );
@@ -49,7 +54,10 @@ impl<'a> CalendarAttachmentRequest<'a> {
 
 register_client!(CalendarGroupAttachmentRequest,);
 
-impl<'a> CalendarGroupAttachmentRequest<'a> {
+impl<'a, Client> CalendarGroupAttachmentRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( ||| get_default, serde_json::Value => "calendargroup/calendars/{{id}}/events/{{id2}}/attachments/{{id3}}" );
     get!( ||| default_content, GraphResponse<Content> => "calendargroup/calendars/{{id}}/events/{{id2}}/attachments/{{id3}}/$value" );
     delete!( ||| delete_default, GraphResponse<Content> => "calendargroup/calendars/{{id}}/events/{{id2}}/attachments/{{id3}}" );
@@ -60,27 +68,33 @@ impl<'a> CalendarGroupAttachmentRequest<'a> {
 
 register_client!(MailMessageAttachmentRequest,);
 
-impl<'a> MailMessageAttachmentRequest<'a> {
+impl<'a, Client> MailMessageAttachmentRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( || get, serde_json::Value => "messages/{{id}}/attachments/{{id2}}" );
     post!( [ | add, serde_json::Value => "messages/{{id}}/attachments" ] );
     get!( || content, GraphResponse<Content> => "messages/{{id}}/attachments/{{id2}}/$value" );
     delete!( || delete, GraphResponse<Content> => "messages/{{id}}/attachments/{{id2}}" );
 
-    pub fn mail_folder(&'a self) -> MailFolderMessageAttachmentRequest<'a> {
-        MailFolderMessageAttachmentRequest::new(self.client)
+    pub fn mail_folder(&self) -> MailFolderMessageAttachmentRequest<'a, Client> {
+        MailFolderMessageAttachmentRequest::new(&self.client)
     }
 }
 
 register_client!(MailFolderMessageAttachmentRequest,);
 
-impl<'a> MailFolderMessageAttachmentRequest<'a> {
+impl<'a, Client> MailFolderMessageAttachmentRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( ||| get, serde_json::Value => "mailFolders/{{id}}/messages/{{id2}}/attachments/{{id3}}" );
     get!( ||| content, GraphResponse<Content> => "mailFolders/{{id}}/messages/{{id2}}/attachments/{{id3}}/$value" );
     post!( [ || add, serde_json::Value => "mailFolders/{{id}}/messages/{{id2}}/attachments" ] );
     delete!( ||| delete, GraphResponse<Content> => "mailFolders/{{id}}/messages/{{id2}}/attachments/{{id3}}" );
 
     fn render_child_folder_path<S: AsRef<str>>(
-        &'a self,
+        &self,
         mail_folder_id: S,
         child_folders: &[&str],
         message_id: S,
@@ -118,12 +132,12 @@ impl<'a> MailFolderMessageAttachmentRequest<'a> {
     }
 
     pub fn child_folder<S: AsRef<str>>(
-        &'a self,
+        &self,
         mail_folder_id: S,
         child_folders: &[&str],
         message_id: S,
         attachment_id: S,
-    ) -> IntoResponse<'a, serde_json::Value> {
+    ) -> IntoResponse<'a, serde_json::Value, Client> {
         self.client.builder().set_method(Method::GET);
         self.render_child_folder_path(
             mail_folder_id,
@@ -132,16 +146,16 @@ impl<'a> MailFolderMessageAttachmentRequest<'a> {
             attachment_id,
             false,
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
     pub fn child_folder_content<S: AsRef<str>>(
-        &'a self,
+        &self,
         mail_folder_id: S,
         child_folders: &[&str],
         message_id: S,
         attachment_id: S,
-    ) -> IntoResponse<'a, serde_json::Value> {
+    ) -> IntoResponse<'a, serde_json::Value, Client> {
         self.client.builder().set_method(Method::GET);
         self.render_child_folder_path(
             mail_folder_id,
@@ -150,16 +164,16 @@ impl<'a> MailFolderMessageAttachmentRequest<'a> {
             attachment_id,
             true,
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
     pub fn delete_child_folder<S: AsRef<str>>(
-        &'a self,
+        &self,
         mail_folder_id: S,
         child_folders: &[&str],
         message_id: S,
         attachment_id: S,
-    ) -> IntoResponse<'a, serde_json::Value> {
+    ) -> IntoResponse<'a, serde_json::Value, Client> {
         self.client.builder().set_method(Method::DELETE);
         self.render_child_folder_path(
             mail_folder_id,
@@ -168,13 +182,16 @@ impl<'a> MailFolderMessageAttachmentRequest<'a> {
             attachment_id,
             false,
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 }
 
 register_client!(ThreadPostAttachmentRequest,);
 
-impl<'a> ThreadPostAttachmentRequest<'a> {
+impl<'a, Client> ThreadPostAttachmentRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( || list, Collection<serde_json::Value> => "threads/{{id}}/posts/{{id2}}/attachments" );
     get!( ||| get, serde_json::Value => "threads/{{id}}/posts/{{id2}}/attachments/{{id3}}" );
     get!( ||| content, GraphResponse<Content> => "threads/{{id}}/posts/{{id2}}/attachments/{{id3}}/$value" );
@@ -183,7 +200,10 @@ impl<'a> ThreadPostAttachmentRequest<'a> {
 
 register_client!(ThreadConvoPostAttachmentRequest,);
 
-impl<'a> ThreadConvoPostAttachmentRequest<'a> {
+impl<'a, Client> ThreadConvoPostAttachmentRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( ||| list, Collection<serde_json::Value> => "conversations/{{id}}/threads/{{id2}}/posts/{{id3}}/attachments" );
     get!( |||| get, serde_json::Value => "conversations/{{id}}/threads/{{id2}}/posts/{{id3}}/attachments/{{id4}}" );
     get!( |||| content, GraphResponse<Content> => "conversations/{{id}}/threads/{{id2}}/posts/{{id3}}/attachments/{{id4}}/$value" );
@@ -1,12 +1,14 @@
 use crate::attachments::{CalendarAttachmentRequest, CalendarGroupAttachmentRequest};
-use crate::client::Graph;
-use crate::http::{GraphResponse, IntoResponse};
+use crate::http::{GraphResponse, IntoResponse, RequestClient};
 use crate::types::{collection::Collection, content::Content};
 use reqwest::Method;
 
 register_client!(CalendarRequest,);
 
-impl<'a> CalendarRequest<'a> {
+impl<'a, Client> CalendarRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( list, Collection<serde_json::Value> => "calendars" );
     get!( get_default, serde_json::Value => "calendar" );
     get!( | get, serde_json::Value => "calendars/{{id}}" );
@@ -16,27 +18,30 @@ impl<'a> CalendarRequest<'a> {
     post!( | create, serde_json::Value => "calendars" );
     delete!( | delete, GraphResponse<Content> => "calendars/{{id}}" );
 
-    pub fn attachments(&'a self) -> CalendarAttachmentRequest<'a> {
-        CalendarAttachmentRequest::new(self.client)
+    pub fn attachments(&self) -> CalendarAttachmentRequest<'a, Client> {
+        CalendarAttachmentRequest::new(&self.client)
     }
 
-    pub fn views(&self) -> CalendarViewRequest<'a> {
-        CalendarViewRequest::new(self.client)
+    pub fn views(&self) -> CalendarViewRequest<'a, Client> {
+        CalendarViewRequest::new(&self.client)
     }
 
-    pub fn groups(&self) -> CalendarGroupRequest<'a> {
-        CalendarGroupRequest::new(self.client)
+    pub fn groups(&self) -> CalendarGroupRequest<'a, Client> {
+        CalendarGroupRequest::new(&self.client)
     }
 }
 
 register_client!(CalendarViewRequest,);
 
-impl<'a> CalendarViewRequest<'a> {
+impl<'a, Client> CalendarViewRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     pub fn list_default_view(
         &self,
         start_date_time: &str,
         end_date_time: &str,
-    ) -> IntoResponse<'a, Collection<serde_json::Value>> {
+    ) -> IntoResponse<'a, Collection<serde_json::Value>, Client> {
         self.client
             .builder()
             .set_method(Method::GET)
@@ -50,7 +55,7 @@ impl<'a> CalendarViewRequest<'a> {
             .builder()
             .as_mut()
             .append_query_pair("endDateTime", end_date_time);
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
     pub fn list_view(
@@ -58,7 +63,7 @@ impl<'a> CalendarViewRequest<'a> {
         id: &str,
         start_date_time: &str,
         end_date_time: &str,
-    ) -> IntoResponse<'a, Collection<serde_json::Value>> {
+    ) -> IntoResponse<'a, Collection<serde_json::Value>, Client> {
         self.client
             .builder()
             .set_method(Method::GET)
@@ -72,7 +77,7 @@ impl<'a> CalendarViewRequest<'a> {
             .builder()
             .as_mut()
             .append_query_pair("endDateTime", end_date_time);
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
     pub fn list_default_group_view(
@@ -80,7 +85,7 @@ impl<'a> CalendarViewRequest<'a> {
         calendar_id: &str,
         start_date_time: &str,
         end_date_time: &str,
-    ) -> IntoResponse<'a, Collection<serde_json::Value>> {
+    ) -> IntoResponse<'a, Collection<serde_json::Value>, Client> {
         self.client
             .builder()
             .set_method(Method::GET)
@@ -94,7 +99,7 @@ impl<'a> CalendarViewRequest<'a> {
             .builder()
             .as_mut()
             .append_query_pair("endDateTime", end_date_time);
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
     pub fn list_group_view(
@@ -103,7 +108,7 @@ impl<'a> CalendarViewRequest<'a> {
         calendar_id: &str,
         start_date_time: &str,
         end_date_time: &str,
-    ) -> IntoResponse<'a, Collection<serde_json::Value>> {
+    ) -> IntoResponse<'a, Collection<serde_json::Value>, Client> {
         self.client
             .builder()
             .set_method(Method::GET)
@@ -123,13 +128,16 @@ impl<'a> CalendarViewRequest<'a> {
             .builder()
             .as_mut()
             .append_query_pair("endDateTime", end_date_time);
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 }
 
 register_client!(CalendarGroupRequest,);
 
-impl<'a> CalendarGroupRequest<'a> {
+impl<'a, Client> CalendarGroupRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( list, Collection<serde_json::Value> => "calendarGroups" );
     get!( | get, serde_json::Value => "calendarGroups/{{id}}" );
     get!( list_default_calendars, Collection<serde_json::Value> => "calendarGroup/calendars" );
@@ -145,7 +153,7 @@ impl<'a> CalendarGroupRequest<'a> {
     patch!( [ | update, serde_json::Value => "calendarGroups/{{id}}" ] );
     delete!( | delete, GraphResponse<Content> => "calendarGroups/{{id}}" );
 
-    pub fn attachments(&'a self) -> CalendarGroupAttachmentRequest<'a> {
-        CalendarGroupAttachmentRequest::new(self.client)
+    pub fn attachments(&self) -> CalendarGroupAttachmentRequest<'a, Client> {
+        CalendarGroupAttachmentRequest::new(&self.client)
     }
 }
@@ -0,0 +1,330 @@
+use crate::client::{AsyncGraph, Graph};
+use crate::http::{
+    download_redirect_policy, GraphRequest, HttpTransport, Middleware, RateLimiter, RequestClient,
+    RequestDefaults, ReqwestTransport, RetryPolicy, TelemetrySink,
+};
+use crate::types::prefer::{Prefer, Preference};
+use graph_error::{GraphFailure, GraphResult};
+use graph_oauth::oauth::{AzureCloud, TokenProvider};
+use reqwest::header::{HeaderMap, HeaderValue, IntoHeaderName, USER_AGENT};
+use reqwest::{Certificate, Proxy};
+use std::sync::Arc;
+use std::time::Duration;
+use url::Url;
+
+/// Applies the HTTP settings of a `GraphClientBuilder` to a blocking
+/// or async reqwest client builder, which have the same methods.
+macro_rules! apply_settings {
+    ($settings:expr, $builder:expr) => {{
+        let settings = $settings;
+        let mut builder = $builder
+            .danger_accept_invalid_certs(settings.accept_invalid_certs)
+            .danger_accept_invalid_hostnames(settings.accept_invalid_hostnames);
+        if let Some(timeout) = settings.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = settings.connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+        if let Some(max_idle) = settings.max_idle_per_host {
+            builder = builder.max_idle_per_host(max_idle);
+        }
+        for proxy in settings.proxies.iter() {
+            builder = builder.proxy(proxy.clone());
+        }
+        for certificate in settings.root_certificates.iter() {
+            builder = builder.add_root_certificate(certificate.clone());
+        }
+        builder
+    }};
+}
+
+/// Builds a `Graph` or `AsyncGraph` client with custom HTTP settings
+/// or for a national cloud.
+///
+/// The settings apply to every request made by the client including
+/// downloads, upload sessions and delta requests. An invalid setting,
+/// such as a user agent that is not a valid header value, is returned
+/// as an error from `build` and `build_async`.
+///
+/// # Example
+/// ```
+/// use graph_rs::client::GraphClientBuilder;
+/// use std::time::Duration;
+///
+/// let client = GraphClientBuilder::new()
+///     .timeout(Duration::from_secs(60))
+///     .connect_timeout(Duration::from_secs(10))
+///     .user_agent("my-app/1.0")
+///     .build("ACCESS_TOKEN")
+///     .unwrap();
+/// ```
+#[derive(Default)]
+pub struct GraphClientBuilder {
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxies: Vec<Proxy>,
+    user_agent: Option<String>,
+    default_headers: HeaderMap,
+    root_certificates: Vec<Certificate>,
+    accept_invalid_certs: bool,
+    accept_invalid_hostnames: bool,
+    max_idle_per_host: Option<usize>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limiter: Option<RateLimiter>,
+    telemetry_sinks: Vec<Arc<dyn TelemetrySink>>,
+    compression: Option<bool>,
+    prefer: Prefer,
+    middleware: Vec<Arc<dyn Middleware>>,
+    token_provider: Option<Arc<dyn TokenProvider>>,
+    transport: Option<Arc<dyn HttpTransport>>,
+    cloud: AzureCloud,
+}
+
+impl GraphClientBuilder {
+    pub fn new() -> GraphClientBuilder {
+        GraphClientBuilder::default()
+    }
+
+    /// Set the timeout for connecting, reading and writing a request.
+    pub fn timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the timeout for connecting to the server.
+    pub fn connect_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Add a proxy used for requests.
+    pub fn proxy(&mut self, proxy: Proxy) -> &mut Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Set the User-Agent header sent with each request.
+    pub fn user_agent(&mut self, user_agent: &str) -> &mut Self {
+        self.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    /// Add a header that is sent with each request.
+    pub fn default_header(&mut self, name: impl IntoHeaderName, value: HeaderValue) -> &mut Self {
+        self.default_headers.insert(name, value);
+        self
+    }
+
+    /// Set the headers that are sent with each request.
+    pub fn default_headers(&mut self, headers: HeaderMap) -> &mut Self {
+        self.default_headers = headers;
+        self
+    }
+
+    /// Add a trusted root certificate.
+    pub fn add_root_certificate(&mut self, certificate: Certificate) -> &mut Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Accept invalid certificates. This should only be used for testing.
+    pub fn danger_accept_invalid_certs(&mut self, value: bool) -> &mut Self {
+        self.accept_invalid_certs = value;
+        self
+    }
+
+    /// Accept certificates that do not match the hostname. This should
+    /// only be used for testing.
+    pub fn danger_accept_invalid_hostnames(&mut self, value: bool) -> &mut Self {
+        self.accept_invalid_hostnames = value;
+        self
+    }
+
+    /// Set the maximum number of idle connections kept per host.
+    pub fn max_idle_per_host(&mut self, max: usize) -> &mut Self {
+        self.max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Set the policy used to retry requests.
+    pub fn retry_policy(&mut self, retry_policy: RetryPolicy) -> &mut Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Limit the rate and concurrency of requests.
+    pub fn rate_limiter(&mut self, rate_limiter: RateLimiter) -> &mut Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    /// Add a sink that receives an event for each request.
+    pub fn telemetry_sink<S>(&mut self, sink: S) -> &mut Self
+    where
+        S: TelemetrySink + 'static,
+    {
+        self.telemetry_sinks.push(Arc::new(sink));
+        self
+    }
+
+    /// Ask for compressed responses and decompress them. Compression is
+    /// on by default.
+    pub fn compression(&mut self, compression: bool) -> &mut Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Add a preference to the Prefer header of every request.
+    pub fn prefer(&mut self, preference: Preference) -> &mut Self {
+        self.prefer.add(preference);
+        self
+    }
+
+    /// Add a middleware that every request goes through.
+    pub fn middleware<M>(&mut self, middleware: M) -> &mut Self
+    where
+        M: Middleware + 'static,
+    {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Set the provider of the bearer token used for requests. The token
+    /// passed to `build` or `build_async` is not used when a provider is set.
+    pub fn token_provider<T>(&mut self, token_provider: T) -> &mut Self
+    where
+        T: TokenProvider + 'static,
+    {
+        self.token_provider = Some(Arc::new(token_provider));
+        self
+    }
+
+    /// Send requests with the transport instead of reqwest. The timeout,
+    /// user agent and default headers are set on each request. The
+    /// proxies, certificates and connection settings are only used
+    /// by the reqwest transport.
+    pub fn transport<T>(&mut self, transport: T) -> &mut Self
+    where
+        T: HttpTransport + 'static,
+    {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Set the cloud that requests are sent to. Use a custom cloud
+    /// to send requests to another host such as a local server.
+    pub fn cloud(&mut self, cloud: AzureCloud) -> &mut Self {
+        self.cloud = cloud;
+        self
+    }
+
+    fn client_builder(&self) -> reqwest::ClientBuilder {
+        apply_settings!(self, reqwest::Client::builder())
+    }
+
+    fn async_client_builder(&self) -> reqwest::r#async::ClientBuilder {
+        apply_settings!(self, reqwest::r#async::Client::builder())
+    }
+
+    /// The transport set on the builder or a reqwest transport with the
+    /// settings of the builder. The reqwest transport of an async client
+    /// also has async clients with the same settings.
+    fn http_transport(&self, is_async: bool) -> GraphResult<Arc<dyn HttpTransport>> {
+        if let Some(transport) = self.transport.as_ref() {
+            return Ok(Arc::clone(transport));
+        }
+        let client = self.client_builder().build().map_err(GraphFailure::from)?;
+        let redirect_client = self
+            .client_builder()
+            .redirect(download_redirect_policy())
+            .build()
+            .map_err(GraphFailure::from)?;
+        let transport = ReqwestTransport::new(client, redirect_client);
+        if !is_async {
+            return Ok(Arc::new(transport));
+        }
+
+        let async_client = self
+            .async_client_builder()
+            .build()
+            .map_err(GraphFailure::from)?;
+        let async_redirect_client = self
+            .async_client_builder()
+            .redirect(download_redirect_policy())
+            .build()
+            .map_err(GraphFailure::from)?;
+        Ok(Arc::new(
+            transport.with_async_clients(async_client, async_redirect_client),
+        ))
+    }
+
+    /// The default headers, including the user agent, and the timeout
+    /// that are set on each request.
+    fn request_defaults(&self) -> GraphResult<RequestDefaults> {
+        let mut headers = self.default_headers.clone();
+        if let Some(user_agent) = self.user_agent.as_ref() {
+            let value = HeaderValue::from_str(user_agent.as_str()).map_err(|_| {
+                GraphFailure::invalid(&format!(
+                    "user agent. {} is not a valid header value",
+                    user_agent
+                ))
+            })?;
+            headers.insert(USER_AGENT, value);
+        }
+        Ok(RequestDefaults::new(headers, self.timeout))
+    }
+
+    fn cloud_checked(&self) -> GraphResult<AzureCloud> {
+        Url::parse(self.cloud.graph_url().as_str())?;
+        Ok(self.cloud.clone())
+    }
+
+    /// A request client with every setting of the builder. The async
+    /// client wraps the same request client so both get the same settings.
+    fn graph_request(&self, token: &str, is_async: bool) -> GraphResult<GraphRequest> {
+        let request = GraphRequest::new(self.http_transport(is_async)?);
+        request.set_request_defaults(self.request_defaults()?);
+        if let Some(retry_policy) = self.retry_policy.as_ref() {
+            request.set_retry_policy(retry_policy.clone());
+        }
+        if let Some(rate_limiter) = self.rate_limiter.as_ref() {
+            request.set_rate_limiter(rate_limiter.clone());
+        }
+        for sink in self.telemetry_sinks.iter() {
+            request.add_telemetry_sink(Arc::clone(sink));
+        }
+        if let Some(compression) = self.compression {
+            request.set_compression(compression);
+        }
+        for middleware in self.middleware.iter() {
+            request.add_middleware(Arc::clone(middleware));
+        }
+        match self.token_provider.as_ref() {
+            Some(token_provider) => request.set_token_provider(Arc::clone(token_provider)),
+            None => request.set_token(token),
+        }
+        Ok(request)
+    }
+
+    fn graph<Client>(&self, token: &str, is_async: bool) -> GraphResult<Graph<Client>>
+    where
+        Client: RequestClient + From<GraphRequest>,
+    {
+        let cloud = self.cloud_checked()?;
+        let request = Client::from(self.graph_request(token, is_async)?);
+        let client = Graph::from_request(request, cloud);
+        client.set_default_preferences(self.prefer.clone());
+        Ok(client)
+    }
+
+    /// Build a blocking client.
+    pub fn build(&self, token: &str) -> GraphResult<Graph> {
+        self.graph(token, false)
+    }
+
+    /// Build an async client.
+    pub fn build_async(&self, token: &str) -> GraphResult<AsyncGraph> {
+        self.graph(token, true)
+    }
+}
@@ -4,23 +4,30 @@ use crate::attachments::{
 use crate::calendar::CalendarRequest;
 use crate::contacts::ContactsRequest;
 use crate::drive::DriveRequest;
-use crate::http::{GraphRequest, IntoResponse};
+use crate::http::{
+    AsyncGraphRequest, DeltaState, DeltaSync, GraphFuture, GraphRequest, HttpTransport,
+    IntoResponse, Middleware, PreparedRequest, RateLimiter, RequestClient, RetryPolicy,
+    TelemetrySink,
+};
 use crate::http::{GraphRequestBuilder, GraphResponse};
 use crate::mail::MailRequest;
 use crate::onenote::OnenoteRequest;
+use crate::types::batch::{BatchRequest, BatchResponse, BatchResponseBody};
+use crate::types::prefer::{Prefer, Preference};
 use crate::types::{
     boolresponse::BoolResponse, collection::Collection, content::Content, delta::DeltaRequest,
 };
 use crate::url::GraphUrl;
-use crate::{GRAPH_URL, GRAPH_URL_BETA};
-use graph_error::GraphFailure;
-use graph_oauth::oauth::{AccessToken, OAuth};
+use graph_error::{GraphFailure, GraphResult};
+use graph_oauth::oauth::{AccessToken, AzureCloud, OAuth, TokenProvider};
 use handlebars::*;
-use reqwest::header::{HeaderValue, ACCEPT};
+use reqwest::header::{HeaderValue, ACCEPT, CONTENT_TYPE};
 use reqwest::Method;
 use std::cell::{Cell, RefCell, RefMut};
 use std::convert::TryFrom;
+use std::rc::Rc;
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 pub enum Ident {
@@ -49,14 +56,17 @@ impl Default for Ident {
     }
 }
 
-pub struct Graph {
-    request: RefCell<GraphRequest>,
-    builder: RefCell<GraphRequestBuilder>,
-    registry: RefCell<Handlebars>,
-    is_v1: Cell<bool>,
+pub struct Graph<Client = GraphRequest> {
+    request: Client,
+    cloud: AzureCloud,
+    last_url: Mutex<GraphUrl>,
+    prefer: Mutex<Prefer>,
 }
 
-impl<'a> Graph {
+/// The async Graph client.
+pub type AsyncGraph = Graph<AsyncGraphRequest>;
+
+impl Graph<GraphRequest> {
     /// Create a new client with an access token.
     ///
     /// # Example
@@ -74,75 +84,295 @@ impl<'a> Graph {
     ///     .root_children()
     ///     .json()?;
     /// ```
-    pub fn new(token: &str) -> Graph {
-        let mut request = GraphRequest::default();
-        request.set_token(token);
-        Graph {
-            request: RefCell::new(request),
-            builder: RefCell::new(GraphRequestBuilder::new(
-                GraphUrl::from_str(GRAPH_URL).unwrap(),
-            )),
-            registry: RefCell::new(Handlebars::new()),
-            is_v1: Cell::new(true),
+    pub fn new(token: &str) -> Graph<GraphRequest> {
+        Graph::with_client(GraphRequest::default(), token, AzureCloud::Global)
+    }
+
+    /// Create a new client that sends requests using the given transport.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let client = Graph::with_transport("ACCESS_TOKEN", ReqwestTransport::default());
+    /// ```
+    pub fn with_transport<T>(token: &str, transport: T) -> Graph<GraphRequest>
+    where
+        T: HttpTransport + 'static,
+    {
+        Graph::with_client(
+            GraphRequest::new(Arc::new(transport)),
+            token,
+            AzureCloud::Global,
+        )
+    }
+
+    /// Create a new client that gets the bearer token for each request
+    /// from a token provider.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let provider = OAuthTokenProvider::new(oauth, GrantType::AuthorizationCode);
+    /// let client = Graph::with_token_provider(provider);
+    /// ```
+    pub fn with_token_provider<T>(token_provider: T) -> Graph<GraphRequest>
+    where
+        T: TokenProvider + 'static,
+    {
+        let client = Graph::new("");
+        client.set_token_provider(token_provider);
+        client
+    }
+
+    /// Send a request that was built using `prepare`.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let prepared = client.v1().me().drive().get_item("ITEM_ID").prepare()?;
+    /// let response: GraphResponse<serde_json::Value> = client.send_prepared(&prepared)?;
+    /// ```
+    pub fn send_prepared<T>(&self, prepared: &PreparedRequest) -> GraphResult<GraphResponse<T>>
+    where
+        for<'de> T: serde::Deserialize<'de>,
+    {
+        let request = GraphRequestBuilder::try_from(prepared)?;
+        self.set_last_url(request.url().clone());
+        self.request.execute(request)
+    }
+
+    /// Run a delta sync starting from the saved state. The client's
+    /// default preferences are sent with every page.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let state = client.v1().me().drive().delta().delta_state()?;
+    /// let mut sync = client.delta_sync(state);
+    /// let events: Vec<DeltaEvent> = sync.by_ref().collect::<GraphResult<_>>()?;
+    /// let state = sync.into_state();
+    /// ```
+    pub fn delta_sync(&self, state: DeltaState) -> DeltaSync {
+        DeltaSync::new(self.request.clone(), state).default_preferences(&self.default_preferences())
+    }
+}
+
+impl Graph<AsyncGraphRequest> {
+    /// Create a new async client with an access token. Requests
+    /// return futures and delta requests return streams.
+    ///
+    /// # Example
+    /// ```
+    /// use graph_rs::client::Graph;
+    ///
+    /// let client = Graph::new_async("ACCESS_TOKEN");
+    /// ```
+    /// ```rust,ignore
+    /// let future = client
+    ///     .v1()
+    ///     .me()
+    ///     .drive()
+    ///     .root_children()
+    ///     .send()
+    ///     .map(|response| println!("{:#?}", response.value()));
+    /// tokio::run(future.map_err(|err| println!("{:#?}", err)));
+    /// ```
+    pub fn new_async(token: &str) -> Graph<AsyncGraphRequest> {
+        Graph::with_client(AsyncGraphRequest::default(), token, AzureCloud::Global)
+    }
+
+    /// Create a new async client that sends requests using the given transport.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let client = Graph::new_async_with_transport("ACCESS_TOKEN", ReqwestTransport::default_async());
+    /// ```
+    pub fn new_async_with_transport<T>(token: &str, transport: T) -> Graph<AsyncGraphRequest>
+    where
+        T: HttpTransport + 'static,
+    {
+        Graph::with_client(
+            AsyncGraphRequest::new(Arc::new(transport)),
+            token,
+            AzureCloud::Global,
+        )
+    }
+
+    /// Create a new async client that gets the bearer token for each
+    /// request from a token provider.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let provider = OAuthTokenProvider::new(oauth, GrantType::AuthorizationCode);
+    /// let client = Graph::new_async_with_token_provider(provider);
+    /// ```
+    pub fn new_async_with_token_provider<T>(token_provider: T) -> Graph<AsyncGraphRequest>
+    where
+        T: TokenProvider + 'static,
+    {
+        let client = Graph::new_async("");
+        client.set_token_provider(token_provider);
+        client
+    }
+
+    /// Send a request that was built using `prepare`.
+    pub fn send_prepared<T>(&self, prepared: &PreparedRequest) -> GraphFuture<GraphResponse<T>>
+    where
+        for<'de> T: serde::Deserialize<'de>,
+        T: 'static + Send,
+    {
+        match GraphRequestBuilder::try_from(prepared) {
+            Ok(request) => {
+                self.set_last_url(request.url().clone());
+                self.request.execute(request)
+            },
+            Err(err) => Box::new(futures::future::err(err)),
         }
     }
+}
 
-    /// Use the v1.0 Graph API
-    pub fn v1(&'a self) -> Identify<'a> {
-        self.is_v1.set(true);
-        self.builder().as_mut().replace(GRAPH_URL).unwrap();
-        Identify { client: &self }
+impl<Client> Graph<Client>
+where
+    Client: AsRef<GraphRequest>,
+{
+    /// Set the provider of the bearer token used for requests. The
+    /// token is refreshed and the request sent again after a 401 response.
+    pub fn set_token_provider<T>(&self, token_provider: T)
+    where
+        T: TokenProvider + 'static,
+    {
+        self.request
+            .as_ref()
+            .set_token_provider(Arc::new(token_provider));
     }
 
-    /// Use the Graph beta API
-    pub fn beta(&'a self) -> Identify<'a> {
-        self.is_v1.set(false);
-        self.builder().as_mut().replace(GRAPH_URL_BETA).unwrap();
-        Identify { client: &self }
+    /// Set the policy used to retry requests that are throttled or fail
+    /// with a 503 or 504 status. By default requests are attempted up to
+    /// 4 times. Use `RetryPolicy::none()` to turn off retries.
+    pub fn set_retry_policy(&self, retry_policy: RetryPolicy) {
+        self.request.as_ref().set_retry_policy(retry_policy);
     }
 
-    /// Check if the current host is v1.0.
-    pub fn is_v1(&self) -> bool {
-        self.is_v1.get()
+    /// Limit the rate and concurrency of requests so they wait instead
+    /// of being throttled. Use a clone of the same rate limiter for every
+    /// client of a tenant to share the limits.
+    pub fn set_rate_limiter(&self, rate_limiter: RateLimiter) {
+        self.request.as_ref().set_rate_limiter(rate_limiter);
     }
 
-    /// Check if the current host is beta.
-    pub fn is_beta(&self) -> bool {
-        !self.is_v1.get()
+    /// Ask for gzip or deflate compressed responses and decompress them as
+    /// they are read. Compression is on by default.
+    pub fn set_compression(&self, compression: bool) {
+        self.request.as_ref().set_compression(compression);
     }
 
-    pub fn ident(&self) -> Ident {
-        self.request.borrow().ident()
+    /// Add a sink that receives an event with the timing, status and
+    /// diagnostic headers of each request. Use `LogSink` to log events.
+    pub fn add_telemetry_sink<S>(&self, sink: S)
+    where
+        S: TelemetrySink + 'static,
+    {
+        self.request.as_ref().add_telemetry_sink(Arc::new(sink));
+    }
+
+    /// Add a middleware that every request goes through. Middleware
+    /// runs in the order it is added, before the retry policy and auth.
+    pub fn add_middleware<M>(&self, middleware: M)
+    where
+        M: Middleware + 'static,
+    {
+        self.request.as_ref().add_middleware(Arc::new(middleware));
+    }
+}
+
+impl<'a, Client> Graph<Client>
+where
+    Client: RequestClient,
+{
+    pub(crate) fn with_client(request: Client, token: &str, cloud: AzureCloud) -> Graph<Client> {
+        request.set_token(token);
+        Graph::from_request(request, cloud)
+    }
+
+    /// A client using the token that is already set on the request client.
+    pub(crate) fn from_request(request: Client, cloud: AzureCloud) -> Graph<Client> {
+        let last_url = GraphUrl::from_str(cloud.graph_url().as_str()).unwrap();
+        Graph {
+            request,
+            cloud,
+            last_url: Mutex::new(last_url),
+            prefer: Default::default(),
+        }
+    }
+
+    /// Use the v1.0 Graph API
+    pub fn v1(&'a self) -> Identify<'a, Client> {
+        Identify {
+            client: self,
+            is_v1: true,
+        }
+    }
+
+    /// Use the Graph beta API
+    pub fn beta(&'a self) -> Identify<'a, Client> {
+        Identify {
+            client: self,
+            is_v1: false,
+        }
     }
 
     /// Set the access token used for requests.
     pub fn set_token(&self, token: &str) {
-        self.request().set_token(token);
+        self.request.set_token(token);
     }
 
-    pub(crate) fn request(&self) -> RefMut<GraphRequest> {
-        self.request.borrow_mut()
+    pub(crate) fn request(&self) -> &Client {
+        &self.request
     }
 
-    pub(crate) fn builder(&self) -> RefMut<GraphRequestBuilder> {
-        self.builder.borrow_mut()
+    /// Add a preference to the Prefer header of every request built by
+    /// the client. A preference set on a request replaces a default
+    /// preference with the same name.
+    pub fn add_default_preference(&self, preference: Preference) {
+        self.prefer.lock().unwrap().add(preference);
     }
 
-    pub(crate) fn take_builder(&self) -> GraphRequestBuilder {
-        self.builder.replace(GraphRequestBuilder::new(
-            GraphUrl::from_str(GRAPH_URL).unwrap(),
-        ))
+    /// The preferences sent with every request.
+    pub fn default_preferences(&self) -> Prefer {
+        self.prefer.lock().unwrap().clone()
     }
 
-    pub(crate) fn registry(&self) -> RefMut<Handlebars> {
-        self.registry.borrow_mut()
+    /// Replace the preferences sent with every request.
+    pub fn set_default_preferences(&self, prefer: Prefer) {
+        *self.prefer.lock().unwrap() = prefer;
+    }
+
+    /// The cloud that requests are sent to.
+    pub fn cloud(&self) -> &AzureCloud {
+        &self.cloud
     }
 
+    /// Inspect the url of the most recently built request.
+    ///
+    /// Each request chain keeps its own url so this is only meant for
+    /// checking requests built on the current thread. Use `url_ref` on
+    /// the request itself when the client is shared.
     pub fn url_ref<F>(&self, f: F)
     where
         F: Fn(&GraphUrl),
     {
-        f(&self.builder.borrow().as_ref())
+        f(&self.last_url.lock().unwrap())
+    }
+}
+
+impl<Client> Graph<Client> {
+    /// A new request with the default preferences.
+    fn request_builder(&self, url: GraphUrl) -> GraphRequestBuilder {
+        let mut builder = GraphRequestBuilder::new(url);
+        self.prefer.lock().unwrap().apply(&mut builder.headers);
+        builder
+    }
+
+    fn set_last_url(&self, url: GraphUrl) {
+        if let Ok(mut last_url) = self.last_url.lock() {
+            *last_url = url;
+        }
     }
 }
 
@@ -154,31 +384,13 @@ impl From<&str> for Graph {
 
 impl From<String> for Graph {
     fn from(token: String) -> Self {
-        let mut request = GraphRequest::default();
-        request.set_token(token.as_ref());
-        Graph {
-            request: RefCell::new(request),
-            builder: RefCell::new(GraphRequestBuilder::new(
-                GraphUrl::from_str(GRAPH_URL).unwrap(),
-            )),
-            registry: RefCell::new(Handlebars::new()),
-            is_v1: Cell::new(true),
-        }
+        Graph::new(token.as_str())
     }
 }
 
 impl From<&AccessToken> for Graph {
     fn from(token: &AccessToken) -> Self {
-        let mut request = GraphRequest::default();
-        request.set_token(token.bearer_token());
-        Graph {
-            request: RefCell::new(request),
-            builder: RefCell::new(GraphRequestBuilder::new(
-                GraphUrl::from_str(GRAPH_URL).unwrap(),
-            )),
-            registry: RefCell::new(Handlebars::new()),
-            is_v1: Cell::new(true),
-        }
+        Graph::new(token.bearer_token())
     }
 }
 
@@ -191,64 +403,210 @@ impl TryFrom<&OAuth> for Graph {
     }
 }
 
-pub struct Identify<'a> {
-    client: &'a Graph,
+/// The state of a single request chain.
+///
+/// A new chain is started each time an ident such as `me()` or `users(id)`
+/// is selected. Each step of the chain splits off its own url, method,
+/// body and headers so one `Graph`, or one part of a chain, can be used
+/// to build many requests at the same time.
+pub struct RequestChain<'a, Client = GraphRequest> {
+    client: &'a Graph<Client>,
+    registry: Rc<RefCell<Handlebars>>,
+    base: GraphUrl,
+    builder: RefCell<GraphRequestBuilder>,
+    ident: Ident,
+    is_taken: Cell<bool>,
 }
 
-impl<'a> Identify<'a> {
+impl<'a, Client> RequestChain<'a, Client>
+where
+    Client: RequestClient,
+{
+    fn new(client: &'a Graph<Client>, ident: Ident, is_v1: bool) -> RequestChain<'a, Client> {
+        let host = if is_v1 {
+            client.cloud.graph_url()
+        } else {
+            client.cloud.graph_url_beta()
+        };
+        let base = GraphUrl::from_str(host.as_str()).unwrap();
+        RequestChain {
+            client,
+            registry: Rc::new(RefCell::new(Handlebars::new())),
+            builder: RefCell::new(client.request_builder(base.clone())),
+            base,
+            ident,
+            is_taken: Cell::new(false),
+        }
+    }
+
+    /// Moves the current request into a new chain and resets this
+    /// chain to the url it started with.
+    pub(crate) fn split(&self) -> RequestChain<'a, Client> {
+        let builder = self
+            .builder
+            .replace(self.client.request_builder(self.base.clone()));
+        RequestChain {
+            client: self.client,
+            registry: Rc::clone(&self.registry),
+            base: builder.url().clone(),
+            builder: RefCell::new(builder),
+            ident: self.ident,
+            is_taken: Cell::new(false),
+        }
+    }
+
+    pub fn ident(&self) -> Ident {
+        self.ident
+    }
+
+    pub(crate) fn request(&self) -> &'a Client {
+        self.client.request()
+    }
+
+    pub(crate) fn base(&self) -> &GraphUrl {
+        &self.base
+    }
+
+    pub(crate) fn builder(&self) -> RefMut<GraphRequestBuilder> {
+        self.builder.borrow_mut()
+    }
+
+    pub(crate) fn take_builder(&self) -> GraphRequestBuilder {
+        let builder = self
+            .builder
+            .replace(self.client.request_builder(self.base.clone()));
+        self.is_taken.set(true);
+        self.client.set_last_url(builder.url().clone());
+        builder
+    }
+
+    pub(crate) fn registry(&self) -> RefMut<Handlebars> {
+        self.registry.borrow_mut()
+    }
+
+    pub fn url_ref<F>(&self, f: F)
+    where
+        F: Fn(&GraphUrl),
+    {
+        f(&self.builder.borrow().as_ref())
+    }
+}
+
+impl<'a, Client> RequestChain<'a, Client> {
+    /// Keep the url of a request that was built but not sent
+    /// so it can be checked using `Graph::url_ref`.
+    pub(crate) fn record_url(&self) {
+        if !self.is_taken.get() {
+            self.client
+                .set_last_url(self.builder.borrow().url().clone());
+        }
+    }
+}
+
+pub struct Identify<'a, Client = GraphRequest> {
+    client: &'a Graph<Client>,
+    is_v1: bool,
+}
+
+impl<'a, Client> Identify<'a, Client>
+where
+    Client: RequestClient,
+{
+    fn chain(&self, ident: Ident) -> RequestChain<'a, Client> {
+        RequestChain::new(self.client, ident, self.is_v1)
+    }
+
+    /// Check if the current host is v1.0.
+    pub fn is_v1(&self) -> bool {
+        self.is_v1
+    }
+
+    /// Check if the current host is beta.
+    pub fn is_beta(&self) -> bool {
+        !self.is_v1
+    }
+
     /// Select the me endpoint.
-    pub fn me(&self) -> IdentMe<'a> {
-        self.client.request().set_ident(Ident::Me);
-        IdentMe::new("", self.client)
+    pub fn me(&self) -> IdentMe<'a, Client> {
+        IdentMe::new("", &self.chain(Ident::Me))
     }
 
     /// Select the drives endpoint.
-    pub fn drives<S: AsRef<str>>(&self, id: S) -> IdentDrives<'a> {
-        self.client.request().set_ident(Ident::Drives);
-        IdentDrives::new(id.as_ref(), self.client)
+    pub fn drives<S: AsRef<str>>(&self, id: S) -> IdentDrives<'a, Client> {
+        IdentDrives::new(id.as_ref(), &self.chain(Ident::Drives))
     }
 
     /// Select the sites endpoint.
-    pub fn sites<S: AsRef<str>>(&self, id: S) -> IdentSites<'a> {
-        self.client.request().set_ident(Ident::Sites);
-        IdentSites::new(id.as_ref(), self.client)
+    pub fn sites<S: AsRef<str>>(&self, id: S) -> IdentSites<'a, Client> {
+        IdentSites::new(id.as_ref(), &self.chain(Ident::Sites))
     }
 
     /// Select the groups endpoint.
-    pub fn groups<S: AsRef<str>>(&self, id: S) -> IdentGroups<'a> {
-        self.client.request().set_ident(Ident::Groups);
-        IdentGroups::new(id.as_ref(), self.client)
+    pub fn groups<S: AsRef<str>>(&self, id: S) -> IdentGroups<'a, Client> {
+        IdentGroups::new(id.as_ref(), &self.chain(Ident::Groups))
     }
 
     /// Select the group lifecycle policies endpoint.
     pub fn group_lifecycle_policies<S: AsRef<str>>(
         &self,
         id: S,
-    ) -> GroupLifecyclePolicyRequest<'a> {
-        GroupLifecyclePolicyRequest::new(id.as_ref(), self.client)
+    ) -> GroupLifecyclePolicyRequest<'a, Client> {
+        GroupLifecyclePolicyRequest::new(id.as_ref(), &self.chain(Ident::Me))
     }
 
     /// Select the users endpoint.
-    pub fn users<S: AsRef<str>>(&self, id: S) -> IdentUsers<'a> {
-        self.client.request().set_ident(Ident::Users);
-        IdentUsers::new(id.as_ref(), self.client)
+    pub fn users<S: AsRef<str>>(&self, id: S) -> IdentUsers<'a, Client> {
+        IdentUsers::new(id.as_ref(), &self.chain(Ident::Users))
     }
 
     pub fn batch<B: serde::Serialize>(
         &self,
         batch: &B,
-    ) -> IntoResponse<'a, DeltaRequest<serde_json::Value>> {
-        self.client
+    ) -> IntoResponse<'a, DeltaRequest<serde_json::Value>, Client> {
+        let client = self.chain(Ident::Me);
+        client
             .builder()
             .set_method(Method::POST)
             .header(ACCEPT, HeaderValue::from_static("application/json"))
             .set_body(serde_json::to_string(batch).unwrap());
-        render_path!(
-            self.client,
-            "$batch",
-            &serde_json::json!({})
-        );
-        IntoResponse::new(self.client)
+        render_path!(client, "$batch", &serde_json::json!({}));
+        IntoResponse::new(&client)
+    }
+}
+
+impl<'a> Identify<'a, GraphRequest> {
+    /// Send a JSON batch and return the responses for each step.
+    ///
+    /// Batches with more than 20 steps are sent as several batches. Steps
+    /// that depend on each other are always sent in the same batch.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let mut batch = BatchRequest::new();
+    /// batch
+    ///     .add(BatchStep::get("1", "/me/drive"))
+    ///     .add(BatchStep::get("2", "/me/drive/root/children").depends_on("1"));
+    ///
+    /// let response = client.v1().send_batch(&batch)?;
+    /// let children: Collection<serde_json::Value> = response.json("2")?;
+    /// ```
+    pub fn send_batch(&self, batch: &BatchRequest) -> GraphResult<BatchResponse> {
+        let base = self.chain(Ident::Me).builder().to_url();
+        let mut responses = Vec::new();
+        for chunk in batch.chunks()? {
+            let client = self.chain(Ident::Me);
+            client
+                .builder()
+                .set_method(Method::POST)
+                .header(ACCEPT, HeaderValue::from_static("application/json"))
+                .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+                .set_body(serde_json::to_string(&chunk)?);
+            render_path!(client, "$batch");
+            let response: GraphResponse<BatchResponseBody> =
+                client.request().execute(client.take_builder())?;
+            responses.extend(response.into_value().responses);
+        }
+        Ok(BatchResponse::new(batch, responses, &base))
     }
 }
 
@@ -258,18 +616,27 @@ register_ident_client!(IdentSites,);
 register_ident_client!(IdentGroups,);
 register_ident_client!(IdentUsers,);
 
-impl<'a> IdentMe<'a> {
+impl<'a, Client> IdentMe<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( get, serde_json::Value => "me" );
     get!( list_events, Collection<serde_json::Value> => "me/events" );
     get!( settings, serde_json::Value => "me/settings" );
     patch!( [ update_settings, serde_json::Value => "me/settings" ] );
 }
 
-impl<'a> IdentDrives<'a> {
+impl<'a, Client> IdentDrives<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( get, serde_json::Value => "drive/{{RID}}" );
 }
 
-impl<'a> IdentSites<'a> {
+impl<'a, Client> IdentSites<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( get, serde_json::Value => "sites/{{RID}}" );
     get!( list_subsites, Collection<serde_json::Value> => "sites/{{RID}}/sites" );
     get!( root, serde_json::Value => "sites/root" );
@@ -278,16 +645,16 @@ impl<'a> IdentSites<'a> {
     get!( | item_analytics, serde_json::Value => "sites/{{RID}}/items/{{id}}/analytics" );
     get!( | list_item_versions, serde_json::Value => "sites/{{RID}}/items/{{id}}/versions" );
 
-    pub fn lists(&'a self) -> SiteListRequest<'a> {
-        SiteListRequest::new(self.client)
+    pub fn lists(&self) -> SiteListRequest<'a, Client> {
+        SiteListRequest::new(&self.client)
     }
 
     pub fn activities_by_interval(
-        &'a self,
+        &self,
         start: &str,
         end: Option<&str>,
         interval: &str,
-    ) -> IntoResponse<'a, serde_json::Value> {
+    ) -> IntoResponse<'a, serde_json::Value, Client> {
         self.client.builder().set_method(Method::GET);
         if let Some(end) = end {
             render_path!(self.client, &format!(
@@ -305,25 +672,31 @@ impl<'a> IdentSites<'a> {
                 )
             );
         }
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 }
 
 register_client!(SiteListRequest,);
 
-impl<'a> SiteListRequest<'a> {
+impl<'a, Client> SiteListRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( list, Collection<serde_json::Value> => "sites/{{RID}}/lists" );
     get!( | get, serde_json::Value => "sites/{{RID}}/lists/{{id}}" );
     post!( [ create, serde_json::Value => "sites/{{RID}}/lists" ] );
 
-    pub fn items(&'a self) -> SiteListItemRequest<'a> {
-        SiteListItemRequest::new(self.client)
+    pub fn items(&self) -> SiteListItemRequest<'a, Client> {
+        SiteListItemRequest::new(&self.client)
     }
 }
 
 register_client!(SiteListItemRequest,);
 
-impl<'a> SiteListItemRequest<'a> {
+impl<'a, Client> SiteListItemRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( | list, Collection<serde_json::Value> => "sites/{{RID}}/lists/{{id}}/items" );
     get!( || get, serde_json::Value => "sites/{{RID}}/lists/{{id}}/items/{{id2}}" );
     get!( || analytics, serde_json::Value => "sites/{{RID}}/lists/{{id}}/items/{{id2}}/analytics" );
@@ -334,7 +707,10 @@ impl<'a> SiteListItemRequest<'a> {
     delete!( || delete, GraphResponse<Content> => "sites/{{RID}}/lists/{{id}}/items/{{id2}}" );
 }
 
-impl<'a> IdentGroups<'a> {
+impl<'a, Client> IdentGroups<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( list, Collection<serde_json::Value> => "groups" );
     get!( get, serde_json::Value => "groups/{{RID}}" );
     get!( delta, DeltaRequest<Collection<serde_json::Value>> => "groups/delta" );
@@ -365,16 +741,16 @@ impl<'a> IdentGroups<'a> {
     delete!( | remove_member, GraphResponse<Content> => "groups/{{RID}}/members/{{id}}/$ref" );
     delete!( | remove_owner, GraphResponse<Content> => "groups/{{RID}}/owners/{{id}}/$ref" );
 
-    pub fn conversations(&self) -> GroupConversationRequest<'a> {
-        GroupConversationRequest::new(self.client)
+    pub fn conversations(&self) -> GroupConversationRequest<'a, Client> {
+        GroupConversationRequest::new(&self.client)
     }
 
-    pub fn conversation_posts(&'a self) -> GroupConversationPostRequest<'a> {
-        GroupConversationPostRequest::new(self.client)
+    pub fn conversation_posts(&self) -> GroupConversationPostRequest<'a, Client> {
+        GroupConversationPostRequest::new(&self.client)
     }
 
-    pub fn thread_posts(&'a self) -> GroupThreadPostRequest<'a> {
-        GroupThreadPostRequest::new(self.client)
+    pub fn thread_posts(&self) -> GroupThreadPostRequest<'a, Client> {
+        GroupThreadPostRequest::new(&self.client)
     }
 }
 
@@ -383,7 +759,10 @@ register_client!(
     co => "conversations",
 );
 
-impl<'a> GroupConversationRequest<'a> {
+impl<'a, Client> GroupConversationRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( list, Collection<serde_json::Value> => "groups/{{RID}}/{{co}}" );
     get!( | list_threads, Collection<serde_json::Value> => "groups/{{RID}}/{{co}}/{{id}}/threads" );
     get!( list_accepted_senders, Collection<serde_json::Value> => "groups/{{RID}}/acceptedSenders" );
@@ -393,49 +772,58 @@ impl<'a> GroupConversationRequest<'a> {
     post!( [ create_accepted_sender, GraphResponse<Content> => "groups/{{RID}}/acceptedSenders/$ref" ] );
     delete!( | delete, GraphResponse<Content> => "groups/{{RID}}/{{co}}/{{id}}" );
 
-    pub fn thread_posts(&'a self) -> GroupThreadPostRequest<'a> {
-        GroupThreadPostRequest::new(self.client)
+    pub fn thread_posts(&self) -> GroupThreadPostRequest<'a, Client> {
+        GroupThreadPostRequest::new(&self.client)
     }
 
-    pub fn conversation_posts(&'a self) -> GroupConversationPostRequest<'a> {
-        GroupConversationPostRequest::new(self.client)
+    pub fn conversation_posts(&self) -> GroupConversationPostRequest<'a, Client> {
+        GroupConversationPostRequest::new(&self.client)
     }
 }
 
 register_client!(GroupThreadPostRequest,);
 
-impl<'a> GroupThreadPostRequest<'a> {
+impl<'a, Client> GroupThreadPostRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( | list, Collection<serde_json::Value> => "groups/{{RID}}/threads/{{id}}/posts" );
     get!( || get, serde_json::Value => "groups/{{RID}}/threads/{{id}}/posts/{{id2}}" );
     post!( [ || reply, GraphResponse<Content> => "groups/{{RID}}/threads/{{id}}/posts/{{id2}}/reply" ] );
     post!( [ || forward, GraphResponse<Content> => "groups/{{RID}}/threads/{{id}}/posts/{{id2}}/forward" ] );
 
-    pub fn attachments(&'a self) -> ThreadPostAttachmentRequest<'a> {
+    pub fn attachments(&self) -> ThreadPostAttachmentRequest<'a, Client> {
         render_path!(self.client, "groups/{{RID}}");
-        ThreadPostAttachmentRequest::new(self.client)
+        ThreadPostAttachmentRequest::new(&self.client)
     }
 }
 
 register_client!(GroupConversationPostRequest,);
 
-impl<'a> GroupConversationPostRequest<'a> {
+impl<'a, Client> GroupConversationPostRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( || list, Collection<serde_json::Value> => "groups/{{RID}}/conversations/{{id}}/threads/{{id2}}/posts" );
     get!( ||| get, serde_json::Value => "groups/{{RID}}/conversations/{{id}}/threads/{{id2}}/posts/{{id3}}" );
     post!( [ ||| reply, GraphResponse<Content> => "groups/{{RID}}/conversations/{{id}}/threads/{{id2}}/posts/{{id3}}/reply" ] );
     post!( [ ||| forward, GraphResponse<Content> => "groups/{{RID}}/conversations/{{id}}/threads/{{id2}}/posts/{{id3}}/forward" ] );
 
-    pub fn attachments(&'a self) -> ThreadConvoPostAttachmentRequest<'a> {
+    pub fn attachments(&self) -> ThreadConvoPostAttachmentRequest<'a, Client> {
         render_path!(self.client, "groups/{{RID}}");
-        ThreadConvoPostAttachmentRequest::new(self.client)
+        ThreadConvoPostAttachmentRequest::new(&self.client)
     }
 }
 
-impl<'a> IdentUsers<'a> {
+impl<'a, Client> IdentUsers<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( get, serde_json::Value => "users/{{RID}}" );
     get!( settings, serde_json::Value => "users/{{RID}}/settings" );
     get!( list, Collection<serde_json::Value> => "users" );
     get!( list_events, Collection<serde_json::Value> => "users/{{RID}}/events" );
-    get!( delta, DeltaRequest<Collection<serde_json::Value>> => "users" );
+    get!( delta, DeltaRequest<Collection<serde_json::Value>> => "users/delta" );
     get!( | list_joined_group_photos, Collection<serde_json::Value> => "users/{{RID}}/joinedGroups/{{id}}/photos" );
     post!( [ create, serde_json::Value => "users" ] );
     patch!( [ update, GraphResponse<Content> => "users/{{RID}}" ] );
@@ -449,7 +837,10 @@ register_ident_client!(
     ()
 );
 
-impl<'a> GroupLifecyclePolicyRequest<'a> {
+impl<'a, Client> GroupLifecyclePolicyRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( list, Collection<serde_json::Value> => "{{glp}}" );
     get!( get, Collection<serde_json::Value> => "{{glp}}/{{RID}}" );
     post!( [ create, serde_json::Value => "{{glp}}" ] );
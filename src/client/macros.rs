@@ -3,12 +3,15 @@ macro_rules! register_client {
     ( $name:ident, $($helper:ident => $value:expr,)* ) => {
         $( register_helper!($helper, $value); )*
 
-        pub struct $name<'a> {
-            client: &'a Graph,
+        pub struct $name<'a, Client = crate::http::GraphRequest> {
+            client: crate::client::RequestChain<'a, Client>,
         }
 
-        impl<'a> $name<'a> {
-            pub fn new(client: &'a Graph) -> $name<'a> {
+        impl<'a, Client> $name<'a, Client>
+        where
+            Client: crate::http::RequestClient,
+        {
+            pub fn new(client: &crate::client::RequestChain<'a, Client>) -> $name<'a, Client> {
 
                 $(
                     client.registry()
@@ -16,19 +19,22 @@ macro_rules! register_client {
                 )*
 
                 $name {
-                    client,
+                    client: client.split(),
                 }
             }
         }
     };
 
     ( $name:ident, $($helper:ident => $value:expr, $value2:expr, $identity:expr,)* ) => {
-        pub struct $name<'a> {
-            client: &'a Graph,
+        pub struct $name<'a, Client = crate::http::GraphRequest> {
+            client: crate::client::RequestChain<'a, Client>,
         }
 
-        impl<'a> $name<'a> {
-            pub fn new(client: &'a Graph) -> $name<'a> {
+        impl<'a, Client> $name<'a, Client>
+        where
+            Client: crate::http::RequestClient,
+        {
+            pub fn new(client: &crate::client::RequestChain<'a, Client>) -> $name<'a, Client> {
                 let ident = client.ident();
                 $(
                     client.registry().register_helper(
@@ -52,7 +58,7 @@ macro_rules! register_client {
                 )*
 
                 $name {
-                    client,
+                    client: client.split(),
                 }
             }
         }
@@ -64,12 +70,15 @@ macro_rules! register_ident_client {
     ( $name:ident, $($helper:ident => $value:expr,)* ()) => {
         $( register_helper!($helper, $value); )*
 
-        pub struct $name<'a> {
-            client: &'a Graph,
+        pub struct $name<'a, Client = crate::http::GraphRequest> {
+            client: crate::client::RequestChain<'a, Client>,
         }
 
-        impl<'a> $name<'a> {
-            pub fn new(id: &str, client: &'a Graph) -> $name<'a> {
+        impl<'a, Client> $name<'a, Client>
+        where
+            Client: crate::http::RequestClient,
+        {
+            pub fn new(id: &str, client: &crate::client::RequestChain<'a, Client>) -> $name<'a, Client> {
                 $(
                     client.registry()
                         .register_helper(stringify!($helper), Box::new($helper));
@@ -89,7 +98,7 @@ macro_rules! register_ident_client {
                     }));
 
                 $name {
-                    client,
+                    client: client.split(),
                 }
             }
         }
@@ -98,13 +107,16 @@ macro_rules! register_ident_client {
     ( $name:ident, $($helper:ident => $value:expr,)* ) => {
         $( register_helper!($helper, $value); )*
 
-        pub struct $name<'a> {
-            client: &'a Graph,
+        pub struct $name<'a, Client = crate::http::GraphRequest> {
+            client: crate::client::RequestChain<'a, Client>,
             id: String,
         }
 
-        impl<'a> $name<'a> {
-            pub fn new(id: &str, client: &'a Graph) -> $name<'a> {
+        impl<'a, Client> $name<'a, Client>
+        where
+            Client: crate::http::RequestClient,
+        {
+            pub fn new(id: &str, client: &crate::client::RequestChain<'a, Client>) -> $name<'a, Client> {
                 $(
                     client.registry()
                         .register_helper(stringify!($helper), Box::new($helper));
@@ -124,7 +136,7 @@ macro_rules! register_ident_client {
                     }));
 
                 $name {
-                    client,
+                    client: client.split(),
                     id: id.into(),
                 }
             }
@@ -144,34 +156,34 @@ macro_rules! register_ident_client {
                 }
             }
 
-            pub fn drive(&'a self) -> DriveRequest<'a> {
+            pub fn drive(&self) -> DriveRequest<'a, Client> {
                 self.set_path();
-                DriveRequest::new(self.client)
+                DriveRequest::new(&self.client)
             }
 
-            pub fn mail(&'a self) -> MailRequest<'a> {
+            pub fn mail(&self) -> MailRequest<'a, Client> {
                 self.set_path();
-                MailRequest::new(self.client)
+                MailRequest::new(&self.client)
             }
 
-            pub fn calendar(&'a self) -> CalendarRequest<'a> {
+            pub fn calendar(&self) -> CalendarRequest<'a, Client> {
                 self.set_path();
-                CalendarRequest::new(self.client)
+                CalendarRequest::new(&self.client)
             }
 
-            pub fn onenote(&'a self) -> OnenoteRequest<'a> {
+            pub fn onenote(&self) -> OnenoteRequest<'a, Client> {
                 self.set_path();
-                OnenoteRequest::new(self.client)
+                OnenoteRequest::new(&self.client)
             }
 
-            pub fn contacts(&'a self) -> ContactsRequest<'a> {
+            pub fn contacts(&self) -> ContactsRequest<'a, Client> {
                 self.set_path();
-                ContactsRequest::new(self.client)
+                ContactsRequest::new(&self.client)
             }
 
-            pub fn attachments(&'a self) -> AttachmentRequest<'a> {
+            pub fn attachments(&self) -> AttachmentRequest<'a, Client> {
                 self.set_path();
-                AttachmentRequest::new(self.client)
+                AttachmentRequest::new(&self.client)
             }
         }
     };
@@ -230,7 +242,7 @@ macro_rules! render_path {
 #[macro_use]
 macro_rules! register_method {
     ( $name:ident, $T:ty => $template:expr, $m:expr ) => {
-      pub fn $name(&'a self) -> IntoResponse<'a, $T> {
+      pub fn $name(&self) -> IntoResponse<'a, $T, Client> {
         self.client.builder()
             .set_method($m);
 
@@ -238,12 +250,12 @@ macro_rules! register_method {
             self.client,
             $template
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
       }
     };
 
     ( | $name:ident, $T:ty => $template:expr, $m:expr ) => {
-      pub fn $name<S: AsRef<str>>(&'a self, id: S) -> IntoResponse<'a, $T> {
+      pub fn $name<S: AsRef<str>>(&self, id: S) -> IntoResponse<'a, $T, Client> {
         self.client.builder()
             .set_method($m);
 
@@ -252,12 +264,12 @@ macro_rules! register_method {
             $template,
             &serde_json::json!({ "id": id.as_ref() })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
       }
     };
 
     ( || $name:ident, $T:ty => $template:expr, $m:expr ) => {
-      pub fn $name<S: AsRef<str>>(&'a self, id: S, id2: S) -> IntoResponse<'a, $T> {
+      pub fn $name<S: AsRef<str>>(&self, id: S, id2: S) -> IntoResponse<'a, $T, Client> {
         self.client.builder()
             .set_method($m);
 
@@ -266,12 +278,12 @@ macro_rules! register_method {
             $template,
             &serde_json::json!({ "id": id.as_ref(), "id2": id2.as_ref() })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
       }
     };
 
     ( ||| $name:ident, $T:ty => $template:expr, $m:expr ) => {
-      pub fn $name<S: AsRef<str>>(&'a self, id: S, id2: S, id3: S) -> IntoResponse<'a, $T> {
+      pub fn $name<S: AsRef<str>>(&self, id: S, id2: S, id3: S) -> IntoResponse<'a, $T, Client> {
         self.client.builder()
             .set_method($m);
 
@@ -280,12 +292,12 @@ macro_rules! register_method {
             $template,
             &serde_json::json!({ "id": id.as_ref(), "id2": id2.as_ref(), "id3": id3.as_ref() })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
       }
     };
 
     ( |||| $name:ident, $T:ty => $template:expr, $m:expr ) => {
-      pub fn $name<S: AsRef<str>>(&'a self, id: S, id2: S, id3: S, id4: S) -> IntoResponse<'a, $T> {
+      pub fn $name<S: AsRef<str>>(&self, id: S, id2: S, id3: S, id4: S) -> IntoResponse<'a, $T, Client> {
         self.client.builder()
             .set_method($m);
 
@@ -299,12 +311,12 @@ macro_rules! register_method {
                 "id4": id4.as_ref()
             })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
       }
     };
 
     ( [ $name:ident, $T:ty => $template:expr, $m:expr ] ) => {
-      pub fn $name<B: serde::Serialize>(&'a self, body: &B) -> IntoResponse<'a, $T> {
+      pub fn $name<B: serde::Serialize>(&self, body: &B) -> IntoResponse<'a, $T, Client> {
         self.client.builder()
             .set_method($m)
             .set_body(serde_json::to_string_pretty(body).unwrap());
@@ -313,12 +325,12 @@ macro_rules! register_method {
             self.client,
             $template
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
       }
     };
 
     ( [ | $name:ident, $T:ty => $template:expr, $m:expr ] ) => {
-      pub fn $name<S: AsRef<str>, B: serde::Serialize>(&'a self, id: S, body: &B) -> IntoResponse<'a, $T> {
+      pub fn $name<S: AsRef<str>, B: serde::Serialize>(&self, id: S, body: &B) -> IntoResponse<'a, $T, Client> {
         self.client.builder()
             .set_method($m)
             .set_body(serde_json::to_string_pretty(body).unwrap());
@@ -328,12 +340,12 @@ macro_rules! register_method {
             $template,
             &serde_json::json!({ "id": id.as_ref() })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
       }
     };
 
     ( [ || $name:ident, $T:ty => $template:expr, $m:expr ] ) => {
-      pub fn $name<S: AsRef<str>, B: serde::Serialize>(&'a self, id: S, id2: S, body: &B) -> IntoResponse<'a, $T> {
+      pub fn $name<S: AsRef<str>, B: serde::Serialize>(&self, id: S, id2: S, body: &B) -> IntoResponse<'a, $T, Client> {
         self.client.builder()
             .set_method($m)
             .set_body(serde_json::to_string_pretty(body).unwrap());
@@ -343,12 +355,12 @@ macro_rules! register_method {
             $template,
             &serde_json::json!({ "id": id.as_ref(), "id2": id2.as_ref() })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
       }
     };
 
     ( [ ||| $name:ident, $T:ty => $template:expr, $m:expr ] ) => {
-      pub fn $name<S: AsRef<str>, B: serde::Serialize>(&'a self, id: S, id2: S, id3: S, body: &B) -> IntoResponse<'a, $T> {
+      pub fn $name<S: AsRef<str>, B: serde::Serialize>(&self, id: S, id2: S, id3: S, body: &B) -> IntoResponse<'a, $T, Client> {
         self.client.builder()
             .set_method($m)
             .set_body(serde_json::to_string_pretty(body).unwrap());
@@ -358,7 +370,7 @@ macro_rules! register_method {
             $template,
             &serde_json::json!({ "id": id.as_ref(), "id2": id2.as_ref(), "id3": id3.as_ref() })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
       }
     };
 }
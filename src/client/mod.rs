@@ -1,5 +1,7 @@
 #[macro_use]
 pub mod macros;
+mod builder;
 mod graph;
 
+pub use builder::*;
 pub use graph::*;
@@ -1,5 +1,4 @@
-use crate::client::Graph;
-use crate::http::{GraphResponse, IntoResponse};
+use crate::http::{GraphResponse, IntoResponse, RequestClient};
 use crate::types::{collection::Collection, content::Content, delta::DeltaRequest};
 use handlebars::*;
 use reqwest::Method;
@@ -10,7 +9,10 @@ register_client!(
     cf => "contactfolders",
 );
 
-impl<'a> ContactsRequest<'a> {
+impl<'a, Client> ContactsRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( delta, DeltaRequest<Collection<serde_json::Value>> => "{{ct}}/delta" );
     get!( list, Collection<serde_json::Value> => "{{ct}}" );
     get!( | get, serde_json::Value => "{{ct}}/{{id}}" );
@@ -18,14 +20,17 @@ impl<'a> ContactsRequest<'a> {
     patch!( [ | update, serde_json::Value => "{{ct}}/{{id}}" ] );
     delete!( | delete, GraphResponse<Content> => "{{ct}}/{{id}}" );
 
-    pub fn contacts_folder(&'a self) -> ContactsFolderRequest<'a> {
-        ContactsFolderRequest::new(self.client)
+    pub fn contacts_folder(&self) -> ContactsFolderRequest<'a, Client> {
+        ContactsFolderRequest::new(&self.client)
     }
 }
 
 register_client!(ContactsFolderRequest,);
 
-impl<'a> ContactsFolderRequest<'a> {
+impl<'a, Client> ContactsFolderRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( delta, DeltaRequest<Collection<serde_json::Value>> => "{{cf}}/delta" );
     get!( | get, serde_json::Value => "{{cf}}/{{id}}" );
     get!( | list_child_folders, Collection<serde_json::Value> => "{{cf}}/{{id}}/childFolders" );
@@ -33,14 +38,17 @@ impl<'a> ContactsFolderRequest<'a> {
     patch!( [ | update, serde_json::Value => "{{cf}}/{{id}}" ] );
     delete!( | delete, GraphResponse<Content> => "{{cf}}/{{id}}" );
 
-    pub fn contacts(&'a self) -> ContactsFolderContactsRequest<'a> {
-        ContactsFolderContactsRequest::new(self.client)
+    pub fn contacts(&self) -> ContactsFolderContactsRequest<'a, Client> {
+        ContactsFolderContactsRequest::new(&self.client)
     }
 }
 
 register_client!(ContactsFolderContactsRequest,);
 
-impl<'a> ContactsFolderContactsRequest<'a> {
+impl<'a, Client> ContactsFolderContactsRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( | delta, DeltaRequest<Collection<serde_json::Value>> => "{{cf}}/{{id}}/{{ct}}/delta" );
     get!( | list, Collection<serde_json::Value> => "{{cf}}/{{id}}/{{ct}}" );
     post!( [ | create, serde_json::Value => "{{cf}}/{{id}}" ] );
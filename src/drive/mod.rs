@@ -8,6 +8,10 @@
 //!     .root_children()
 //!     .send();
 //! ```
+//!
+//! Drive requests return typed values such as `Drive`, `DriveItem` and
+//! `Collection<DriveItem>`. Use `json` to get any other type,
+//! such as the raw `serde_json::Value`.
 
 mod request;
 
@@ -1,10 +1,15 @@
 use crate::client::*;
 use crate::http::{
-    DownloadClient, GraphRequestType, GraphResponse, IntoResponse, UploadSessionClient,
+    GraphRequestType, GraphResponse, IntoResponse, RequestClient, UploadSessionClient,
 };
 use crate::types::collection::Collection;
 use crate::types::content::Content;
 use crate::types::delta::DeltaRequest;
+use crate::types::drive::Drive;
+use crate::types::driveitem::DriveItem;
+use crate::types::itemactivity::ItemActivity;
+use crate::types::permission::Permission;
+use crate::types::thumbnail::{Thumbnail, ThumbnailSet};
 use graph_error::{GraphFailure, GraphRsError};
 use handlebars::*;
 use reqwest::header::{HeaderValue, CONTENT_LENGTH};
@@ -40,68 +45,71 @@ register_client!(
     drive_root_path => "drive/root", "root", Ident::Drives,
 );
 
-impl<'a> DriveRequest<'a> {
-    get!( drive, serde_json::Value => "{{drive_root}}" );
-    get!( root, serde_json::Value => "{{drive_root}}/root" );
-    get!( recent, Collection<serde_json::Value> => "{{drive_root}}/recent" );
-    get!( delta, DeltaRequest<Collection<serde_json::Value>> => "{{drive_root}}/root/delta" );
-    get!( root_children, Collection<serde_json::Value> => "{{drive_root}}/root/children" );
-    get!( drive_activity, Collection<serde_json::Value> => "{{drive_root}}/activities" );
-    get!( thumbnails, Collection<serde_json::Value> => "{{drive_item}}/thumbnails" );
-    get!( shared_with_me, Collection<serde_json::Value> => "{{drive_root}}/sharedWithMe" );
-    get!( | special_folder, serde_json::Value => "{{drive_root}}/special/{{id}}" );
-    get!( special_documents, serde_json::Value => "{{drive_root}}/special/documents" );
-    get!( special_documents_children, Collection<serde_json::Value> => "{{drive_root}}/special/documents/children" );
-    get!( special_photos, serde_json::Value => "{{drive_root}}/special/photos" );
-    get!( special_photos_children, Collection<serde_json::Value> => "{{drive_root}}/special/photos/children" );
-    get!( special_camera_roll, serde_json::Value => "{{drive_root}}/special/cameraroll" );
-    get!( special_camera_roll_children, Collection<serde_json::Value> => "{{drive_root}}/special/cameraroll/children" );
-    get!( special_app_root, serde_json::Value => "{{drive_root}}/special/approot" );
-    get!( special_app_root_children, Collection<serde_json::Value> => "{{drive_root}}/special/approot/children" );
-    get!( special_music, serde_json::Value => "{{drive_root}}/special/music" );
-    get!( special_music_children, Collection<serde_json::Value> => "{{drive_root}}/special/music/children" );
+impl<'a, Client> DriveRequest<'a, Client>
+where
+    Client: RequestClient,
+{
+    get!( drive, Drive => "{{drive_root}}" );
+    get!( root, DriveItem => "{{drive_root}}/root" );
+    get!( recent, Collection<DriveItem> => "{{drive_root}}/recent" );
+    get!( delta, DeltaRequest<Collection<DriveItem>> => "{{drive_root}}/root/delta" );
+    get!( root_children, Collection<DriveItem> => "{{drive_root}}/root/children" );
+    get!( drive_activity, Collection<ItemActivity> => "{{drive_root}}/activities" );
+    get!( thumbnails, Collection<ThumbnailSet> => "{{drive_item}}/thumbnails" );
+    get!( shared_with_me, Collection<DriveItem> => "{{drive_root}}/sharedWithMe" );
+    get!( | special_folder, DriveItem => "{{drive_root}}/special/{{id}}" );
+    get!( special_documents, DriveItem => "{{drive_root}}/special/documents" );
+    get!( special_documents_children, Collection<DriveItem> => "{{drive_root}}/special/documents/children" );
+    get!( special_photos, DriveItem => "{{drive_root}}/special/photos" );
+    get!( special_photos_children, Collection<DriveItem> => "{{drive_root}}/special/photos/children" );
+    get!( special_camera_roll, DriveItem => "{{drive_root}}/special/cameraroll" );
+    get!( special_camera_roll_children, Collection<DriveItem> => "{{drive_root}}/special/cameraroll/children" );
+    get!( special_app_root, DriveItem => "{{drive_root}}/special/approot" );
+    get!( special_app_root_children, Collection<DriveItem> => "{{drive_root}}/special/approot/children" );
+    get!( special_music, DriveItem => "{{drive_root}}/special/music" );
+    get!( special_music_children, Collection<DriveItem> => "{{drive_root}}/special/music/children" );
 
     pub fn list_children<S: AsRef<str>>(
-        &'a self,
+        &self,
         id: S,
-    ) -> IntoResponse<'a, Collection<serde_json::Value>> {
+    ) -> IntoResponse<'a, Collection<DriveItem>, Client> {
         self.client.builder().set_method(Method::GET);
         render_path!(
             self.client,
             &template(id.as_ref(), "children"),
             &json!({ "id": encode(id.as_ref()) })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
     pub fn item_activity<S: AsRef<str>>(
-        &'a self,
+        &self,
         id: S,
-    ) -> IntoResponse<'a, Collection<serde_json::Value>> {
+    ) -> IntoResponse<'a, Collection<ItemActivity>, Client> {
         self.client.builder().set_method(Method::GET);
         render_path!(
             self.client,
             &template(id.as_ref(), "activities"),
             &json!({ "id": encode(id.as_ref()) })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
-    pub fn get_item<S: AsRef<str>>(&'a self, id: S) -> IntoResponse<'a, serde_json::Value> {
+    pub fn get_item<S: AsRef<str>>(&self, id: S) -> IntoResponse<'a, DriveItem, Client> {
         self.client.builder().set_method(Method::GET);
         render_path!(
             self.client,
             template(id.as_ref(), "").as_str(),
             &json!({ "id": encode(id.as_ref()) })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
     pub fn update<S: AsRef<str>, B: serde::Serialize>(
-        &'a self,
+        &self,
         id: S,
         body: &B,
-    ) -> IntoResponse<'a, serde_json::Value> {
+    ) -> IntoResponse<'a, DriveItem, Client> {
         let body = serde_json::to_string(body);
         if let Ok(body) = body {
             self.client
@@ -109,31 +117,31 @@ impl<'a> DriveRequest<'a> {
                 .set_method(Method::PATCH)
                 .set_body(body);
         } else if let Err(e) = body {
-            return IntoResponse::new_error(self.client, GraphFailure::from(e));
+            return IntoResponse::new_error(&self.client, GraphFailure::from(e));
         }
         render_path!(
             self.client,
             template(id.as_ref(), "").as_str(),
             &json!({"id": encode(id.as_ref()) })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
-    pub fn delete<S: AsRef<str>>(&'a self, id: S) -> IntoResponse<'a, GraphResponse<Content>> {
+    pub fn delete<S: AsRef<str>>(&self, id: S) -> IntoResponse<'a, GraphResponse<Content>, Client> {
         self.client.builder().set_method(Method::DELETE);
         render_path!(
             self.client,
             template(id.as_ref(), "").as_str(),
             &json!({"id": encode(id.as_ref()) })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
     pub fn create_folder<S: AsRef<str>, B: serde::Serialize>(
-        &'a self,
+        &self,
         id: S,
         body: &B,
-    ) -> IntoResponse<'a, serde_json::Value> {
+    ) -> IntoResponse<'a, DriveItem, Client> {
         let body = serde_json::to_string(body);
         if let Ok(body) = body {
             self.client
@@ -141,7 +149,7 @@ impl<'a> DriveRequest<'a> {
                 .set_method(Method::POST)
                 .set_body(body);
         } else if let Err(e) = body {
-            return IntoResponse::new_error(self.client, GraphFailure::from(e));
+            return IntoResponse::new_error(&self.client, GraphFailure::from(e));
         }
 
         if id.as_ref().is_empty() {
@@ -153,14 +161,14 @@ impl<'a> DriveRequest<'a> {
                 &json!({ "id": encode(id.as_ref()) })
             );
         }
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
     pub fn copy<S: AsRef<str>, B: serde::Serialize>(
-        &'a self,
+        &self,
         id: S,
         body: &B,
-    ) -> IntoResponse<'a, GraphResponse<Content>> {
+    ) -> IntoResponse<'a, GraphResponse<Content>, Client> {
         let body = serde_json::to_string(body);
         if let Ok(body) = body {
             self.client
@@ -168,35 +176,48 @@ impl<'a> DriveRequest<'a> {
                 .set_method(Method::POST)
                 .set_body(body);
         } else if let Err(e) = body {
-            return IntoResponse::new_error(self.client, GraphFailure::from(e));
+            return IntoResponse::new_error(&self.client, GraphFailure::from(e));
         }
         render_path!(
             self.client,
             template(id.as_ref(), "copy").as_str(),
             &json!({"id": encode(id.as_ref()) })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
+    }
+
+    pub fn list_permissions<S: AsRef<str>>(
+        &self,
+        id: S,
+    ) -> IntoResponse<'a, Collection<Permission>, Client> {
+        self.client.builder().set_method(Method::GET);
+        render_path!(
+            self.client,
+            template(id.as_ref(), "permissions").as_str(),
+            &json!({ "id": encode(id.as_ref()) })
+        );
+        IntoResponse::new(&self.client)
     }
 
     pub fn list_versions<S: AsRef<str>>(
         &self,
         id: S,
-    ) -> IntoResponse<'a, Collection<serde_json::Value>> {
+    ) -> IntoResponse<'a, Collection<serde_json::Value>, Client> {
         self.client.builder().set_method(Method::GET);
         render_path!(
             self.client,
             template(id.as_ref(), "versions").as_str(),
             &json!({ "id": encode(id.as_ref()) })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
     pub fn single_thumbnail<S: AsRef<str>>(
-        &'a self,
+        &self,
         id: S,
         thumb_id: &str,
         size: &str,
-    ) -> IntoResponse<'a, serde_json::Value> {
+    ) -> IntoResponse<'a, Thumbnail, Client> {
         self.client.builder().set_method(Method::GET);
         render_path!(
             self.client,
@@ -207,15 +228,15 @@ impl<'a> DriveRequest<'a> {
                "size": size
             })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
     pub fn thumbnail_binary<S: AsRef<str>>(
-        &'a self,
+        &self,
         id: S,
         thumb_id: &str,
         size: &str,
-    ) -> IntoResponse<'a, Vec<u8>> {
+    ) -> IntoResponse<'a, Vec<u8>, Client> {
         self.client.builder().set_method(Method::GET);
         render_path!(
             self.client,
@@ -226,45 +247,43 @@ impl<'a> DriveRequest<'a> {
                "size": size
             })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
     pub fn upload_replace<S: AsRef<str>, P: AsRef<Path>>(
-        &'a self,
+        &self,
         id: S,
         file: P,
-    ) -> IntoResponse<'a, serde_json::Value> {
-        let file = File::open(file).map_err(GraphFailure::from);
-        if let Err(err) = file {
-            return IntoResponse::new_error(self.client, err);
+    ) -> IntoResponse<'a, DriveItem, Client> {
+        if let Err(err) = File::open(file.as_ref()) {
+            return IntoResponse::new_error(&self.client, GraphFailure::from(err));
         }
 
         self.client
             .builder()
             .set_method(Method::PUT)
-            .set_body(file.unwrap());
+            .set_body_file(file);
         render_path!(
             self.client,
             template(id.as_ref(), "content").as_str(),
             &json!({"id": encode(id.as_ref()) })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
     pub fn upload_new<S: AsRef<str>, P: AsRef<Path>>(
-        &'a self,
+        &self,
         id: S,
         file: P,
-    ) -> IntoResponse<'a, serde_json::Value> {
+    ) -> IntoResponse<'a, DriveItem, Client> {
         if id.as_ref().starts_with(':') {
-            let file = File::open(file).map_err(GraphFailure::from);
-            if let Err(err) = file {
-                return IntoResponse::new_error(self.client, err);
+            if let Err(err) = File::open(file.as_ref()) {
+                return IntoResponse::new_error(&self.client, GraphFailure::from(err));
             }
             self.client
                 .builder()
                 .set_method(Method::PUT)
-                .set_body(file.unwrap());
+                .set_body_file(file);
             render_path!(
                 self.client,
                 template(id.as_ref(), "content").as_str(),
@@ -273,12 +292,12 @@ impl<'a> DriveRequest<'a> {
         } else {
             let name = file.as_ref().file_name();
             if name.is_none() {
-                return IntoResponse::new_error(self.client, GraphFailure::invalid("file_name"));
+                return IntoResponse::new_error(&self.client, GraphFailure::invalid("file_name"));
             }
             let name = name.unwrap().to_str();
             if name.is_none() {
                 return IntoResponse::new_error(
-                    self.client,
+                    &self.client,
                     GraphFailure::internal(GraphRsError::FileNameInvalidUTF8),
                 );
             }
@@ -291,23 +310,22 @@ impl<'a> DriveRequest<'a> {
                 })
             );
 
-            let file = File::open(file).map_err(GraphFailure::from);
-            if let Err(err) = file {
-                return IntoResponse::new_error(self.client, err);
+            if let Err(err) = File::open(file.as_ref()) {
+                return IntoResponse::new_error(&self.client, GraphFailure::from(err));
             }
             self.client
                 .builder()
                 .set_method(Method::PUT)
-                .set_body(file.unwrap());
+                .set_body_file(file);
         }
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
     pub fn restore_version<S: AsRef<str>>(
-        &'a self,
+        &self,
         id: S,
         version_id: S,
-    ) -> IntoResponse<'a, GraphResponse<Content>> {
+    ) -> IntoResponse<'a, GraphResponse<Content>, Client> {
         self.client.builder().set_method(Method::POST);
         render_path!(
             self.client,
@@ -317,15 +335,15 @@ impl<'a> DriveRequest<'a> {
                 "version_id": version_id.as_ref(),
             })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
     pub fn upload_session<S: AsRef<str>, P: AsRef<Path>, B: serde::Serialize>(
-        &'a self,
+        &self,
         id: S,
         file: P,
         body: &B,
-    ) -> IntoResponse<'a, UploadSessionClient> {
+    ) -> IntoResponse<'a, UploadSessionClient, Client> {
         let body = serde_json::to_string(body);
         if let Ok(body) = body {
             self.client
@@ -334,21 +352,21 @@ impl<'a> DriveRequest<'a> {
                 .set_upload_session(file)
                 .set_body(body);
         } else if let Err(e) = body {
-            return IntoResponse::new_error(self.client, GraphFailure::from(e));
+            return IntoResponse::new_error(&self.client, GraphFailure::from(e));
         }
         render_path!(
             self.client,
             template(id.as_ref(), "createUploadSession").as_str(),
             &json!({ "id": encode(id.as_ref()) })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
     pub fn preview<S: AsRef<str>, B: serde::Serialize>(
-        &'a self,
+        &self,
         id: S,
         body: Option<&B>,
-    ) -> IntoResponse<'a, serde_json::Value> {
+    ) -> IntoResponse<'a, serde_json::Value, Client> {
         if let Some(body) = body {
             let body = serde_json::to_string(body);
             if let Ok(body) = body {
@@ -357,7 +375,7 @@ impl<'a> DriveRequest<'a> {
                     .set_method(Method::POST)
                     .set_body(body);
             } else if let Err(e) = body {
-                return IntoResponse::new_error(self.client, GraphFailure::from(e));
+                return IntoResponse::new_error(&self.client, GraphFailure::from(e));
             }
         } else {
             self.client
@@ -370,24 +388,23 @@ impl<'a> DriveRequest<'a> {
             template(id.as_ref(), "preview").as_str(),
             &json!({ "id": encode(id.as_ref()) })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
-    pub fn content<S: AsRef<str>>(&'a self, id: S) -> IntoResponse<'a, GraphResponse<Content>> {
+    pub fn content<S: AsRef<str>>(
+        &self,
+        id: S,
+    ) -> IntoResponse<'a, GraphResponse<Content>, Client> {
         render_path!(
             self.client,
             template(id.as_ref(), "content").as_str(),
             &json!({ "id": encode(id.as_ref()) })
         );
         self.client.builder().set_method(Method::GET);
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
-    pub fn download<S: AsRef<str>, P: AsRef<Path>>(
-        &'a self,
-        id: S,
-        directory: P,
-    ) -> DownloadClient {
+    pub fn download<S: AsRef<str>, P: AsRef<Path>>(&self, id: S, directory: P) -> Client::Download {
         render_path!(
             self.client,
             template(id.as_ref(), "content").as_str(),
@@ -401,7 +418,10 @@ impl<'a> DriveRequest<'a> {
         self.client.request().download(self.client.take_builder())
     }
 
-    pub fn check_out<S: AsRef<str>>(&'a self, id: S) -> IntoResponse<'a, GraphResponse<Content>> {
+    pub fn check_out<S: AsRef<str>>(
+        &self,
+        id: S,
+    ) -> IntoResponse<'a, GraphResponse<Content>, Client> {
         render_path!(
             self.client,
             template(id.as_ref(), "checkout").as_str(),
@@ -411,14 +431,14 @@ impl<'a> DriveRequest<'a> {
             .builder()
             .set_method(Method::POST)
             .header(CONTENT_LENGTH, HeaderValue::from(0));
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
     pub fn check_in<S: AsRef<str>, B: serde::Serialize>(
-        &'a self,
+        &self,
         id: S,
         body: &B,
-    ) -> IntoResponse<'a, GraphResponse<Content>> {
+    ) -> IntoResponse<'a, GraphResponse<Content>, Client> {
         render_path!(
             self.client,
             template(id.as_ref(), "checkin").as_str(),
@@ -432,16 +452,16 @@ impl<'a> DriveRequest<'a> {
                 .set_method(Method::POST)
                 .set_body(body);
         } else if let Err(e) = body {
-            return IntoResponse::new_error(self.client, GraphFailure::from(e));
+            return IntoResponse::new_error(&self.client, GraphFailure::from(e));
         }
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
     pub fn move_item<S: AsRef<str>, B: serde::Serialize>(
-        &'a self,
+        &self,
         id: S,
         body: &B,
-    ) -> IntoResponse<'a, serde_json::Value> {
+    ) -> IntoResponse<'a, DriveItem, Client> {
         let body = serde_json::to_string(body);
         if let Ok(body) = body {
             self.client
@@ -449,23 +469,23 @@ impl<'a> DriveRequest<'a> {
                 .set_method(Method::PATCH)
                 .set_body(body);
         } else if let Err(e) = body {
-            return IntoResponse::new_error(self.client, GraphFailure::from(e));
+            return IntoResponse::new_error(&self.client, GraphFailure::from(e));
         }
         render_path!(
             self.client,
             template(id.as_ref(), "").as_str(),
             &json!({ "id": id.as_ref() })
         );
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 
     pub fn activities_by_interval<S: AsRef<str>>(
-        &'a self,
+        &self,
         id: S,
         start: &str,
         end: Option<&str>,
         interval: &str,
-    ) -> IntoResponse<'a, serde_json::Value> {
+    ) -> IntoResponse<'a, serde_json::Value, Client> {
         self.client.builder().set_method(Method::GET);
         if let Some(end) = end {
             let interval = format!(
@@ -492,6 +512,6 @@ impl<'a> DriveRequest<'a> {
                 })
             );
         }
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 }
@@ -0,0 +1,276 @@
+use crate::http::{GraphRequest, GraphRequestBuilder, GraphResponse, RetryPolicy};
+use crate::types::asyncjobstatus::AsyncJobStatus;
+use graph_error::{GraphFailure, GraphResult, GraphRsError};
+use reqwest::header::LOCATION;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// Stops an `AsyncJobPoller` that is waiting on another thread.
+/// Clones of a handle cancel the same poller.
+#[derive(Debug, Clone, Default)]
+pub struct CancelHandle {
+    inner: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl CancelHandle {
+    pub fn new() -> CancelHandle {
+        CancelHandle::default()
+    }
+
+    pub fn cancel(&self) {
+        let (cancelled, condvar) = &*self.inner;
+        *cancelled.lock().unwrap() = true;
+        condvar.notify_all();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        *self.inner.0.lock().unwrap()
+    }
+
+    /// Sleep for the duration or until cancelled. Returns
+    /// true if cancelled.
+    fn sleep(&self, duration: Duration) -> bool {
+        let (cancelled, condvar) = &*self.inner;
+        let deadline = Instant::now() + duration;
+        let mut guard = cancelled.lock().unwrap();
+        while !*guard {
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            guard = condvar.wait_timeout(guard, deadline - now).unwrap().0;
+        }
+        *guard
+    }
+}
+
+/// Follows the monitor url of a long running operation, such as copying
+/// a drive item or a OneNote page, until the job is completed or failed.
+///
+/// The monitor url is requested without an access token. Between requests
+/// the poller waits for the Retry-After header of the monitor response or
+/// an exponential backoff.
+///
+/// # Example
+/// ```rust,ignore
+/// let response = client.v1()
+///     .me()
+///     .drive()
+///     .copy("ITEM_ID", &serde_json::json!({ "name": "copy.txt" }))
+///     .send()?;
+///
+/// let mut poller = response
+///     .async_job(&client)?
+///     .timeout(Duration::from_secs(5 * 60))
+///     .on_progress(|status| println!("{:?}%", status.percentage_complete()));
+///
+/// let item: GraphResponse<serde_json::Value> = poller.wait_for_resource()?;
+/// ```
+pub struct AsyncJobPoller {
+    client: GraphRequest,
+    monitor_url: Url,
+    resource_base: Option<Url>,
+    base_delay: Duration,
+    max_delay: Duration,
+    timeout: Option<Duration>,
+    cancel: CancelHandle,
+    on_progress: Option<Box<dyn FnMut(&AsyncJobStatus) + Send>>,
+}
+
+impl AsyncJobPoller {
+    pub fn new(client: GraphRequest, monitor_url: Url) -> AsyncJobPoller {
+        AsyncJobPoller {
+            client,
+            monitor_url,
+            resource_base: None,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+            timeout: None,
+            cancel: CancelHandle::new(),
+            on_progress: None,
+        }
+    }
+
+    /// A poller for the monitor url in the Location header of a 202
+    /// response. Resources are fetched relative to the url of the request.
+    pub fn from_response<T>(
+        client: GraphRequest,
+        response: &GraphResponse<T>,
+    ) -> GraphResult<AsyncJobPoller> {
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .ok_or_else(|| GraphFailure::not_found("Location header with the monitor url"))?
+            .to_str()?;
+        let mut poller = AsyncJobPoller::new(client, Url::parse(location)?);
+        poller.resource_base = AsyncJobPoller::resource_base(response.url());
+        Ok(poller)
+    }
+
+    pub fn monitor_url(&self) -> &Url {
+        &self.monitor_url
+    }
+
+    /// Wait `base` after the first request and double the wait after
+    /// each request up to `max`. Defaults to 1 and 30 seconds.
+    pub fn delay(mut self, base: Duration, max: Duration) -> AsyncJobPoller {
+        self.base_delay = base;
+        self.max_delay = max;
+        self
+    }
+
+    /// Stop waiting with an error after the timeout. By default
+    /// the poller waits until the job is completed or failed.
+    pub fn timeout(mut self, timeout: Duration) -> AsyncJobPoller {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Called with the status returned by each request to the monitor url.
+    pub fn on_progress<F>(mut self, on_progress: F) -> AsyncJobPoller
+    where
+        F: FnMut(&AsyncJobStatus) + Send + 'static,
+    {
+        self.on_progress = Some(Box::new(on_progress));
+        self
+    }
+
+    /// Set the url that the resource id is added to when fetching the
+    /// resource, such as `https://graph.microsoft.com/v1.0/me/drive/items`.
+    pub fn resource_url(mut self, base: Url) -> AsyncJobPoller {
+        self.resource_base = Some(base);
+        self
+    }
+
+    /// A handle that can cancel the poller from another thread.
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.cancel.clone()
+    }
+
+    /// Request the status of the job once.
+    pub fn status(&self) -> GraphResult<AsyncJobStatus> {
+        self.poll().map(|(status, _)| status)
+    }
+
+    /// Poll the monitor url until the job is completed. A failed
+    /// job returns `GraphRsError::AsyncJobFailed`.
+    pub fn wait(&mut self) -> GraphResult<AsyncJobStatus> {
+        let start = Instant::now();
+        let mut attempt = 0;
+        loop {
+            if self.cancel.is_cancelled() {
+                return Err(GraphFailure::internal(GraphRsError::AsyncJobCancelled));
+            }
+
+            let (status, retry_after) = self.poll()?;
+            if let Some(on_progress) = self.on_progress.as_mut() {
+                on_progress(&status);
+            }
+            if status.is_completed() {
+                return Ok(status);
+            }
+            if status.is_failed() {
+                let description = status
+                    .status_description()
+                    .clone()
+                    .or_else(|| status.status().clone())
+                    .unwrap_or_default();
+                return Err(GraphFailure::internal(GraphRsError::AsyncJobFailed {
+                    description,
+                }));
+            }
+
+            attempt += 1;
+            let mut delay = retry_after.unwrap_or_else(|| self.backoff(attempt));
+            if let Some(timeout) = self.timeout {
+                let elapsed = start.elapsed();
+                if elapsed >= timeout {
+                    return Err(GraphFailure::internal(GraphRsError::AsyncJobTimedOut {
+                        timeout,
+                    }));
+                }
+                delay = delay.min(timeout - elapsed);
+            }
+            if self.cancel.sleep(delay) {
+                return Err(GraphFailure::internal(GraphRsError::AsyncJobCancelled));
+            }
+        }
+    }
+
+    /// Wait until the job is completed and then get the resource
+    /// it created using the resource location or resource id.
+    pub fn wait_for_resource<T>(&mut self) -> GraphResult<GraphResponse<T>>
+    where
+        for<'de> T: serde::Deserialize<'de>,
+    {
+        let status = self.wait()?;
+        let url = match status.resource_location() {
+            Some(location) => Url::parse(location.as_str())?,
+            None => {
+                let id = status
+                    .resource_id()
+                    .as_ref()
+                    .ok_or_else(|| GraphFailure::not_found("resource id of the async job"))?;
+                let mut url = self
+                    .resource_base
+                    .clone()
+                    .ok_or_else(|| GraphFailure::not_found("resource url of the async job"))?;
+                url.path_segments_mut()
+                    .map_err(|_| GraphFailure::invalid("resource url of the async job"))?
+                    .pop_if_empty()
+                    .push(id.as_str());
+                url
+            },
+        };
+        self.client.execute(GraphRequestBuilder::from(url))
+    }
+
+    fn poll(&self) -> GraphResult<(AsyncJobStatus, Option<Duration>)> {
+        // The monitor url does not require an Authorization header.
+        let mut response = self
+            .client
+            .pipeline_without_auth()
+            .send(GraphRequestBuilder::from(self.monitor_url.clone()))?;
+        if let Some(err) = response.error() {
+            return Err(err);
+        }
+        let retry_after = RetryPolicy::retry_after(response.headers());
+        Ok((response.json()?, retry_after))
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exp = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay
+            .checked_mul(exp)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+
+    /// The url that resource ids are added to, based on the url of the
+    /// request that started the job: `/me/drive/items/{id}/copy` becomes
+    /// `/me/drive/items` and `/me/onenote/pages/{id}/copyToSection`
+    /// becomes `/me/onenote/pages`.
+    fn resource_base(url: &Url) -> Option<Url> {
+        let segments: Vec<&str> = url.path_segments()?.collect();
+        // Remove the action such as copy.
+        let (_, segments) = segments.split_last()?;
+        let mut base: Vec<&str> = match segments.iter().rposition(|s| *s == "items") {
+            Some(i) => segments[..=i].to_vec(),
+            None => match segments.iter().position(|s| s.starts_with("root")) {
+                Some(i) => {
+                    let mut base = segments[..i].to_vec();
+                    base.push("items");
+                    base
+                },
+                None => segments.split_last()?.1.to_vec(),
+            },
+        };
+        base.insert(0, "");
+
+        let mut resource_url = url.clone();
+        resource_url.set_query(None);
+        resource_url.set_path(base.join("/").as_str());
+        Some(resource_url)
+    }
+}
@@ -0,0 +1,277 @@
+use crate::http::{
+    AsyncDownload, AsyncUploadSessionClient, GraphRequest, GraphRequestBuilder, GraphResponse,
+    HttpResponse, HttpTransport, Middleware, RateLimiter, RequestClient, RequestDefaults,
+    ReqwestTransport, RetryPolicy, TelemetrySink,
+};
+use crate::types::content::Content;
+use crate::types::delta::NextLink;
+use futures::{future, stream, Future, Stream};
+use graph_error::GraphFailure;
+use graph_oauth::oauth::TokenProvider;
+use std::convert::TryFrom;
+use std::io::ErrorKind;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio_timer::Delay;
+
+/// A boxed future returned by async requests.
+pub type GraphFuture<T> = Box<dyn Future<Item = T, Error = GraphFailure> + Send>;
+
+/// A boxed stream returned by async requests that follow next links.
+pub type GraphStream<T> = Box<dyn Stream<Item = T, Error = GraphFailure> + Send>;
+
+/// Wait for the duration using a tokio timer. A zero duration
+/// completes at once without using the timer.
+pub(crate) fn sleep(duration: Duration) -> GraphFuture<()> {
+    if duration == Duration::from_secs(0) {
+        return Box::new(future::ok(()));
+    }
+    Box::new(
+        Delay::new(Instant::now() + duration)
+            .map_err(|err| GraphFailure::error_kind(ErrorKind::Other, &err.to_string())),
+    )
+}
+
+enum NextLinkState {
+    First(GraphRequestBuilder),
+    Next(String),
+    Done,
+}
+
+/// The async counterpart to `GraphRequest`.
+///
+/// Async requests go through the same middleware and `HttpTransport` as
+/// blocking requests, using `Middleware::handle_async` and
+/// `HttpTransport::send_async`. The default transport sends requests with
+/// the reqwest async client, so requests have to be run on a tokio runtime.
+/// Response bodies are read into memory without blocking before they are
+/// deserialized.
+pub struct AsyncGraphRequest {
+    request: GraphRequest,
+}
+
+impl AsyncGraphRequest {
+    pub fn new(transport: Arc<dyn HttpTransport>) -> AsyncGraphRequest {
+        AsyncGraphRequest::from(GraphRequest::new(transport))
+    }
+
+    /// The transport used to send requests.
+    pub fn transport(&self) -> Arc<dyn HttpTransport> {
+        self.request.transport()
+    }
+
+    /// The provider of the bearer token used for requests.
+    pub fn token_provider(&self) -> Arc<dyn TokenProvider> {
+        self.request.token_provider()
+    }
+
+    pub fn set_token_provider(&self, token_provider: Arc<dyn TokenProvider>) {
+        self.request.set_token_provider(token_provider);
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.request.retry_policy()
+    }
+
+    pub fn set_retry_policy(&self, retry_policy: RetryPolicy) {
+        self.request.set_retry_policy(retry_policy);
+    }
+
+    /// Set the rate limiter. Requests wait for the limiter without
+    /// blocking the executor.
+    pub fn set_rate_limiter(&self, rate_limiter: RateLimiter) {
+        self.request.set_rate_limiter(rate_limiter);
+    }
+
+    /// Add a sink that receives an event for each request.
+    pub fn add_telemetry_sink(&self, sink: Arc<dyn TelemetrySink>) {
+        self.request.add_telemetry_sink(sink);
+    }
+
+    /// Ask for compressed responses and decompress them. On by default.
+    pub fn set_compression(&self, compression: bool) {
+        self.request.set_compression(compression);
+    }
+
+    /// Add a middleware to the end of the middleware that has been added.
+    /// Added middleware runs before the retry policy and auth.
+    pub fn add_middleware(&self, middleware: Arc<dyn Middleware>) {
+        self.request.add_middleware(middleware);
+    }
+
+    /// Set the headers, such as the User-Agent, and the timeout
+    /// that are used for every request.
+    pub fn set_request_defaults(&self, defaults: RequestDefaults) {
+        self.request.set_request_defaults(defaults);
+    }
+
+    /// Send the request and return the response, or the error for an
+    /// error response. The body of an error response is read into memory
+    /// so the error includes the message from the API.
+    pub fn response(&self, request: GraphRequestBuilder) -> GraphFuture<HttpResponse> {
+        Box::new(self.request.pipeline().send_async(request).and_then(
+            |response| -> GraphFuture<HttpResponse> {
+                if response.status().is_success() {
+                    return Box::new(future::ok(response));
+                }
+                Box::new(
+                    response
+                        .into_buffered()
+                        .and_then(|mut response| match response.error() {
+                            Some(err) => Err(err),
+                            None => Ok(response),
+                        }),
+                )
+            },
+        ))
+    }
+
+    /// Send the request and read the body into memory.
+    fn buffered(&self, request: GraphRequestBuilder) -> GraphFuture<HttpResponse> {
+        Box::new(
+            self.response(request)
+                .and_then(|response| response.into_buffered()),
+        )
+    }
+
+    pub fn execute<T>(&self, request: GraphRequestBuilder) -> GraphFuture<GraphResponse<T>>
+    where
+        for<'de> T: serde::Deserialize<'de>,
+        T: 'static + Send,
+    {
+        Box::new(self.buffered(request).and_then(|mut response| {
+            let value: T = response.json()?;
+            Ok(GraphResponse::new(response, value))
+        }))
+    }
+
+    pub fn execute_content(
+        &self,
+        request: GraphRequestBuilder,
+    ) -> GraphFuture<GraphResponse<Content>> {
+        Box::new(
+            self.buffered(request)
+                .and_then(GraphResponse::<Content>::try_from),
+        )
+    }
+
+    pub fn json<T>(&self, request: GraphRequestBuilder) -> GraphFuture<T>
+    where
+        for<'de> T: serde::Deserialize<'de>,
+        T: 'static + Send,
+    {
+        Box::new(
+            self.buffered(request)
+                .and_then(|mut response| response.json()),
+        )
+    }
+
+    /// Sends the request and follows any next links that are returned.
+    /// Each page is returned as the next item in the stream.
+    pub fn delta<T>(&self, request: GraphRequestBuilder) -> GraphStream<GraphResponse<T>>
+    where
+        for<'de> T: serde::Deserialize<'de>,
+        T: 'static + Send + NextLink,
+    {
+        let client = self.clone();
+        let headers = request.link_headers();
+
+        let pages = stream::unfold(NextLinkState::First(request), move |state| {
+            let request = match state {
+                NextLinkState::First(request) => Ok(request),
+                NextLinkState::Next(link) => {
+                    GraphRequestBuilder::from_link(link.as_str(), &headers)
+                },
+                NextLinkState::Done => return None,
+            };
+
+            let page: GraphFuture<GraphResponse<T>> = match request {
+                Ok(request) => client.execute(request),
+                Err(err) => Box::new(future::err(err)),
+            };
+
+            // An error ends the stream after it is returned.
+            Some(page.then(|result| {
+                let next = match result.as_ref() {
+                    Ok(response) => response
+                        .value()
+                        .next_link()
+                        .map(NextLinkState::Next)
+                        .unwrap_or(NextLinkState::Done),
+                    Err(_) => NextLinkState::Done,
+                };
+                Ok::<_, GraphFailure>((result, next))
+            }))
+        });
+
+        Box::new(pages.and_then(|result| result))
+    }
+
+    pub fn upload_session(
+        &self,
+        request: GraphRequestBuilder,
+    ) -> GraphFuture<AsyncUploadSessionClient> {
+        let file = match request.upload_session_file.clone() {
+            Some(file) => file,
+            None => {
+                return Box::new(future::err(GraphFailure::invalid(
+                    "file for upload session",
+                )))
+            },
+        };
+        let pipeline = self.request.pipeline_without_auth();
+        Box::new(self.json(request).and_then(move |upload_session| {
+            let mut session = AsyncUploadSessionClient::new(upload_session, pipeline)?;
+            session.set_file(file)?;
+            Ok(session)
+        }))
+    }
+
+    pub(crate) fn request(&self) -> &GraphRequest {
+        &self.request
+    }
+}
+
+impl RequestClient for AsyncGraphRequest {
+    type Download = AsyncDownload;
+
+    fn token(&self) -> String {
+        self.request.token()
+    }
+
+    fn set_token(&self, token: &str) {
+        self.request.set_token(token);
+    }
+
+    fn download(&self, request: GraphRequestBuilder) -> AsyncDownload {
+        AsyncDownload::new(self.clone(), request)
+    }
+}
+
+impl Clone for AsyncGraphRequest {
+    fn clone(&self) -> Self {
+        AsyncGraphRequest {
+            request: self.request.clone(),
+        }
+    }
+}
+
+impl AsRef<GraphRequest> for AsyncGraphRequest {
+    fn as_ref(&self) -> &GraphRequest {
+        self.request()
+    }
+}
+
+/// Send the requests of a blocking client asynchronously. The
+/// settings of the blocking client are kept.
+impl From<GraphRequest> for AsyncGraphRequest {
+    fn from(request: GraphRequest) -> Self {
+        AsyncGraphRequest { request }
+    }
+}
+
+impl Default for AsyncGraphRequest {
+    fn default() -> Self {
+        AsyncGraphRequest::new(Arc::new(ReqwestTransport::default_async()))
+    }
+}
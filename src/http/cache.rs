@@ -0,0 +1,455 @@
+use crate::http::{
+    AsyncNext, GraphFuture, GraphRequestBuilder, GraphRequestType, HttpResponse, Middleware, Next,
+    RecordedResponse, CONSISTENCY_LEVEL,
+};
+use crate::types::etag::ETag;
+use crate::types::prefer::PREFER;
+use futures::{future, Future};
+use graph_error::GraphResult;
+use openssl::sha::sha256;
+use reqwest::header::{
+    HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION, CACHE_CONTROL, ETAG, IF_MATCH, IF_NONE_MATCH,
+    LOCATION,
+};
+use reqwest::{Method, StatusCode};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{BufReader, BufWriter, Cursor};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use url::Url;
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+fn has_no_cache(headers: &HeaderMap) -> bool {
+    headers.get_all(CACHE_CONTROL).iter().any(|value| {
+        value
+            .to_str()
+            .map(|value| value.contains("no-store") || value.contains("no-cache"))
+            .unwrap_or(false)
+    })
+}
+
+/// A cached response and the ETag used to revalidate it.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Getters)]
+#[get = "pub"]
+pub struct CacheEntry {
+    key: String,
+    url: String,
+    etag: String,
+    /// Seconds since the unix epoch when the response was
+    /// stored or last revalidated.
+    stored_at: u64,
+    response: RecordedResponse,
+}
+
+impl CacheEntry {
+    fn is_expired(&self, ttl: Option<Duration>) -> bool {
+        ttl.map_or(false, |ttl| {
+            now().saturating_sub(self.stored_at) >= ttl.as_secs()
+        })
+    }
+}
+
+/// Where a `ResponseCache` keeps its entries. Entries are keyed by the
+/// url, the Accept, Prefer and ConsistencyLevel headers and a hash of
+/// the access token of the request.
+pub trait CacheStorage: Send + Sync {
+    fn get(&self, key: &str) -> Option<CacheEntry>;
+
+    fn put(&self, key: &str, entry: CacheEntry);
+
+    fn remove(&self, key: &str);
+
+    fn clear(&self);
+}
+
+/// Keeps entries in memory. Once the maximum number of entries is
+/// reached the entry that was stored the longest ago is removed.
+pub struct MemoryCache {
+    max_entries: usize,
+    entries: Mutex<(HashMap<String, CacheEntry>, VecDeque<String>)>,
+}
+
+impl MemoryCache {
+    pub fn new(max_entries: usize) -> MemoryCache {
+        MemoryCache {
+            max_entries,
+            entries: Mutex::new((HashMap::new(), VecDeque::new())),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl CacheStorage for MemoryCache {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        self.entries.lock().unwrap().0.get(key).cloned()
+    }
+
+    fn put(&self, key: &str, entry: CacheEntry) {
+        if self.max_entries == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        let (map, order) = &mut *entries;
+        order.retain(|k| k != key);
+        while map.len() >= self.max_entries && !order.is_empty() {
+            if let Some(oldest) = order.pop_front() {
+                map.remove(&oldest);
+            }
+        }
+        map.insert(key.to_string(), entry);
+        order.push_back(key.to_string());
+    }
+
+    fn remove(&self, key: &str) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.0.remove(key);
+        entries.1.retain(|k| k != key);
+    }
+
+    fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.0.clear();
+        entries.1.clear();
+    }
+}
+
+/// Keeps each entry as a json file in a directory.
+pub struct FileCache {
+    dir: PathBuf,
+}
+
+impl FileCache {
+    pub fn new<P: AsRef<Path>>(dir: P) -> FileCache {
+        FileCache {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        self.dir.as_path()
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        self.dir.join(format!("{:016x}.json", hasher.finish()))
+    }
+
+    fn write(&self, path: &Path, entry: &CacheEntry) -> GraphResult<()> {
+        std::fs::create_dir_all(self.dir.as_path())?;
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(writer, entry)?;
+        Ok(())
+    }
+}
+
+impl CacheStorage for FileCache {
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        let file = File::open(self.path(key)).ok()?;
+        let entry: CacheEntry = serde_json::from_reader(BufReader::new(file)).ok()?;
+        // Different keys can have the same file name.
+        if entry.key == key {
+            Some(entry)
+        } else {
+            None
+        }
+    }
+
+    fn put(&self, key: &str, entry: CacheEntry) {
+        let path = self.path(key);
+        if let Err(err) = self.write(path.as_path(), &entry) {
+            warn!("Could not write cache entry {:#?}: {}", path, err);
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        let _ = std::fs::remove_file(self.path(key));
+    }
+
+    fn clear(&self) {
+        if let Ok(dir) = std::fs::read_dir(self.dir.as_path()) {
+            for entry in dir.filter_map(Result::ok) {
+                if entry.path().extension().map_or(false, |ext| ext == "json") {
+                    let _ = std::fs::remove_file(entry.path());
+                }
+            }
+        }
+    }
+}
+
+/// A middleware that caches GET responses that have an ETag.
+///
+/// A cached response is revalidated by sending its ETag in an
+/// If-None-Match header. If the API returns 304 Not Modified the cached
+/// response is returned. Requests using `bypass_cache` or that set
+/// `Cache-Control: no-cache` or `no-store` skip the cache.
+///
+/// Responses are only shared between requests with the same url, the
+/// same Accept, Prefer and ConsistencyLevel headers and the same access
+/// token. The token is kept as a hash.
+///
+/// Downloads and responses that redirect, such as the pre-authenticated
+/// urls of `DownloadClient`, are never cached.
+///
+/// # Example
+/// ```rust,ignore
+/// let cache = ResponseCache::in_memory(500)
+///     .ttl(Duration::from_secs(60 * 60))
+///     .max_body_size(1024 * 1024);
+/// client.add_middleware(cache);
+///
+/// // Skip the cache for a single request.
+/// let response = client.v1().me().get().bypass_cache().send()?;
+/// ```
+pub struct ResponseCache {
+    storage: Arc<dyn CacheStorage>,
+    ttl: Option<Duration>,
+    max_body_size: Option<usize>,
+}
+
+impl ResponseCache {
+    pub fn new<S>(storage: S) -> ResponseCache
+    where
+        S: CacheStorage + 'static,
+    {
+        ResponseCache {
+            storage: Arc::new(storage),
+            ttl: None,
+            max_body_size: None,
+        }
+    }
+
+    /// A cache that keeps up to `max_entries` responses in memory.
+    pub fn in_memory(max_entries: usize) -> ResponseCache {
+        ResponseCache::new(MemoryCache::new(max_entries))
+    }
+
+    /// A cache that keeps responses as files in the directory.
+    pub fn on_disk<P: AsRef<Path>>(dir: P) -> ResponseCache {
+        ResponseCache::new(FileCache::new(dir))
+    }
+
+    /// Remove entries that were stored or last revalidated longer
+    /// ago than the ttl. By default entries do not expire.
+    pub fn ttl(mut self, ttl: Duration) -> ResponseCache {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Do not cache responses with a body larger than this many bytes.
+    pub fn max_body_size(mut self, max: usize) -> ResponseCache {
+        self.max_body_size = Some(max);
+        self
+    }
+
+    /// The storage of the cache, which can be used to remove entries.
+    pub fn storage(&self) -> Arc<dyn CacheStorage> {
+        Arc::clone(&self.storage)
+    }
+
+    fn is_cacheable_request(request: &GraphRequestBuilder) -> bool {
+        request.method == Method::GET &&
+            !request.bypass_cache() &&
+            request.req_type == GraphRequestType::Basic &&
+            request.download_dir.is_none() &&
+            !request.headers.contains_key(IF_MATCH) &&
+            !request.headers.contains_key(IF_NONE_MATCH) &&
+            !has_no_cache(&request.headers)
+    }
+
+    fn is_cacheable_response(response: &HttpResponse, url: &Url) -> bool {
+        // A different url means a redirect was followed, which may be
+        // to a pre-authenticated url that should not be kept.
+        response.status() == StatusCode::OK &&
+            response.url() == url &&
+            !response.headers().contains_key(LOCATION) &&
+            !has_no_cache(response.headers())
+    }
+
+    /// The url and the headers that change the response, followed by
+    /// a hash of the access token so that users do not share responses.
+    fn key(request: &GraphRequestBuilder, url: &Url) -> GraphResult<String> {
+        let mut key = url.to_string();
+        for name in [ACCEPT.as_str(), PREFER, CONSISTENCY_LEVEL].iter() {
+            for value in request.headers.get_all(*name).iter() {
+                key.push_str(&format!(
+                    "\n{}: {}",
+                    name,
+                    String::from_utf8_lossy(value.as_bytes())
+                ));
+            }
+        }
+
+        let token = match request.token_provider.as_ref() {
+            Some(token_provider) => Some(token_provider.bearer_token()?.into_bytes()),
+            None => request
+                .headers
+                .get(AUTHORIZATION)
+                .map(|value| value.as_bytes().to_vec()),
+        };
+        if let Some(token) = token {
+            key.push_str("\nidentity: ");
+            for byte in sha256(token.as_slice()).iter() {
+                key.push_str(&format!("{:02x}", byte));
+            }
+        }
+        Ok(key)
+    }
+
+    fn cached(&self, key: &str) -> Option<CacheEntry> {
+        let entry = self.storage.get(key)?;
+        if entry.is_expired(self.ttl) {
+            self.storage.remove(key);
+            return None;
+        }
+        Some(entry)
+    }
+
+    fn store(&self, key: &str, response: &mut HttpResponse) -> GraphResult<()> {
+        let mut body = Vec::new();
+        response.copy_to(&mut body)?;
+        response.set_body(Cursor::new(body.clone()));
+        if self.max_body_size.map_or(false, |max| body.len() > max) {
+            return Ok(());
+        }
+
+        let etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string())
+            .or_else(|| {
+                serde_json::from_slice::<serde_json::Value>(&body)
+                    .ok()
+                    .and_then(|value| value.etag())
+            });
+        if let Some(etag) = etag {
+            self.storage.put(
+                key,
+                CacheEntry {
+                    key: key.to_string(),
+                    url: response.url().to_string(),
+                    etag,
+                    stored_at: now(),
+                    response: RecordedResponse::from_response(response, &body),
+                },
+            );
+        }
+        Ok(())
+    }
+
+    /// Add the ETag of the cached response, if any, to the request so
+    /// the API returns 304 Not Modified if it has not changed.
+    /// Returns None if the access token cannot be read, in which case
+    /// the request skips the cache.
+    fn revalidate(
+        &self,
+        request: &mut GraphRequestBuilder,
+    ) -> Option<(Url, String, Option<CacheEntry>)> {
+        let url = request.to_url();
+        let key = ResponseCache::key(request, &url).ok()?;
+        let cached = self.cached(key.as_str());
+        if let Some(entry) = cached.as_ref() {
+            if let Ok(value) = HeaderValue::from_str(entry.etag.as_str()) {
+                request.header(IF_NONE_MATCH, value);
+            }
+        }
+        Some((url, key, cached))
+    }
+
+    /// The cached response for a 304 Not Modified response.
+    fn not_modified(
+        &self,
+        url: Url,
+        key: &str,
+        cached: Option<CacheEntry>,
+        response: HttpResponse,
+    ) -> GraphResult<HttpResponse> {
+        match cached {
+            Some(mut entry) => {
+                debug!("Using the cached response for {}", key);
+                let cached_response = entry.response.to_response(url)?;
+                entry.stored_at = now();
+                self.storage.put(key, entry);
+                Ok(cached_response)
+            },
+            None => Ok(response),
+        }
+    }
+}
+
+impl Middleware for ResponseCache {
+    fn handle(&self, mut request: GraphRequestBuilder, next: Next) -> GraphResult<HttpResponse> {
+        if !ResponseCache::is_cacheable_request(&request) {
+            return next.run(request);
+        }
+
+        let (url, key, cached) = match self.revalidate(&mut request) {
+            Some(revalidate) => revalidate,
+            None => return next.run(request),
+        };
+        let mut response = next.run(request)?;
+        if response.status() == StatusCode::NOT_MODIFIED {
+            return self.not_modified(url, key.as_str(), cached, response);
+        }
+
+        if ResponseCache::is_cacheable_response(&response, &url) {
+            self.store(key.as_str(), &mut response)?;
+        }
+        Ok(response)
+    }
+
+    /// A response that is cached is read into memory without blocking
+    /// before it is stored.
+    fn handle_async(
+        self: Arc<Self>,
+        mut request: GraphRequestBuilder,
+        next: AsyncNext,
+    ) -> GraphFuture<HttpResponse> {
+        if !ResponseCache::is_cacheable_request(&request) {
+            return next.run(request);
+        }
+
+        let (url, key, cached) = match self.revalidate(&mut request) {
+            Some(revalidate) => revalidate,
+            None => return next.run(request),
+        };
+        Box::new(
+            next.run(request)
+                .and_then(move |response| -> GraphFuture<HttpResponse> {
+                    if response.status() == StatusCode::NOT_MODIFIED {
+                        return Box::new(future::result(self.not_modified(
+                            url,
+                            key.as_str(),
+                            cached,
+                            response,
+                        )));
+                    }
+                    if !ResponseCache::is_cacheable_response(&response, &url) {
+                        return Box::new(future::ok(response));
+                    }
+                    Box::new(response.into_buffered().and_then(move |mut response| {
+                        self.store(key.as_str(), &mut response)?;
+                        Ok(response)
+                    }))
+                }),
+        )
+    }
+}
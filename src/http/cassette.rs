@@ -0,0 +1,281 @@
+use crate::http::{GraphRequestBuilder, HttpResponse, HttpTransport, PreparedRequest};
+use graph_error::{GraphFailure, GraphResult};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::StatusCode;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Cursor};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+/// The value that scrubbed secrets are replaced with.
+pub static SCRUBBED: &str = "[REDACTED]";
+
+/// A response saved in a cassette. Bodies that are not valid UTF-8
+/// are stored as base64.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Getters)]
+#[get = "pub"]
+pub struct RecordedResponse {
+    status: u16,
+    headers: BTreeMap<String, String>,
+    body: String,
+    #[serde(default)]
+    base64: bool,
+}
+
+impl RecordedResponse {
+    pub(crate) fn from_response(response: &HttpResponse, body: &[u8]) -> RecordedResponse {
+        let mut headers = BTreeMap::new();
+        for (name, value) in response.headers().iter() {
+            headers.insert(
+                name.as_str().to_string(),
+                String::from_utf8_lossy(value.as_bytes()).to_string(),
+            );
+        }
+        let (body, base64) = match std::str::from_utf8(body) {
+            Ok(text) => (text.to_string(), false),
+            Err(_) => (base64::encode(body), true),
+        };
+        RecordedResponse {
+            status: response.status().as_u16(),
+            headers,
+            body,
+            base64,
+        }
+    }
+
+    pub(crate) fn to_response(&self, url: Url) -> GraphResult<HttpResponse> {
+        let status =
+            StatusCode::from_u16(self.status).map_err(|_| GraphFailure::invalid("status code"))?;
+        let mut headers = HeaderMap::new();
+        for (name, value) in self.headers.iter() {
+            headers.insert(
+                HeaderName::from_str(name.as_str())
+                    .map_err(|_| GraphFailure::invalid("header name"))?,
+                HeaderValue::from_str(value.as_str())
+                    .map_err(|_| GraphFailure::invalid("header value"))?,
+            );
+        }
+        let body = if self.base64 {
+            base64::decode(self.body.as_str())
+                .map_err(|_| GraphFailure::invalid("base64 response body"))?
+        } else {
+            self.body.clone().into_bytes()
+        };
+        Ok(HttpResponse::new(status, url, headers, Cursor::new(body)))
+    }
+}
+
+/// A request and the response that was returned for it.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Getters)]
+#[get = "pub"]
+pub struct Interaction {
+    request: PreparedRequest,
+    response: RecordedResponse,
+}
+
+impl Interaction {
+    /// Requests match when the method, path and query are the same.
+    /// The host and the order of the query pairs are ignored.
+    fn matches(&self, request: &PreparedRequest) -> bool {
+        fn key(request: &PreparedRequest) -> Option<(String, String, Vec<(String, String)>)> {
+            let url = Url::parse(request.url()).ok()?;
+            let mut query: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+            query.sort();
+            Some((request.method().clone(), url.path().to_string(), query))
+        }
+        match key(&self.request) {
+            Some(recorded) => Some(recorded) == key(request),
+            None => false,
+        }
+    }
+}
+
+/// A list of recorded interactions that can be saved to a file.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize, Getters)]
+#[get = "pub"]
+pub struct Cassette {
+    interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> GraphResult<Cassette> {
+        let file = File::open(path)?;
+        Ok(serde_json::from_reader(BufReader::new(file))?)
+    }
+
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> GraphResult<()> {
+        let file = File::create(path)?;
+        Ok(serde_json::to_writer_pretty(BufWriter::new(file), self)?)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CassetteMode {
+    /// Send requests and save each request and response.
+    Record,
+    /// Return saved responses without sending requests.
+    Replay,
+}
+
+/// A transport that records requests and responses to a cassette file
+/// and replays them so tests can run offline.
+///
+/// In record mode each request is sent using another transport and the
+/// request and response are saved to the cassette file. In replay mode
+/// the response is taken from the first unused interaction with the same
+/// method, path and query. Requests that were not recorded return an error.
+///
+/// The Authorization header is never saved. Other secrets, such as ids or
+/// pre-authenticated urls, can be replaced using `scrub`. Multipart
+/// requests cannot be recorded.
+///
+/// # Example
+/// ```rust,ignore
+/// // Record using a real access token.
+/// let mut transport = CassetteTransport::record(
+///     "./test_files/cassettes/drive.json",
+///     ReqwestTransport::default(),
+/// );
+/// transport.scrub(user_id.as_str());
+/// let client = Graph::with_transport(access_token.as_str(), transport);
+///
+/// // Replay in CI.
+/// let transport = CassetteTransport::replay("./test_files/cassettes/drive.json")?;
+/// let client = Graph::with_transport("ACCESS_TOKEN", transport);
+/// ```
+pub struct CassetteTransport {
+    path: PathBuf,
+    mode: CassetteMode,
+    transport: Option<Arc<dyn HttpTransport>>,
+    cassette: Mutex<Cassette>,
+    used: Mutex<Vec<bool>>,
+    secrets: Vec<String>,
+}
+
+impl CassetteTransport {
+    /// Send requests using the transport and save them to the cassette
+    /// file. The file is replaced once the first request is recorded.
+    pub fn record<P, T>(path: P, transport: T) -> CassetteTransport
+    where
+        P: AsRef<Path>,
+        T: HttpTransport + 'static,
+    {
+        CassetteTransport {
+            path: path.as_ref().to_path_buf(),
+            mode: CassetteMode::Record,
+            transport: Some(Arc::new(transport)),
+            cassette: Default::default(),
+            used: Default::default(),
+            secrets: Vec::new(),
+        }
+    }
+
+    /// Replay the interactions saved in the cassette file.
+    pub fn replay<P: AsRef<Path>>(path: P) -> GraphResult<CassetteTransport> {
+        let cassette = Cassette::from_file(path.as_ref())?;
+        let used = vec![false; cassette.interactions.len()];
+        Ok(CassetteTransport {
+            path: path.as_ref().to_path_buf(),
+            mode: CassetteMode::Replay,
+            transport: None,
+            cassette: Mutex::new(cassette),
+            used: Mutex::new(used),
+            secrets: Vec::new(),
+        })
+    }
+
+    /// Replace a secret with `[REDACTED]` wherever it appears in a recorded
+    /// url, header or body. Requests are scrubbed in the same way before
+    /// they are matched during replay.
+    pub fn scrub(&mut self, secret: &str) -> &mut Self {
+        if !secret.is_empty() {
+            self.secrets.push(secret.to_string());
+        }
+        self
+    }
+
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// A copy of the interactions that have been recorded or loaded.
+    pub fn cassette(&self) -> Cassette {
+        self.cassette.lock().unwrap().clone()
+    }
+
+    fn scrub_value<T>(&self, value: T) -> GraphResult<T>
+    where
+        T: serde::Serialize,
+        for<'de> T: serde::Deserialize<'de>,
+    {
+        if self.secrets.is_empty() {
+            return Ok(value);
+        }
+        let mut json = serde_json::to_string(&value)?;
+        for secret in self.secrets.iter() {
+            json = json.replace(secret.as_str(), SCRUBBED);
+        }
+        Ok(serde_json::from_str(json.as_str())?)
+    }
+
+    fn record_request(&self, request: GraphRequestBuilder) -> GraphResult<HttpResponse> {
+        let prepared = self.scrub_value(PreparedRequest::try_from(&request)?)?;
+        let transport = self
+            .transport
+            .as_ref()
+            .ok_or_else(|| GraphFailure::invalid("transport for recording"))?;
+        let mut response = transport.send(request)?;
+
+        let mut body = Vec::new();
+        response.copy_to(&mut body)?;
+        let recorded = self.scrub_value(RecordedResponse::from_response(&response, &body))?;
+        response.set_body(Cursor::new(body));
+
+        let mut cassette = self.cassette.lock().unwrap();
+        cassette.interactions.push(Interaction {
+            request: prepared,
+            response: recorded,
+        });
+        cassette.to_file(self.path.as_path())?;
+        Ok(response)
+    }
+
+    fn replay_request(&self, request: GraphRequestBuilder) -> GraphResult<HttpResponse> {
+        let prepared = self.scrub_value(PreparedRequest::try_from(&request)?)?;
+        let cassette = self.cassette.lock().unwrap();
+        let mut used = self.used.lock().unwrap();
+        let position = cassette
+            .interactions
+            .iter()
+            .enumerate()
+            .position(|(i, interaction)| !used[i] && interaction.matches(&prepared))
+            .ok_or_else(|| {
+                GraphFailure::invalid(&format!(
+                    "recorded interaction for {} {}",
+                    prepared.method(),
+                    prepared.url()
+                ))
+            })?;
+        used[position] = true;
+        cassette.interactions[position]
+            .response
+            .to_response(request.to_url())
+    }
+}
+
+impl HttpTransport for CassetteTransport {
+    fn send(&self, request: GraphRequestBuilder) -> GraphResult<HttpResponse> {
+        match self.mode {
+            CassetteMode::Record => self.record_request(request),
+            CassetteMode::Replay => self.replay_request(request),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::http::{
+    AsyncNext, GraphFuture, GraphRequestBuilder, GraphRequestType, GraphStream, HttpResponse,
+    Middleware, Next,
+};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use flate2::write;
+use futures::{Async, Future, Poll, Stream};
+use graph_error::{GraphFailure, GraphResult};
+use reqwest::header::{HeaderValue, ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_LENGTH};
+use std::io::{BufReader, Write};
+use std::sync::Arc;
+
+/// Decompresses the chunks of a stream body as they arrive.
+enum ChunkDecoder {
+    Gzip(write::GzDecoder<Vec<u8>>),
+    Deflate(write::ZlibDecoder<Vec<u8>>),
+}
+
+impl ChunkDecoder {
+    /// Decompress a chunk and return the bytes that are ready.
+    fn decode(&mut self, chunk: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            ChunkDecoder::Gzip(decoder) => {
+                decoder.write_all(chunk)?;
+                Ok(std::mem::replace(decoder.get_mut(), Vec::new()))
+            },
+            ChunkDecoder::Deflate(decoder) => {
+                decoder.write_all(chunk)?;
+                Ok(std::mem::replace(decoder.get_mut(), Vec::new()))
+            },
+        }
+    }
+
+    fn finish(self) -> std::io::Result<Vec<u8>> {
+        match self {
+            ChunkDecoder::Gzip(decoder) => decoder.finish(),
+            ChunkDecoder::Deflate(decoder) => decoder.finish(),
+        }
+    }
+}
+
+struct DecodedStream {
+    body: GraphStream<Vec<u8>>,
+    decoder: Option<ChunkDecoder>,
+}
+
+impl Stream for DecodedStream {
+    type Item = Vec<u8>;
+    type Error = GraphFailure;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        loop {
+            // The decoder is taken once the body has ended.
+            let decoder = match self.decoder.as_mut() {
+                Some(decoder) => decoder,
+                None => return Ok(Async::Ready(None)),
+            };
+            let decoded = match self.body.poll()? {
+                Async::NotReady => return Ok(Async::NotReady),
+                Async::Ready(Some(chunk)) => decoder.decode(&chunk)?,
+                Async::Ready(None) => match self.decoder.take() {
+                    Some(decoder) => decoder.finish()?,
+                    None => Vec::new(),
+                },
+            };
+            if !decoded.is_empty() {
+                return Ok(Async::Ready(Some(decoded)));
+            }
+        }
+    }
+}
+
+/// Asks for gzip or deflate compressed responses and decompresses
+/// them as they are read.
+///
+/// The Accept-Encoding header is only added to requests that do not set
+/// one and are not downloads. A response with a Content-Encoding of gzip
+/// or deflate is decompressed and the Content-Encoding and Content-Length
+/// headers are removed, so the rest of the pipeline sees the decoded body.
+/// Compression is used by the blocking and async clients by default.
+#[derive(Debug, Default, Clone)]
+pub struct Compression;
+
+impl Compression {
+    fn accepts_encoding(request: &GraphRequestBuilder) -> bool {
+        request.req_type == GraphRequestType::Basic &&
+            request.download_dir.is_none() &&
+            !request.headers.contains_key(ACCEPT_ENCODING)
+    }
+
+    /// Decompress the body of the response if it has a Content-Encoding
+    /// of gzip or deflate. A stream body stays a stream and each chunk is
+    /// decompressed as it arrives.
+    pub fn decode(response: &mut HttpResponse) {
+        let encoding = response
+            .headers()
+            .get(CONTENT_ENCODING)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim().to_ascii_lowercase());
+
+        let is_stream = response.is_stream();
+        match encoding.as_ref().map(|encoding| encoding.as_str()) {
+            Some("gzip") | Some("x-gzip") if is_stream => {
+                let body = response.take_stream();
+                response.set_stream(DecodedStream {
+                    body,
+                    decoder: Some(ChunkDecoder::Gzip(write::GzDecoder::new(Vec::new()))),
+                });
+            },
+            Some("deflate") if is_stream => {
+                let body = response.take_stream();
+                response.set_stream(DecodedStream {
+                    body,
+                    decoder: Some(ChunkDecoder::Deflate(write::ZlibDecoder::new(Vec::new()))),
+                });
+            },
+            Some("gzip") | Some("x-gzip") => {
+                let body = BufReader::new(response.take_body());
+                response.set_body(GzDecoder::new(body));
+            },
+            Some("deflate") => {
+                let body = BufReader::new(response.take_body());
+                response.set_body(ZlibDecoder::new(body));
+            },
+            _ => return,
+        }
+        let headers = response.headers_mut();
+        headers.remove(CONTENT_ENCODING);
+        headers.remove(CONTENT_LENGTH);
+    }
+}
+
+impl Middleware for Compression {
+    fn handle(&self, mut request: GraphRequestBuilder, next: Next) -> GraphResult<HttpResponse> {
+        if Compression::accepts_encoding(&request) {
+            request.header(ACCEPT_ENCODING, HeaderValue::from_static("gzip, deflate"));
+        }
+        let mut response = next.run(request)?;
+        Compression::decode(&mut response);
+        Ok(response)
+    }
+
+    fn handle_async(
+        self: Arc<Self>,
+        mut request: GraphRequestBuilder,
+        next: AsyncNext,
+    ) -> GraphFuture<HttpResponse> {
+        if Compression::accepts_encoding(&request) {
+            request.header(ACCEPT_ENCODING, HeaderValue::from_static("gzip, deflate"));
+        }
+        Box::new(next.run(request).map(|mut response| {
+            Compression::decode(&mut response);
+            response
+        }))
+    }
+}
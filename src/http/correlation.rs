@@ -0,0 +1,84 @@
+use crate::http::{
+    AsyncNext, GraphFuture, GraphRequestBuilder, HttpResponse, Middleware, Next, CLIENT_REQUEST_ID,
+};
+use futures::Future;
+use graph_error::{GraphFailure, GraphResult};
+use reqwest::header::{HeaderMap, HeaderValue};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Asks the API to return the client-request-id in the response.
+pub static RETURN_CLIENT_REQUEST_ID: &str = "return-client-request-id";
+
+/// Sends a client-request-id with each request so a request can be found
+/// in logs and support tickets.
+///
+/// A new id is generated for requests that do not already have one. Retries
+/// of a request use the same id. The id is added to the response headers if
+/// the API does not return it, so it is part of every `GraphResponse` and
+/// every `GraphError` from an error response. Failures that are not from an
+/// error response, such as transport failures, are returned with the id
+/// attached.
+///
+/// The id is only sent with Graph API requests and not to upload session
+/// or monitor urls.
+#[derive(Debug, Default, Clone)]
+pub struct ClientRequestId;
+
+impl ClientRequestId {
+    /// A new random id.
+    pub fn generate() -> String {
+        Uuid::new_v4().to_string()
+    }
+
+    /// Add a client-request-id, unless the headers already have one, and
+    /// the return-client-request-id header. Returns the id.
+    pub fn set(headers: &mut HeaderMap) -> Option<HeaderValue> {
+        if !headers.contains_key(CLIENT_REQUEST_ID) {
+            let id = HeaderValue::from_str(ClientRequestId::generate().as_str()).ok()?;
+            headers.insert(CLIENT_REQUEST_ID, id);
+        }
+        headers.insert(RETURN_CLIENT_REQUEST_ID, HeaderValue::from_static("true"));
+        headers.get(CLIENT_REQUEST_ID).cloned()
+    }
+
+    fn failure(err: GraphFailure, id: Option<&HeaderValue>) -> GraphFailure {
+        match id.and_then(|id| id.to_str().ok()) {
+            Some(id) => err.with_client_request_id(id),
+            None => err,
+        }
+    }
+
+    fn response(mut response: HttpResponse, id: Option<HeaderValue>) -> HttpResponse {
+        if let Some(id) = id {
+            if !response.headers().contains_key(CLIENT_REQUEST_ID) {
+                response.headers_mut().insert(CLIENT_REQUEST_ID, id);
+            }
+        }
+        response
+    }
+}
+
+impl Middleware for ClientRequestId {
+    fn handle(&self, mut request: GraphRequestBuilder, next: Next) -> GraphResult<HttpResponse> {
+        let id = ClientRequestId::set(&mut request.headers);
+        let response = next
+            .run(request)
+            .map_err(|err| ClientRequestId::failure(err, id.as_ref()))?;
+        Ok(ClientRequestId::response(response, id))
+    }
+
+    fn handle_async(
+        self: Arc<Self>,
+        mut request: GraphRequestBuilder,
+        next: AsyncNext,
+    ) -> GraphFuture<HttpResponse> {
+        let id = ClientRequestId::set(&mut request.headers);
+        let failure_id = id.clone();
+        Box::new(
+            next.run(request)
+                .map_err(move |err| ClientRequestId::failure(err, failure_id.as_ref()))
+                .map(move |response| ClientRequestId::response(response, id)),
+        )
+    }
+}
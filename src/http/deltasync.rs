@@ -0,0 +1,225 @@
+use crate::http::{GraphRequest, GraphRequestBuilder};
+use crate::types::collection::Collection;
+use crate::types::prefer::{Prefer, PREFER};
+use from_as::*;
+use graph_error::GraphResult;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, LOCATION};
+use reqwest::StatusCode;
+use std::collections::BTreeMap;
+
+/// The headers of the first delta request that are kept in the state.
+const STATE_HEADERS: [&str; 3] = ["prefer", "consistencylevel", "accept"];
+
+/// The saved state of a delta query.
+///
+/// The state has the url of the first delta request, which is used for a
+/// full sync, and the delta link returned at the end of the last sync. The
+/// Prefer, ConsistencyLevel and Accept headers of the first request are
+/// kept so they are sent with every page. Other headers are not saved. It
+/// can be saved to and loaded from a json, yaml or toml file.
+///
+/// # Example
+/// ```rust,ignore
+/// let state = DeltaState::from_file("./drive_delta.json")
+///     .or_else(|_| client.v1().me().drive().delta().delta_state())?;
+///
+/// let mut sync = client.delta_sync(state);
+/// for event in &mut sync {
+///     match event? {
+///         DeltaEvent::Changed(item) => println!("changed: {}", item["id"]),
+///         DeltaEvent::Removed(item) => println!("removed: {}", item["id"]),
+///         DeltaEvent::Resync => println!("starting a full sync"),
+///     }
+/// }
+/// sync.state().as_file("./drive_delta.json")?;
+/// ```
+#[derive(
+    Default, Debug, Clone, Eq, PartialEq, Serialize, Deserialize, FromFile, AsFile, Getters,
+)]
+#[get = "pub"]
+pub struct DeltaState {
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delta_link: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    headers: BTreeMap<String, String>,
+}
+
+impl DeltaState {
+    /// A new state that starts with a full sync using the url.
+    pub fn new(url: &str) -> DeltaState {
+        DeltaState {
+            url: url.to_string(),
+            delta_link: None,
+            headers: BTreeMap::new(),
+        }
+    }
+
+    /// A new state that starts with a full sync of the request. The
+    /// Prefer, ConsistencyLevel and Accept headers of the request are
+    /// sent with every page. A header with more than one value is saved
+    /// as a comma separated list.
+    pub fn from_request(request: &GraphRequestBuilder) -> DeltaState {
+        let mut state = DeltaState::new(request.url().as_str());
+        for name in STATE_HEADERS.iter() {
+            let values: Vec<&str> = request
+                .headers()
+                .get_all(*name)
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .collect();
+            if !values.is_empty() {
+                state.headers.insert(name.to_string(), values.join(", "));
+            }
+        }
+        state
+    }
+
+    /// The url of the next sync: the delta link if there is one and
+    /// otherwise the url of a full sync.
+    pub fn next_url(&self) -> &str {
+        self.delta_link.as_ref().unwrap_or(&self.url).as_str()
+    }
+
+    fn header_map(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in self.headers.iter() {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_bytes(name.as_bytes()),
+                HeaderValue::from_str(value.as_str()),
+            ) {
+                headers.append(name, value);
+            }
+        }
+        headers
+    }
+
+    /// Forget the delta link so the next sync is a full sync.
+    pub fn reset(&mut self) {
+        self.delta_link = None;
+    }
+}
+
+/// A change returned by a delta query.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeltaEvent {
+    /// An item that was created or changed.
+    Changed(serde_json::Value),
+    /// An item that was deleted or removed. The `@removed` property
+    /// of the item has the reason.
+    Removed(serde_json::Value),
+    /// The delta link expired and a full sync was started. Items that
+    /// were synced before may be returned again.
+    Resync,
+}
+
+impl From<serde_json::Value> for DeltaEvent {
+    fn from(value: serde_json::Value) -> Self {
+        if value.get("@removed").is_some() {
+            DeltaEvent::Removed(value)
+        } else {
+            DeltaEvent::Changed(value)
+        }
+    }
+}
+
+/// An iterator over the changes of a delta query.
+///
+/// Pages are requested on the current thread as the iterator is advanced,
+/// following next links until a delta link is returned. The delta link is
+/// then kept in the state so the next sync only returns new changes. If the
+/// delta link has expired and the API returns 410 Gone, a full sync is
+/// started and `DeltaEvent::Resync` is returned. Every page, including the
+/// pages of a resync, is sent with the headers kept in the state.
+pub struct DeltaSync {
+    client: GraphRequest,
+    headers: HeaderMap,
+    state: DeltaState,
+    next: Option<String>,
+    items: std::vec::IntoIter<serde_json::Value>,
+    resynced: bool,
+}
+
+impl DeltaSync {
+    pub fn new(client: GraphRequest, state: DeltaState) -> DeltaSync {
+        DeltaSync {
+            client,
+            headers: state.header_map(),
+            next: Some(state.next_url().to_string()),
+            state,
+            items: Vec::new().into_iter(),
+            resynced: false,
+        }
+    }
+
+    /// Send the preferences with every page. A preference of the request
+    /// the state was made from replaces one with the same name.
+    pub fn default_preferences(mut self, prefer: &Prefer) -> DeltaSync {
+        let mut prefer = prefer.clone();
+        prefer.extend(&Prefer::from_headers(&self.headers, PREFER));
+        prefer.apply(&mut self.headers);
+        self
+    }
+
+    /// The current state. The delta link is updated once the
+    /// last page of the sync has been returned.
+    pub fn state(&self) -> &DeltaState {
+        &self.state
+    }
+
+    pub fn into_state(self) -> DeltaState {
+        self.state
+    }
+
+    fn request_page(&mut self, url: String) -> GraphResult<Option<DeltaEvent>> {
+        let request = GraphRequestBuilder::from_link(url.as_str(), &self.headers)?;
+        let mut response = self.client.pipeline().send(request)?;
+
+        // Only restart once in a row so a resync url that also
+        // returns 410 does not loop.
+        if response.status() == StatusCode::GONE && !self.resynced {
+            let location = response
+                .headers()
+                .get(LOCATION)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string());
+            warn!("Delta link expired. Starting a full sync");
+            self.resynced = true;
+            self.state.reset();
+            self.next = Some(location.unwrap_or_else(|| self.state.url.clone()));
+            return Ok(Some(DeltaEvent::Resync));
+        }
+        if let Some(err) = response.error() {
+            return Err(err);
+        }
+
+        let page: Collection<serde_json::Value> = response.json()?;
+        self.resynced = false;
+        if let Some(next_link) = page.odata_next_link() {
+            self.next = Some(next_link.to_string());
+        } else if let Some(delta_link) = page.odata_delta_link() {
+            self.state.delta_link = Some(delta_link.to_string());
+        }
+        self.items = page.into_inner().into_iter();
+        Ok(None)
+    }
+}
+
+impl Iterator for DeltaSync {
+    type Item = GraphResult<DeltaEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.items.next() {
+                return Some(Ok(DeltaEvent::from(item)));
+            }
+            let url = self.next.take()?;
+            match self.request_page(url) {
+                Ok(Some(event)) => return Some(Ok(event)),
+                Ok(None) => continue,
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
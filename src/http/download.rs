@@ -1,12 +1,107 @@
 use crate::graph_error::AsRes;
-use crate::http::{GraphRequest, GraphRequestBuilder, GraphRequestType, IoTools};
+use crate::http::{
+    AsyncGraphRequest, GraphFuture, GraphRequest, GraphRequestBuilder, GraphRequestType,
+    HttpResponse, IoTools,
+};
 use crate::url::GraphUrl;
+use futures::{future, Future, Stream};
 use graph_error::{GraphFailure, GraphResult, GraphRsError};
-use reqwest::{Method, Response};
+use reqwest::header::HeaderMap;
+use reqwest::Method;
 use std::cell::{Cell, Ref, RefCell};
 use std::ffi::OsString;
+use std::fs::OpenOptions;
+use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
+use url::Url;
+
+fn parse_content_disposition(header: &str) -> Option<OsString> {
+    let mut v: Vec<&str> = header.split(';').collect();
+    v.retain(|s| !s.is_empty());
+
+    // The filename* indicates that the filename is encoded
+    if let Some(value) = v.iter().find(|s| s.starts_with("filename*=utf-8''")) {
+        let s = value.replace("filename*=utf-8''", "");
+        if let Ok(s) = percent_encoding::percent_decode(s.as_bytes()).decode_utf8() {
+            return Some(OsString::from(s.to_string()));
+        }
+    }
+
+    if let Some(value) = v.last() {
+        if value.starts_with("filename=") {
+            return Some(OsString::from(
+                value.replace("\"", "").replace("filename=", ""),
+            ));
+        }
+    }
+    None
+}
+
+fn check_file_path(path: PathBuf, overwrite_existing_file: bool) -> GraphResult<PathBuf> {
+    if path.exists() && !overwrite_existing_file {
+        return GraphRsError::DownloadFileExists {
+            name: path.to_string_lossy().to_string(),
+        }
+        .as_err_res();
+    }
+    Ok(path)
+}
+
+/// Finds the path of the file to write a download to using, in order, the file
+/// name given by the caller, the content-disposition header, or the last segment
+/// of the url.
+fn file_path(
+    dir: &Path,
+    file_name: Option<OsString>,
+    headers: &HeaderMap,
+    url: &Url,
+    overwrite_existing_file: bool,
+) -> GraphResult<PathBuf> {
+    // If a filename was specified beforehand.
+    if let Some(name) = file_name {
+        if name.len() <= 255 {
+            return check_file_path(dir.join(name), overwrite_existing_file);
+        }
+    }
+
+    // The content-disposition header, if available, may include the
+    // filename either in its normal form or percent encoded.
+    if let Some(value) = headers.get("content-disposition") {
+        if let Ok(s) = std::str::from_utf8(value.as_ref()) {
+            if let Some(name) = parse_content_disposition(s) {
+                if name.len() <= 255 {
+                    return check_file_path(dir.join(name), overwrite_existing_file);
+                }
+            }
+        }
+    }
+
+    // This is a last ditch effort to find the file name and it
+    // may not be the correct one.
+    if let Some(name) = url
+        .path_segments()
+        .and_then(std::iter::Iterator::last)
+        .and_then(|name| if name.is_empty() { None } else { Some(name) })
+    {
+        if name.len() <= 255 {
+            return check_file_path(dir.join(name), overwrite_existing_file);
+        }
+    }
+
+    GraphRsError::DownloadFileName.as_err_res()
+}
+
+fn create_dir(path: &Path, create_dir_all: bool) -> GraphResult<()> {
+    // Create the directory if it does not exist.
+    if create_dir_all {
+        IoTools::create_dir(path)?;
+    } else if !path.exists() {
+        let dir = path.to_string_lossy().to_string();
+        return GraphRsError::DownloadDirNoExists { dir }.as_err_res();
+    }
+    Ok(())
+}
 
 /// Provides an abstraction for downloading files.
 pub struct DownloadClient {
@@ -16,14 +111,12 @@ pub struct DownloadClient {
     file_name: RefCell<Option<OsString>>,
     extension: RefCell<Option<String>>,
     request: RefCell<GraphRequestBuilder>,
-    client: RefCell<GraphRequest>,
+    client: GraphRequest,
 }
 
 impl DownloadClient {
-    pub fn new(token: &str, request: GraphRequestBuilder) -> DownloadClient {
+    pub fn new(client: GraphRequest, request: GraphRequestBuilder) -> DownloadClient {
         let path = request.download_dir.clone().unwrap();
-        let mut client = GraphRequest::default();
-        client.set_token(token);
         DownloadClient {
             path: RefCell::new(path),
             create_dir_all: Cell::new(true),
@@ -31,7 +124,7 @@ impl DownloadClient {
             file_name: RefCell::new(None),
             extension: RefCell::new(None),
             request: RefCell::new(request),
-            client: RefCell::new(client),
+            client,
         }
     }
 
@@ -94,121 +187,172 @@ impl DownloadClient {
         request.url.format(format);
     }
 
-    fn parse_content_disposition(&self, header: &str) -> Option<OsString> {
-        let mut v: Vec<&str> = header.split(';').collect();
-        v.retain(|s| !s.is_empty());
-
-        // The filename* indicates that the filename is encoded
-        if let Some(value) = v.iter().find(|s| s.starts_with("filename*=utf-8''")) {
-            let s = value.replace("filename*=utf-8''", "");
-            if let Ok(s) = percent_encoding::percent_decode(s.as_bytes()).decode_utf8() {
-                return Some(OsString::from(s.to_string()));
-            }
-        }
-
-        if let Some(value) = v.last() {
-            if value.starts_with("filename=") {
-                return Some(OsString::from(
-                    value.replace("\"", "").replace("filename=", ""),
-                ));
-            }
-        }
-        None
-    }
-
     fn download(&self) -> GraphResult<PathBuf> {
         let path = self.path.replace(PathBuf::new());
-
-        // Create the directory if it does not exist.
-        if self.is_create_dir_all() {
-            IoTools::create_dir(path.as_path())?;
-        } else if !path.exists() {
-            let dir = path.to_string_lossy().to_string();
-            return GraphRsError::DownloadDirNoExists { dir }.as_err_res();
-        }
+        create_dir(path.as_path(), self.is_create_dir_all())?;
 
         if self.request.borrow().req_type == GraphRequestType::Redirect {
             let request = self.request.replace(GraphRequestBuilder::default());
-            let mut response = self.client.borrow_mut().build(request).send()?;
-
-            if let Some(err) = GraphFailure::from_response(&mut response) {
-                return Err(err);
-            }
+            let response = self.client.response(request)?;
             let mut request = GraphRequestBuilder::new(GraphUrl::from(response.url().clone()));
             request.set_method(Method::GET);
             self.request.replace(request);
         }
 
         let request = self.request.replace(GraphRequestBuilder::default());
-        let mut response = self.client.borrow_mut().build(request).send()?;
+        let response = self.client.response(request)?;
 
-        if let Some(err) = GraphFailure::from_response(&mut response) {
-            return Err(err);
-        }
+        let path = file_path(
+            path.as_path(),
+            self.file_name.replace(None),
+            response.headers(),
+            response.url(),
+            self.is_overwrite_existing_file(),
+        )?;
+        self.finish((path, response))
+    }
 
-        // If a filename was specified beforehand.
-        if self.file_name.borrow().is_some() {
-            let name = self.file_name.replace(None).unwrap();
-            if name.len() <= 255 {
-                let path = path.join(name);
-                if path.exists() && !self.is_overwrite_existing_file() {
-                    return GraphRsError::DownloadFileExists {
-                        name: path.to_string_lossy().to_string(),
-                    }
-                    .as_err_res();
-                }
-                return self.finish((path, response));
-            }
+    fn finish(&self, values: (PathBuf, HttpResponse)) -> GraphResult<PathBuf> {
+        if self.extension.borrow().is_some() {
+            values
+                .0
+                .with_extension(self.extension.replace(None).unwrap().as_str());
         }
+        IoTools::copy(values)
+    }
+}
 
-        // The content-disposition header, if available, may include the
-        // filename either in its normal form or percent encoded.
-        if let Some(value) = response.headers().get("content-disposition") {
-            if let Ok(s) = std::str::from_utf8(value.as_ref()) {
-                if let Some(name) = self.parse_content_disposition(s) {
-                    if name.len() <= 255 {
-                        let path = path.join(name);
-                        if path.exists() && !self.is_overwrite_existing_file() {
-                            return GraphRsError::DownloadFileExists {
-                                name: path.to_string_lossy().to_string(),
-                            }
-                            .as_err_res();
-                        }
-                        return self.finish((path, response));
-                    }
-                }
-            }
-        }
+/// The async counterpart to `DownloadClient`. The response body is written
+/// to the file as a stream of chunks.
+pub struct AsyncDownload {
+    path: PathBuf,
+    create_dir_all: bool,
+    overwrite_existing_file: bool,
+    file_name: Option<OsString>,
+    extension: Option<String>,
+    request: GraphRequestBuilder,
+    client: AsyncGraphRequest,
+}
 
-        // This is a last ditch effort to find the file name and it
-        // may not be the correct one.
-        if let Some(name) = response
-            .url()
-            .path_segments()
-            .and_then(std::iter::Iterator::last)
-            .and_then(|name| if name.is_empty() { None } else { Some(name) })
-        {
-            if name.len() <= 255 {
-                let path = path.join(name);
-                if path.exists() && !self.is_overwrite_existing_file() {
-                    return GraphRsError::DownloadFileExists {
-                        name: path.to_string_lossy().to_string(),
-                    }
-                    .as_err_res();
-                }
-                return self.finish((path, response));
-            }
+impl AsyncDownload {
+    pub fn new(client: AsyncGraphRequest, request: GraphRequestBuilder) -> AsyncDownload {
+        let path = request.download_dir.clone().unwrap_or_default();
+        AsyncDownload {
+            path,
+            create_dir_all: true,
+            overwrite_existing_file: false,
+            file_name: None,
+            extension: None,
+            request,
+            client,
         }
+    }
 
-        GraphRsError::DownloadFileName.as_err_res()
+    pub fn create_dir_all(&mut self, value: bool) -> &mut Self {
+        self.create_dir_all = value;
+        self
     }
 
-    fn finish(&self, values: (PathBuf, Response)) -> GraphResult<PathBuf> {
-        if self.extension.borrow().is_some() {
-            values
-                .0
-                .with_extension(self.extension.replace(None).unwrap().as_str());
+    pub fn is_create_dir_all(&self) -> bool {
+        self.create_dir_all
+    }
+
+    pub fn overwrite_existing_file(&mut self, value: bool) -> &mut Self {
+        self.overwrite_existing_file = value;
+        self
+    }
+
+    pub fn is_overwrite_existing_file(&self) -> bool {
+        self.overwrite_existing_file
+    }
+
+    pub fn rename(&mut self, value: OsString) -> &mut Self {
+        self.file_name = Some(value);
+        self
+    }
+
+    pub fn set_extension(&mut self, value: &str) -> &mut Self {
+        self.extension = Some(value.into());
+        self
+    }
+
+    pub fn set_dir<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        self.path = path.as_ref().to_path_buf();
+        self
+    }
+
+    pub fn directory(&self) -> &PathBuf {
+        &self.path
+    }
+
+    pub fn file_name(&self) -> Option<&OsString> {
+        self.file_name.as_ref()
+    }
+
+    pub fn extension(&self) -> Option<&String> {
+        self.extension.as_ref()
+    }
+
+    pub fn url(&self) -> GraphUrl {
+        self.request.url().clone()
+    }
+
+    pub fn format(&mut self, format: &str) -> &mut Self {
+        self.request.url.format(format);
+        self
+    }
+
+    /// Sends the request and writes each chunk of the response
+    /// body to the file as it is received.
+    pub fn send(self) -> GraphFuture<PathBuf> {
+        let AsyncDownload {
+            path,
+            create_dir_all,
+            overwrite_existing_file,
+            file_name,
+            extension,
+            mut request,
+            client,
+        } = self;
+
+        // The download follows the redirect to the download url.
+        request.set_request_type(GraphRequestType::Basic);
+        if let Err(err) = create_dir(path.as_path(), create_dir_all) {
+            return Box::new(future::err(err));
         }
-        IoTools::copy(values)
+
+        Box::new(
+            client
+                .response(request)
+                .and_then(move |mut response| -> GraphResult<GraphFuture<PathBuf>> {
+                    let file_path = file_path(
+                        path.as_path(),
+                        file_name,
+                        response.headers(),
+                        response.url(),
+                        overwrite_existing_file,
+                    )?;
+                    let file_path = match extension {
+                        Some(ext) => file_path.with_extension(ext.as_str()),
+                        None => file_path,
+                    };
+                    let file = OpenOptions::new()
+                        .create(true)
+                        .write(true)
+                        .read(true)
+                        .open(&file_path)?;
+                    Ok(Box::new(
+                        response
+                            .take_stream()
+                            .fold(file, |mut file, chunk| {
+                                file.write_all(chunk.as_slice())
+                                    .map(|_| file)
+                                    .map_err(GraphFailure::from)
+                            })
+                            .map(move |_| file_path),
+                    ))
+                })
+                .flatten(),
+        )
     }
 }
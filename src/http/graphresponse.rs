@@ -1,18 +1,32 @@
+use crate::client::Graph;
+use crate::http::{AsyncJobPoller, HttpResponse, CLIENT_REQUEST_ID, REQUEST_ID};
 use crate::types::asyncjobstatus::AsyncJobStatus;
 use crate::types::content::Content;
 use crate::types::delta::{DeltaLink, MetadataLink, NextLink};
+use crate::types::etag::ETag;
+use crate::types::prefer::{Prefer, PREFERENCE_APPLIED};
 use from_as::TryFrom;
 use graph_error::{GraphError, GraphFailure, GraphResult};
+use reqwest::header::{HeaderMap, ETAG};
+use reqwest::StatusCode;
+use url::Url;
 
 #[derive(Debug)]
 pub struct GraphResponse<T> {
     value: T,
-    response: reqwest::Response,
+    status: StatusCode,
+    headers: HeaderMap,
+    url: Url,
 }
 
 impl<T> GraphResponse<T> {
-    pub fn new(response: reqwest::Response, value: T) -> GraphResponse<T> {
-        GraphResponse { value, response }
+    pub fn new(response: HttpResponse, value: T) -> GraphResponse<T> {
+        GraphResponse {
+            value,
+            status: response.status(),
+            headers: response.headers().to_owned(),
+            url: response.url().clone(),
+        }
     }
 
     pub fn value(&self) -> &T {
@@ -23,16 +37,38 @@ impl<T> GraphResponse<T> {
         self.value
     }
 
-    pub fn response(&self) -> &reqwest::Response {
-        &self.response
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// The client-request-id sent with the request.
+    pub fn client_request_id(&self) -> Option<String> {
+        self.header(CLIENT_REQUEST_ID)
+    }
+
+    /// The id the API gave the request.
+    pub fn request_id(&self) -> Option<String> {
+        self.header(REQUEST_ID)
     }
 
-    pub fn response_mut(&mut self) -> &mut reqwest::Response {
-        &mut self.response
+    fn header(&self, name: &str) -> Option<String> {
+        self.headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string())
     }
 
-    pub fn status(&self) -> reqwest::StatusCode {
-        self.response.status()
+    /// The preferences the API applied, from the Preference-Applied header.
+    pub fn preference_applied(&self) -> Prefer {
+        Prefer::from_headers(&self.headers, PREFERENCE_APPLIED)
     }
 
     pub fn success(&mut self) -> bool {
@@ -48,26 +84,20 @@ impl<T> GraphResponse<T> {
         None
     }
 
-    pub fn async_job_status(&mut self) -> Option<GraphResult<AsyncJobStatus>> {
-        let headers = self.response.headers();
+    pub fn async_job_status(&mut self, client: &Graph) -> Option<GraphResult<AsyncJobStatus>> {
         // The location header contains the URL for monitoring progress.
-        let location: &reqwest::header::HeaderValue = headers.get(reqwest::header::LOCATION)?;
-        let location_str = location.to_str().ok()?;
-        let response = reqwest::Client::new()
-            .get(location_str)
-            .send()
-            .map_err(GraphFailure::from);
-        if let Ok(mut response) = response {
-            if let Some(err) = GraphFailure::from_response(&mut response) {
-                return Some(Err(err));
-            }
-            Some(response.json().map_err(GraphFailure::from))
-        } else if let Err(e) = response {
-            Some(Err(e))
-        } else {
-            None
+        let location = self.headers.get(reqwest::header::LOCATION)?.to_str().ok()?;
+        match Url::parse(location) {
+            Ok(url) => Some(AsyncJobPoller::new(client.request().clone(), url).status()),
+            Err(e) => Some(Err(GraphFailure::from(e))),
         }
     }
+
+    /// A poller that follows the monitor url of a long running operation
+    /// until it is completed, such as after copying a drive item.
+    pub fn async_job(&self, client: &Graph) -> GraphResult<AsyncJobPoller> {
+        AsyncJobPoller::from_response(client.request().clone(), self)
+    }
 }
 
 impl<T> AsRef<T> for GraphResponse<T> {
@@ -82,22 +112,22 @@ impl<T> AsMut<T> for GraphResponse<T> {
     }
 }
 
-impl<T> TryFrom<reqwest::Response> for GraphResponse<T>
+impl<T> TryFrom<HttpResponse> for GraphResponse<T>
 where
     for<'de> T: serde::Deserialize<'de>,
 {
     type Error = GraphFailure;
 
-    fn try_from(mut response: reqwest::Response) -> GraphResult<GraphResponse<T>> {
+    fn try_from(mut response: HttpResponse) -> GraphResult<GraphResponse<T>> {
         let value: T = response.json()?;
-        Ok(GraphResponse { value, response })
+        Ok(GraphResponse::new(response, value))
     }
 }
 
-impl TryFrom<reqwest::Response> for GraphResponse<Content> {
+impl TryFrom<HttpResponse> for GraphResponse<Content> {
     type Error = GraphFailure;
 
-    fn try_from(mut value: reqwest::Response) -> Result<Self, Self::Error> {
+    fn try_from(mut value: HttpResponse) -> Result<Self, Self::Error> {
         if let Ok(content) = value.text() {
             Ok(GraphResponse::new(value, Content::from(content)))
         } else {
@@ -132,3 +162,31 @@ where
         self.value.metadata_link()
     }
 }
+
+impl<T> ETag for GraphResponse<T>
+where
+    T: ETag,
+{
+    fn odata_etag(&self) -> Option<String> {
+        self.value.odata_etag()
+    }
+
+    fn e_tag(&self) -> Option<String> {
+        self.value.e_tag()
+    }
+
+    fn c_tag(&self) -> Option<String> {
+        self.value.c_tag()
+    }
+
+    /// The ETag of the value or, if the value has none,
+    /// the ETag header of the response.
+    fn etag(&self) -> Option<String> {
+        self.value.etag().or_else(|| {
+            self.headers
+                .get(ETAG)
+                .and_then(|value| value.to_str().ok())
+                .map(|value| value.to_string())
+        })
+    }
+}
@@ -1,41 +1,60 @@
 use crate::client::*;
-use crate::http::{GraphResponse, UploadSessionClient};
+use crate::http::{
+    AsyncGraphRequest, AsyncUploadSessionClient, DeltaState, GraphFuture, GraphRequest,
+    GraphResponse, GraphStream, Items, Pages, PreparedRequest, RequestClient, UploadSessionClient,
+    CLIENT_REQUEST_ID,
+};
+use crate::types::collection::Collection;
 use crate::types::delta::{Delta, NextLink};
+use crate::types::prefer::{Prefer, Preference};
 use crate::types::{content::Content, delta::DeltaRequest};
+use crate::url::{Expand, Filter, GraphUrl};
+use futures::future;
 use graph_error::{GraphFailure, GraphResult};
-use reqwest::header::{HeaderValue, IntoHeaderName, CONTENT_TYPE};
+use reqwest::header::{HeaderName, HeaderValue, IntoHeaderName, IF_MATCH, IF_NONE_MATCH};
 use std::cell::RefCell;
 use std::convert::TryFrom;
 use std::marker::PhantomData;
 use std::sync::mpsc::{channel, Receiver};
-use std::thread;
+
+/// The header used to request advanced queries on directory objects.
+pub static CONSISTENCY_LEVEL: &str = "consistencylevel";
 
 /// A trait for sending an API request and converting the response
 /// to a suitable Rust type.
+///
+/// For the blocking client the output is the response itself and for the
+/// async client the output is a future or stream of the response.
 pub trait ToResponse {
     type Output;
 
     fn send(&self) -> Self::Output;
 }
 
-pub struct IntoResponse<'a, T> {
-    client: &'a Graph,
+pub struct IntoResponse<'a, T, Client = GraphRequest> {
+    client: RequestChain<'a, Client>,
     ident: PhantomData<T>,
     error: RefCell<Option<GraphFailure>>,
 }
 
-impl<'a, T> IntoResponse<'a, T> {
-    pub fn new(client: &'a Graph) -> IntoResponse<'a, T> {
+impl<'a, T, Client> IntoResponse<'a, T, Client>
+where
+    Client: RequestClient,
+{
+    pub fn new(client: &RequestChain<'a, Client>) -> IntoResponse<'a, T, Client> {
         IntoResponse {
-            client,
+            client: client.split(),
             ident: PhantomData,
             error: RefCell::new(None),
         }
     }
 
-    pub(crate) fn new_error(client: &'a Graph, error: GraphFailure) -> IntoResponse<'a, T> {
+    pub(crate) fn new_error(
+        client: &RequestChain<'a, Client>,
+        error: GraphFailure,
+    ) -> IntoResponse<'a, T, Client> {
         IntoResponse {
-            client,
+            client: client.split(),
             ident: PhantomData,
             error: RefCell::new(Some(error)),
         }
@@ -56,21 +75,76 @@ impl<'a, T> IntoResponse<'a, T> {
         self
     }
 
+    /// Expand properties using nested query options.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let response = client.v1()
+    ///     .me()
+    ///     .drive()
+    ///     .root()
+    ///     .expand_with(&[Expand::new("children").select(&["id", "name"])])
+    ///     .send()?;
+    /// ```
+    pub fn expand_with(&self, value: &[Expand]) -> &Self {
+        self.client.builder().as_mut().expand_with(value);
+        self
+    }
+
     pub fn filter(&self, value: &[&str]) -> &Self {
         self.client.builder().as_mut().filter(value);
         self
     }
 
+    /// Add a typed filter expression. Values in the expression are
+    /// quoted and escaped.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let response = client.v1()
+    ///     .me()
+    ///     .drive()
+    ///     .root_children()
+    ///     .filter_by(&Filter::starts_with("name", "report").and(Filter::gt("size", 1024)))
+    ///     .send()?;
+    /// ```
+    pub fn filter_by(&self, filter: &Filter) -> &Self {
+        self.client.builder().as_mut().filter_by(filter);
+        self
+    }
+
     pub fn order_by(&self, value: &[&str]) -> &Self {
         self.client.builder().as_mut().order_by(value);
         self
     }
 
+    /// Add `$search`. Directory objects such as users and groups
+    /// require the ConsistencyLevel header, which is added and sent
+    /// with each next link.
     pub fn search(&self, value: &str) -> &Self {
         self.client.builder().as_mut().search(value);
+        self.consistency_level_eventual()
+    }
+
+    /// Add `$count` and the ConsistencyLevel header required for
+    /// counting directory objects. The header is sent with each next link.
+    pub fn count(&self, value: bool) -> &Self {
+        self.client.builder().as_mut().count_items(value);
+        if value {
+            self.consistency_level_eventual();
+        }
         self
     }
 
+    /// Set the ConsistencyLevel header to eventual. This is needed for
+    /// advanced queries on directory objects.
+    pub fn consistency_level_eventual(&self) -> &Self {
+        self.header(
+            HeaderName::from_static(CONSISTENCY_LEVEL),
+            HeaderValue::from_static("eventual"),
+        )
+    }
+
     pub fn format(&self, value: &str) -> &Self {
         self.client.builder().as_mut().format(value);
         self
@@ -81,6 +155,11 @@ impl<'a, T> IntoResponse<'a, T> {
         self
     }
 
+    pub fn skip_token(&self, value: &str) -> &Self {
+        self.client.builder().as_mut().skip_token(value);
+        self
+    }
+
     pub fn top(&self, value: &str) -> &Self {
         self.client.builder().as_mut().top(value);
         self
@@ -91,73 +170,123 @@ impl<'a, T> IntoResponse<'a, T> {
         self
     }
 
+    /// Only send the request if the ETag of the resource matches. Use `*`
+    /// to match any version. If the resource has changed the request fails
+    /// with `GraphFailure::PreconditionFailed`.
+    pub fn if_match(&self, etag: &str) -> &Self {
+        self.etag_header(IF_MATCH, etag)
+    }
+
+    /// Only send the request if the ETag of the resource does not match.
+    /// Use `*` to only create a resource if it does not exist.
+    pub fn if_none_match(&self, etag: &str) -> &Self {
+        self.etag_header(IF_NONE_MATCH, etag)
+    }
+
+    fn etag_header(&self, name: HeaderName, etag: &str) -> &Self {
+        match HeaderValue::from_str(etag) {
+            Ok(value) => self.header(name, value),
+            Err(_) => {
+                self.error
+                    .replace(Some(GraphFailure::invalid("etag header value")));
+                self
+            },
+        }
+    }
+
+    /// Send the request with this client-request-id instead of a generated
+    /// one. The id is returned in the response and in error responses.
+    pub fn client_request_id(&self, id: &str) -> &Self {
+        match HeaderValue::from_str(id) {
+            Ok(value) => self.header(CLIENT_REQUEST_ID, value),
+            Err(_) => {
+                self.error.replace(Some(GraphFailure::invalid(
+                    "client-request-id header value",
+                )));
+                self
+            },
+        }
+    }
+
+    /// Add a preference to the Prefer header, replacing a preference
+    /// with the same name including one set as a client default.
+    pub fn prefer(&self, preference: Preference) -> &Self {
+        Prefer::from(preference).apply(&mut self.client.builder().headers);
+        self
+    }
+
+    /// Skip the response cache for this request. The response is
+    /// not served from or stored in the cache. No header is added
+    /// to the request.
+    pub fn bypass_cache(&self) -> &Self {
+        self.client.builder().set_bypass_cache(true);
+        self
+    }
+
+    /// Inspect the url of this request.
+    pub fn url_ref<F>(&self, f: F)
+    where
+        F: Fn(&GraphUrl),
+    {
+        self.client.url_ref(f)
+    }
+
+    /// The request as it was built, without the headers that are
+    /// added when it is sent.
+    pub(crate) fn built(&self) -> GraphResult<PreparedRequest> {
+        if self.error.borrow().is_some() {
+            return Err(self.error.replace(None).unwrap());
+        }
+        PreparedRequest::try_from(&*self.client.builder())
+    }
+
+    /// The url the request chain started with, such as
+    /// `https://graph.microsoft.com/v1.0`.
+    pub(crate) fn base_url(&self) -> &GraphUrl {
+        self.client.base()
+    }
+}
+
+impl<'a, T, Client> IntoResponse<'a, T, Client>
+where
+    Client: RequestClient + AsRef<GraphRequest>,
+{
+    /// Build the request without sending it.
+    ///
+    /// The prepared request has the method, url, headers and body that
+    /// would be sent, including the client's default headers, the
+    /// client-request-id and Accept-Encoding, with the access token
+    /// redacted. It can be sent later using `Graph::send_prepared`.
+    pub fn prepare(&self) -> GraphResult<PreparedRequest> {
+        if self.error.borrow().is_some() {
+            return Err(self.error.replace(None).unwrap());
+        }
+        self.client
+            .request()
+            .as_ref()
+            .prepare(&*self.client.builder())
+    }
+}
+
+impl<'a, T, Client> Drop for IntoResponse<'a, T, Client> {
+    fn drop(&mut self) {
+        self.client.record_url();
+    }
+}
+
+impl<'a, T> IntoResponse<'a, T, GraphRequest> {
     fn delta<U: 'static + Send + NextLink + Clone>(&self) -> Receiver<Delta<U>>
     where
         for<'de> U: serde::Deserialize<'de>,
     {
-        let (sender, receiver) = channel();
         if self.error.borrow().is_some() {
+            let (sender, receiver) = channel();
             sender.send(Delta::Done(self.error.replace(None))).unwrap();
             return receiver;
         }
 
         let builder = self.client.take_builder();
-        let response: GraphResult<GraphResponse<U>> = self.client.request().execute(builder);
-        if let Err(err) = response {
-            sender.send(Delta::Done(Some(err))).unwrap();
-            return receiver;
-        }
-
-        let token = self.client.request().token().clone();
-        let response = response.unwrap();
-        let mut next_link = response.value().next_link();
-        sender.send(Delta::Next(response)).unwrap();
-
-        thread::spawn(move || {
-            let mut is_done = false;
-            let client = reqwest::Client::new();
-            while let Some(next) = next_link {
-                let res = client
-                    .get(next.as_str())
-                    .header(CONTENT_TYPE, "application/json")
-                    .bearer_auth(token.as_str())
-                    .send()
-                    .map_err(GraphFailure::from);
-
-                if let Err(err) = res {
-                    next_link = None;
-                    sender.send(Delta::Done(Some(err))).unwrap();
-                    is_done = true;
-                } else {
-                    let mut response = res.unwrap();
-                    if let Some(err) = GraphFailure::from_response(&mut response) {
-                        next_link = None;
-                        sender.send(Delta::Done(Some(err))).unwrap();
-                        is_done = true;
-                    } else {
-                        let value_res: GraphResult<U> = response.json().map_err(GraphFailure::from);
-                        match value_res {
-                            Ok(value) => {
-                                next_link = value.next_link();
-                                sender
-                                    .send(Delta::Next(GraphResponse::new(response, value)))
-                                    .unwrap();
-                            },
-                            Err(err) => {
-                                next_link = None;
-                                sender.send(Delta::Done(Some(err))).unwrap();
-                                is_done = true;
-                            },
-                        }
-                    }
-                }
-            }
-            if !is_done {
-                sender.send(Delta::Done(None)).unwrap();
-            }
-        });
-
-        receiver
+        self.client.request().delta(builder)
     }
 
     pub fn json<U>(&self) -> GraphResult<U>
@@ -172,7 +301,26 @@ impl<'a, T> IntoResponse<'a, T> {
     }
 }
 
-impl<'a, T> ToResponse for IntoResponse<'a, T>
+impl<'a, T> IntoResponse<'a, Collection<T>, GraphRequest>
+where
+    for<'de> T: serde::Deserialize<'de>,
+{
+    /// An iterator over the pages of the collection that follows next links.
+    pub fn pages(&self) -> Pages<T> {
+        let client = self.client.request().clone();
+        if self.error.borrow().is_some() {
+            return Pages::error(client, self.error.replace(None).unwrap());
+        }
+        Pages::new(client, self.client.take_builder())
+    }
+
+    /// An iterator over the items in each page of the collection.
+    pub fn items(&self) -> Items<T> {
+        self.pages().items()
+    }
+}
+
+impl<'a, T> ToResponse for IntoResponse<'a, T, GraphRequest>
 where
     for<'de> T: serde::Deserialize<'de>,
 {
@@ -187,7 +335,7 @@ where
     }
 }
 
-impl<'a> ToResponse for IntoResponse<'a, UploadSessionClient> {
+impl<'a> ToResponse for IntoResponse<'a, UploadSessionClient, GraphRequest> {
     type Output = GraphResult<UploadSessionClient>;
 
     fn send(&self) -> Self::Output {
@@ -200,7 +348,7 @@ impl<'a> ToResponse for IntoResponse<'a, UploadSessionClient> {
     }
 }
 
-impl<'a> ToResponse for IntoResponse<'a, GraphResponse<Content>> {
+impl<'a> ToResponse for IntoResponse<'a, GraphResponse<Content>, GraphRequest> {
     type Output = GraphResult<GraphResponse<Content>>;
 
     fn send(&self) -> Self::Output {
@@ -213,7 +361,22 @@ impl<'a> ToResponse for IntoResponse<'a, GraphResponse<Content>> {
     }
 }
 
-impl<'a, T: 'static + Send + NextLink + Clone> ToResponse for IntoResponse<'a, DeltaRequest<T>>
+impl<'a, T, Client> IntoResponse<'a, DeltaRequest<T>, Client>
+where
+    Client: RequestClient,
+{
+    /// A delta state that starts with a full sync of this request.
+    /// Use `Graph::delta_sync` to run the sync.
+    pub fn delta_state(&self) -> GraphResult<DeltaState> {
+        if self.error.borrow().is_some() {
+            return Err(self.error.replace(None).unwrap());
+        }
+        Ok(DeltaState::from_request(&self.client.take_builder()))
+    }
+}
+
+impl<'a, T: 'static + Send + NextLink + Clone> ToResponse
+    for IntoResponse<'a, DeltaRequest<T>, GraphRequest>
 where
     for<'de> T: serde::Deserialize<'de>,
 {
@@ -223,3 +386,74 @@ where
         self.delta::<T>()
     }
 }
+
+impl<'a, T> IntoResponse<'a, T, AsyncGraphRequest> {
+    pub fn json<U>(&self) -> GraphFuture<U>
+    where
+        for<'de> U: serde::Deserialize<'de>,
+        U: 'static + Send,
+    {
+        if self.error.borrow().is_some() {
+            return Box::new(future::err(self.error.replace(None).unwrap()));
+        }
+        self.client.request().json(self.client.take_builder())
+    }
+}
+
+impl<'a, T> ToResponse for IntoResponse<'a, T, AsyncGraphRequest>
+where
+    for<'de> T: serde::Deserialize<'de>,
+    T: 'static + Send,
+{
+    type Output = GraphFuture<GraphResponse<T>>;
+
+    fn send(&self) -> Self::Output {
+        if self.error.borrow().is_some() {
+            return Box::new(future::err(self.error.replace(None).unwrap()));
+        }
+        let builder = self.client.take_builder();
+        self.client.request().execute(builder)
+    }
+}
+
+impl<'a> ToResponse for IntoResponse<'a, UploadSessionClient, AsyncGraphRequest> {
+    type Output = GraphFuture<AsyncUploadSessionClient>;
+
+    fn send(&self) -> Self::Output {
+        if self.error.borrow().is_some() {
+            return Box::new(future::err(self.error.replace(None).unwrap()));
+        }
+        self.client
+            .request()
+            .upload_session(self.client.take_builder())
+    }
+}
+
+impl<'a> ToResponse for IntoResponse<'a, GraphResponse<Content>, AsyncGraphRequest> {
+    type Output = GraphFuture<GraphResponse<Content>>;
+
+    fn send(&self) -> Self::Output {
+        if self.error.borrow().is_some() {
+            return Box::new(future::err(self.error.replace(None).unwrap()));
+        }
+        let builder = self.client.take_builder();
+        self.client.request().execute_content(builder)
+    }
+}
+
+impl<'a, T: 'static + Send + NextLink> ToResponse
+    for IntoResponse<'a, DeltaRequest<T>, AsyncGraphRequest>
+where
+    for<'de> T: serde::Deserialize<'de>,
+{
+    type Output = GraphStream<GraphResponse<T>>;
+
+    fn send(&self) -> Self::Output {
+        if self.error.borrow().is_some() {
+            let err = self.error.replace(None).unwrap();
+            return Box::new(futures::stream::once(Err(err)));
+        }
+        let builder = self.client.take_builder();
+        self.client.request().delta(builder)
+    }
+}
@@ -1,5 +1,5 @@
+use crate::http::HttpResponse;
 use graph_error::{GraphFailure, GraphResult};
-use reqwest::Response;
 use std::fs::OpenOptions;
 use std::io::copy;
 use std::path::{Path, PathBuf};
@@ -16,7 +16,7 @@ impl IoTools {
         Ok(())
     }
 
-    pub fn copy(mut response: (PathBuf, Response)) -> GraphResult<PathBuf> {
+    pub fn copy(mut response: (PathBuf, HttpResponse)) -> GraphResult<PathBuf> {
         let (sender, receiver) = mpsc::channel();
         let handle = thread::spawn(move || {
             let mut file_writer = OpenOptions::new()
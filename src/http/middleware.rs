@@ -0,0 +1,380 @@
+use crate::http::{send_on_thread, GraphFuture, GraphRequestBuilder, HttpResponse, HttpTransport};
+use futures::{future, Future};
+use graph_error::{GraphFailure, GraphResult};
+use graph_oauth::oauth::TokenProvider;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+use reqwest::StatusCode;
+use std::io::Cursor;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A step in the pipeline that every blocking and async request goes through.
+///
+/// A middleware can change the request before calling the next step
+/// and change the response that is returned. Calling `next.run` more
+/// than once sends the request again.
+///
+/// Blocking requests use `handle` and async requests use `handle_async`.
+/// The default `handle_async` runs `handle` on a separate thread, with
+/// the rest of the pipeline sent as blocking requests on that thread.
+/// Middleware that only changes headers, or that can wait on the future
+/// of the next step, should implement `handle_async` so async requests
+/// are not sent on a separate thread.
+///
+/// # Example
+/// ```rust,ignore
+/// struct TenantHeader;
+///
+/// impl Middleware for TenantHeader {
+///     fn handle(
+///         &self,
+///         mut request: GraphRequestBuilder,
+///         next: Next,
+///     ) -> GraphResult<HttpResponse> {
+///         request.header("x-tenant", HeaderValue::from_static("contoso"));
+///         next.run(request)
+///     }
+/// }
+///
+/// client.add_middleware(TenantHeader);
+/// ```
+pub trait Middleware: Send + Sync + 'static {
+    fn handle(&self, request: GraphRequestBuilder, next: Next) -> GraphResult<HttpResponse>;
+
+    fn handle_async(
+        self: Arc<Self>,
+        request: GraphRequestBuilder,
+        next: AsyncNext,
+    ) -> GraphFuture<HttpResponse> {
+        send_on_thread(move || self.handle(request, next.blocking()))
+    }
+}
+
+/// The rest of the pipeline after the current middleware.
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    middleware: &'a [Arc<dyn Middleware>],
+    transport: &'a dyn HttpTransport,
+}
+
+impl<'a> Next<'a> {
+    /// Run the request through the rest of the pipeline.
+    pub fn run(self, request: GraphRequestBuilder) -> GraphResult<HttpResponse> {
+        match self.middleware.split_first() {
+            Some((middleware, rest)) => middleware.handle(
+                request,
+                Next {
+                    middleware: rest,
+                    transport: self.transport,
+                },
+            ),
+            None => self.transport.send(request),
+        }
+    }
+}
+
+/// The rest of the pipeline after the current middleware for an async request.
+#[derive(Clone)]
+pub struct AsyncNext {
+    middleware: Arc<[Arc<dyn Middleware>]>,
+    index: usize,
+    transport: Arc<dyn HttpTransport>,
+}
+
+impl AsyncNext {
+    /// Run the request through the rest of the pipeline.
+    pub fn run(self, request: GraphRequestBuilder) -> GraphFuture<HttpResponse> {
+        match self.middleware.get(self.index).cloned() {
+            Some(middleware) => middleware.handle_async(
+                request,
+                AsyncNext {
+                    index: self.index + 1,
+                    ..self
+                },
+            ),
+            None => self.transport.send_async(request),
+        }
+    }
+
+    /// The rest of the pipeline for sending blocking requests.
+    pub fn blocking(&self) -> Next {
+        Next {
+            middleware: &self.middleware[self.index..],
+            transport: self.transport.as_ref(),
+        }
+    }
+}
+
+/// An ordered list of middleware that ends with the transport.
+#[derive(Clone)]
+pub struct Pipeline {
+    middleware: Vec<Arc<dyn Middleware>>,
+    transport: Arc<dyn HttpTransport>,
+    token_provider: Option<Arc<dyn TokenProvider>>,
+}
+
+impl Pipeline {
+    pub fn new(
+        middleware: Vec<Arc<dyn Middleware>>,
+        transport: Arc<dyn HttpTransport>,
+    ) -> Pipeline {
+        Pipeline {
+            middleware,
+            transport,
+            token_provider: None,
+        }
+    }
+
+    /// Give each request the token provider that authorizes it, so that
+    /// middleware such as `ResponseCache` can tell users apart.
+    pub(crate) fn set_token_provider(&mut self, token_provider: Arc<dyn TokenProvider>) {
+        self.token_provider = Some(token_provider);
+    }
+
+    fn with_token_provider(&self, mut request: GraphRequestBuilder) -> GraphRequestBuilder {
+        if request.token_provider.is_none() {
+            request.token_provider = self.token_provider.clone();
+        }
+        request
+    }
+
+    pub fn middleware(&self) -> &[Arc<dyn Middleware>] {
+        self.middleware.as_slice()
+    }
+
+    /// Add a middleware to the end of the pipeline, before the transport.
+    pub fn push(&mut self, middleware: Arc<dyn Middleware>) {
+        self.middleware.push(middleware);
+    }
+
+    pub fn transport(&self) -> Arc<dyn HttpTransport> {
+        Arc::clone(&self.transport)
+    }
+
+    /// Send the request through each middleware and then the transport.
+    pub fn send(&self, request: GraphRequestBuilder) -> GraphResult<HttpResponse> {
+        Next {
+            middleware: self.middleware.as_slice(),
+            transport: self.transport.as_ref(),
+        }
+        .run(self.with_token_provider(request))
+    }
+
+    /// Send the request through each middleware and then the transport
+    /// without blocking.
+    pub fn send_async(&self, request: GraphRequestBuilder) -> GraphFuture<HttpResponse> {
+        AsyncNext {
+            middleware: Arc::from(self.middleware.clone()),
+            index: 0,
+            transport: self.transport(),
+        }
+        .run(self.with_token_provider(request))
+    }
+}
+
+/// Adds the bearer token from a token provider to the Authorization
+/// header. If the response is 401 Unauthorized the token is refreshed
+/// and the request is sent once more.
+pub struct BearerAuth {
+    token_provider: Arc<dyn TokenProvider>,
+}
+
+impl BearerAuth {
+    pub fn new(token_provider: Arc<dyn TokenProvider>) -> BearerAuth {
+        BearerAuth { token_provider }
+    }
+
+    fn authorize(request: &mut GraphRequestBuilder, token: &str) -> GraphResult<()> {
+        let bearer = format!("Bearer {}", token);
+        let value = HeaderValue::from_str(bearer.as_str())
+            .map_err(|_| GraphFailure::invalid("access token"))?;
+        request.header(AUTHORIZATION, value);
+        Ok(())
+    }
+
+    fn refreshed(
+        &self,
+        retry: Option<GraphRequestBuilder>,
+    ) -> GraphResult<Option<GraphRequestBuilder>> {
+        match retry {
+            Some(mut retry) => match self.token_provider.refresh()? {
+                Some(token) => {
+                    info!("Refreshed the access token after a 401 response");
+                    BearerAuth::authorize(&mut retry, token.as_str())?;
+                    Ok(Some(retry))
+                },
+                None => Ok(None),
+            },
+            None => Ok(None),
+        }
+    }
+}
+
+impl Middleware for BearerAuth {
+    fn handle(&self, mut request: GraphRequestBuilder, next: Next) -> GraphResult<HttpResponse> {
+        BearerAuth::authorize(&mut request, self.token_provider.bearer_token()?.as_str())?;
+        let retry = request.try_clone();
+        let response = next.run(request)?;
+        if response.status() != StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        match self.refreshed(retry)? {
+            Some(retry) => next.run(retry),
+            None => Ok(response),
+        }
+    }
+
+    /// The token provider is called on the thread that polls the future,
+    /// so a provider that requests a new token blocks while it does.
+    fn handle_async(
+        self: Arc<Self>,
+        mut request: GraphRequestBuilder,
+        next: AsyncNext,
+    ) -> GraphFuture<HttpResponse> {
+        let authorized = self
+            .token_provider
+            .bearer_token()
+            .and_then(|token| BearerAuth::authorize(&mut request, token.as_str()));
+        if let Err(err) = authorized {
+            return Box::new(future::err(err));
+        }
+
+        let retry = request.try_clone();
+        let retry_next = next.clone();
+        Box::new(
+            next.run(request)
+                .and_then(move |response| -> GraphFuture<HttpResponse> {
+                    if response.status() != StatusCode::UNAUTHORIZED {
+                        return Box::new(future::ok(response));
+                    }
+                    match self.refreshed(retry) {
+                        Ok(Some(retry)) => retry_next.run(retry),
+                        Ok(None) => Box::new(future::ok(response)),
+                        Err(err) => Box::new(future::err(err)),
+                    }
+                }),
+        )
+    }
+}
+
+/// Adds the default headers of the client, such as the User-Agent, to
+/// requests that do not set them and sets the timeout of the client on
+/// requests that do not have one.
+#[derive(Debug, Default, Clone)]
+pub struct RequestDefaults {
+    headers: HeaderMap,
+    timeout: Option<Duration>,
+}
+
+impl RequestDefaults {
+    pub fn new(headers: HeaderMap, timeout: Option<Duration>) -> RequestDefaults {
+        RequestDefaults { headers, timeout }
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty() && self.timeout.is_none()
+    }
+
+    fn apply(&self, request: &mut GraphRequestBuilder) {
+        for name in self.headers.keys() {
+            if !request.headers.contains_key(name) {
+                for value in self.headers.get_all(name).iter() {
+                    request.headers.append(name.clone(), value.clone());
+                }
+            }
+        }
+        if request.timeout.is_none() {
+            request.timeout = self.timeout;
+        }
+    }
+}
+
+impl Middleware for RequestDefaults {
+    fn handle(&self, mut request: GraphRequestBuilder, next: Next) -> GraphResult<HttpResponse> {
+        self.apply(&mut request);
+        next.run(request)
+    }
+
+    fn handle_async(
+        self: Arc<Self>,
+        mut request: GraphRequestBuilder,
+        next: AsyncNext,
+    ) -> GraphFuture<HttpResponse> {
+        self.apply(&mut request);
+        next.run(request)
+    }
+}
+
+/// Logs each request and response using the log crate. Request and
+/// response bodies are logged at the trace level.
+#[derive(Debug, Default, Clone)]
+pub struct LoggingMiddleware;
+
+impl LoggingMiddleware {
+    fn log_request(request: &GraphRequestBuilder) -> (String, String) {
+        let method = request.method.to_string();
+        let url = request.url().to_string();
+        info!("{} {}", method, url);
+        if let Some(body) = request.body() {
+            trace!("{} {} request body: {:?}", method, url, body);
+        }
+        (method, url)
+    }
+
+    fn log_body(method: &str, url: &str, response: &mut HttpResponse) -> GraphResult<()> {
+        let mut body = Vec::new();
+        response.copy_to(&mut body)?;
+        trace!(
+            "{} {} response body: {}",
+            method,
+            url,
+            String::from_utf8_lossy(&body)
+        );
+        response.set_body(Cursor::new(body));
+        Ok(())
+    }
+}
+
+impl Middleware for LoggingMiddleware {
+    fn handle(&self, request: GraphRequestBuilder, next: Next) -> GraphResult<HttpResponse> {
+        let (method, url) = LoggingMiddleware::log_request(&request);
+        let mut response = next.run(request)?;
+        info!("{} {} returned {}", method, url, response.status());
+        if log_enabled!(log::Level::Trace) {
+            LoggingMiddleware::log_body(&method, &url, &mut response)?;
+        }
+        Ok(response)
+    }
+
+    /// The response body is read into memory without blocking before
+    /// it is logged at the trace level.
+    fn handle_async(
+        self: Arc<Self>,
+        request: GraphRequestBuilder,
+        next: AsyncNext,
+    ) -> GraphFuture<HttpResponse> {
+        let (method, url) = LoggingMiddleware::log_request(&request);
+        Box::new(
+            next.run(request)
+                .and_then(move |response| -> GraphFuture<HttpResponse> {
+                    info!("{} {} returned {}", method, url, response.status());
+                    if !log_enabled!(log::Level::Trace) {
+                        return Box::new(future::ok(response));
+                    }
+                    Box::new(response.into_buffered().and_then(move |mut response| {
+                        LoggingMiddleware::log_body(&method, &url, &mut response)?;
+                        Ok(response)
+                    }))
+                }),
+        )
+    }
+}
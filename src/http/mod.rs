@@ -1,15 +1,43 @@
+mod asyncjob;
+mod asyncrequest;
 mod byterange;
+mod cache;
+mod cassette;
+mod compression;
+mod correlation;
+mod deltasync;
 mod download;
 mod graphresponse;
 mod intoresponse;
 mod iotools;
+mod middleware;
+mod pages;
+mod prepared;
+mod ratelimit;
 mod request;
+mod retry;
+mod telemetry;
+mod transport;
 mod uploadsession;
 
+pub use asyncjob::*;
+pub use asyncrequest::*;
 pub use byterange::*;
+pub use cache::*;
+pub use cassette::*;
+pub use compression::*;
+pub use correlation::*;
+pub use deltasync::*;
 pub use download::*;
 pub use graphresponse::*;
 pub use intoresponse::*;
 pub use iotools::*;
+pub use middleware::*;
+pub use pages::*;
+pub use prepared::*;
+pub use ratelimit::*;
 pub use request::*;
+pub use retry::*;
+pub use telemetry::*;
+pub use transport::*;
 pub use uploadsession::*;
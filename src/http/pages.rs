@@ -0,0 +1,158 @@
+use crate::http::{GraphRequest, GraphRequestBuilder, GraphResponse};
+use crate::types::collection::Collection;
+use crate::types::delta::NextLink;
+use graph_error::{GraphFailure, GraphResult};
+use reqwest::header::HeaderMap;
+use std::marker::PhantomData;
+
+/// An iterator over the pages of a collection.
+///
+/// Each call to `next` sends one request, starting with the original
+/// request and then following `@odata.nextLink` until there are no more
+/// pages. Requests are sent on the current thread using the client's
+/// transport, middleware and token. The headers of the original request,
+/// such as Prefer and ConsistencyLevel, are sent with each next link.
+/// Iteration stops after an error.
+///
+/// # Example
+/// ```rust,ignore
+/// for page in client.v1().me().drive().root_children().pages().max_pages(3) {
+///     let page = page?;
+///     println!("{:#?}", page.value().value());
+/// }
+/// ```
+pub struct Pages<T> {
+    client: GraphRequest,
+    next: Option<GraphResult<GraphRequestBuilder>>,
+    headers: HeaderMap,
+    max_pages: Option<usize>,
+    count: usize,
+    phantom: PhantomData<T>,
+}
+
+impl<T> Pages<T> {
+    pub(crate) fn new(client: GraphRequest, request: GraphRequestBuilder) -> Pages<T> {
+        Pages {
+            client,
+            headers: request.link_headers(),
+            next: Some(Ok(request)),
+            max_pages: None,
+            count: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    /// An iterator that only returns the error.
+    pub(crate) fn error(client: GraphRequest, error: GraphFailure) -> Pages<T> {
+        Pages {
+            client,
+            next: Some(Err(error)),
+            headers: HeaderMap::new(),
+            max_pages: None,
+            count: 0,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Stop after this many pages have been requested.
+    pub fn max_pages(mut self, max: usize) -> Pages<T> {
+        self.max_pages = Some(max);
+        self
+    }
+
+    /// An iterator over the items of each page.
+    pub fn items(self) -> Items<T> {
+        Items {
+            pages: self,
+            current: Vec::new().into_iter(),
+            max_items: None,
+            count: 0,
+        }
+    }
+}
+
+impl<T> Iterator for Pages<T>
+where
+    for<'de> T: serde::Deserialize<'de>,
+{
+    type Item = GraphResult<GraphResponse<Collection<T>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.max_pages.map_or(false, |max| self.count >= max) {
+            return None;
+        }
+        let request = match self.next.take()? {
+            Ok(request) => request,
+            Err(err) => return Some(Err(err)),
+        };
+
+        self.count += 1;
+        let response: GraphResult<GraphResponse<Collection<T>>> = self.client.execute(request);
+        if let Ok(response) = response.as_ref() {
+            let headers = &self.headers;
+            self.next = response
+                .value()
+                .next_link()
+                .map(|link| GraphRequestBuilder::from_link(link.as_str(), headers));
+        }
+        Some(response)
+    }
+}
+
+/// An iterator over the items in each page of a collection.
+///
+/// # Example
+/// ```rust,ignore
+/// let names: Vec<String> = client.v1()
+///     .me()
+///     .drive()
+///     .root_children()
+///     .items()
+///     .max_items(100)
+///     .map(|item| item.map(|item| item.name().clone().unwrap_or_default()))
+///     .collect::<GraphResult<Vec<String>>>()?;
+/// ```
+pub struct Items<T> {
+    pages: Pages<T>,
+    current: std::vec::IntoIter<T>,
+    max_items: Option<usize>,
+    count: usize,
+}
+
+impl<T> Items<T> {
+    /// Stop after this many pages have been requested.
+    pub fn max_pages(mut self, max: usize) -> Items<T> {
+        self.pages.max_pages = Some(max);
+        self
+    }
+
+    /// Stop after this many items. No more pages are requested once
+    /// the maximum is reached.
+    pub fn max_items(mut self, max: usize) -> Items<T> {
+        self.max_items = Some(max);
+        self
+    }
+}
+
+impl<T> Iterator for Items<T>
+where
+    for<'de> T: serde::Deserialize<'de>,
+{
+    type Item = GraphResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.max_items.map_or(false, |max| self.count >= max) {
+            return None;
+        }
+        loop {
+            if let Some(item) = self.current.next() {
+                self.count += 1;
+                return Some(Ok(item));
+            }
+            match self.pages.next()? {
+                Ok(page) => self.current = page.into_value().into_inner().into_iter(),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
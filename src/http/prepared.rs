@@ -0,0 +1,102 @@
+use crate::http::{GraphRequestBuilder, RequestBody};
+use crate::url::GraphUrl;
+use graph_error::{GraphFailure, GraphResult};
+use reqwest::header::{HeaderName, HeaderValue, AUTHORIZATION};
+use reqwest::Method;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// The value shown in place of the access token.
+pub const REDACTED: &str = "Bearer [REDACTED]";
+
+/// A request that has been built but not sent.
+///
+/// A prepared request has the method, final url, headers and body that
+/// would be sent, including the headers added by the client's middleware.
+/// The Authorization header is always redacted: the access token is
+/// added by the client when the request is sent. A prepared
+/// request can be serialized for logging or auditing and sent later
+/// using `Graph::send_prepared`.
+///
+/// # Example
+/// ```rust,ignore
+/// let prepared = client.v1().me().drive().get_item("ITEM_ID").prepare()?;
+/// assert_eq!("GET", prepared.method());
+/// println!("{}", serde_json::to_string_pretty(&prepared)?);
+///
+/// let response: GraphResponse<serde_json::Value> = client.send_prepared(&prepared)?;
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Getters)]
+#[get = "pub"]
+pub struct PreparedRequest {
+    method: String,
+    url: String,
+    headers: BTreeMap<String, String>,
+    body: Option<RequestBody>,
+}
+
+impl PreparedRequest {
+    /// The body as text. Returns None if there is no body or the
+    /// body is bytes or a file.
+    pub fn body_text(&self) -> Option<&str> {
+        match self.body.as_ref() {
+            Some(RequestBody::Text(text)) => Some(text.as_str()),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<&GraphRequestBuilder> for PreparedRequest {
+    type Error = GraphFailure;
+
+    fn try_from(request: &GraphRequestBuilder) -> Result<Self, Self::Error> {
+        if request.form.is_some() {
+            return Err(GraphFailure::invalid(
+                "request that can be prepared. Multipart requests cannot be prepared",
+            ));
+        }
+
+        let mut headers = BTreeMap::new();
+        for (name, value) in request.headers().iter() {
+            headers.insert(
+                name.as_str().to_string(),
+                String::from_utf8_lossy(value.as_bytes()).to_string(),
+            );
+        }
+        headers.insert(AUTHORIZATION.as_str().to_string(), REDACTED.to_string());
+
+        Ok(PreparedRequest {
+            method: request.method().to_string(),
+            url: request.url().to_string(),
+            headers,
+            body: request.body().cloned(),
+        })
+    }
+}
+
+impl TryFrom<&PreparedRequest> for GraphRequestBuilder {
+    type Error = GraphFailure;
+
+    fn try_from(prepared: &PreparedRequest) -> Result<Self, Self::Error> {
+        let method = Method::from_str(prepared.method.as_str())
+            .map_err(|_| GraphFailure::invalid("method"))?;
+        let mut request = GraphRequestBuilder::new(GraphUrl::from_str(prepared.url.as_str())?);
+        request.set_method(method);
+        request.headers.clear();
+        for (name, value) in prepared.headers.iter() {
+            let name = HeaderName::from_str(name.as_str())
+                .map_err(|_| GraphFailure::invalid("header name"))?;
+            if name == AUTHORIZATION {
+                continue;
+            }
+            let value = HeaderValue::from_str(value.as_str())
+                .map_err(|_| GraphFailure::invalid("header value"))?;
+            request.header(name, value);
+        }
+        if let Some(body) = prepared.body.clone() {
+            request.set_body(body);
+        }
+        Ok(request)
+    }
+}
@@ -0,0 +1,399 @@
+use crate::http::{
+    sleep, AsyncNext, GraphFuture, GraphRequestBuilder, GraphStream, HttpResponse, Middleware, Next,
+};
+use futures::future::{self, Loop};
+use futures::task::{self, Task};
+use futures::{Async, Future, Poll, Stream};
+use graph_error::{GraphFailure, GraphResult};
+use std::collections::HashMap;
+use std::io::Read;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// The group of APIs a request belongs to. The Graph API throttles
+/// each workload separately.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Workload {
+    /// OneDrive and SharePoint: drives, drive items and sites.
+    Drive,
+    /// Outlook: messages, mail folders, events and contacts.
+    Mail,
+    /// Directory objects such as users and groups.
+    Directory,
+    Other,
+}
+
+impl Workload {
+    /// The workload of a request url.
+    pub fn from_url(url: &Url) -> Workload {
+        // The first segment is the version of the API.
+        let segments: Vec<&str> = url
+            .path_segments()
+            .map(|segments| segments.skip(1).collect())
+            .unwrap_or_default();
+
+        let is_any = |names: &[&str]| segments.iter().any(|s| names.contains(s));
+        if is_any(&["drive", "drives", "sites"]) {
+            Workload::Drive
+        } else if is_any(&[
+            "messages",
+            "mailFolders",
+            "sendMail",
+            "events",
+            "calendar",
+            "calendars",
+            "contacts",
+            "contactFolders",
+        ]) {
+            Workload::Mail
+        } else if segments.first().map_or(false, |s| {
+            [
+                "users",
+                "groups",
+                "directoryObjects",
+                "directoryRoles",
+                "applications",
+                "servicePrincipals",
+                "organization",
+            ]
+            .contains(s)
+        }) {
+            Workload::Directory
+        } else {
+            Workload::Other
+        }
+    }
+}
+
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    per_second: f64,
+    last: Instant,
+}
+
+impl TokenBucket {
+    fn new(requests: u32, per: Duration) -> TokenBucket {
+        let secs = per.as_secs() as f64 + f64::from(per.subsec_nanos()) / 1_000_000_000.0;
+        let capacity = f64::from(requests.max(1));
+        TokenBucket {
+            capacity,
+            tokens: capacity,
+            per_second: capacity / secs.max(std::f64::EPSILON),
+            last: Instant::now(),
+        }
+    }
+
+    /// Take a token or return how long to wait for the next one.
+    fn take(&mut self) -> Option<Duration> {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last);
+        let elapsed =
+            elapsed.as_secs() as f64 + f64::from(elapsed.subsec_nanos()) / 1_000_000_000.0;
+        self.tokens = (self.tokens + elapsed * self.per_second).min(self.capacity);
+        self.last = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            let wait = (1.0 - self.tokens) / self.per_second;
+            Some(Duration::from_micros((wait * 1_000_000.0).ceil() as u64))
+        }
+    }
+}
+
+#[derive(Default)]
+struct SemaphoreState {
+    in_use: usize,
+    /// The async requests waiting for a permit.
+    waiting: Vec<Task>,
+}
+
+struct Semaphore {
+    max: usize,
+    state: Mutex<SemaphoreState>,
+    released: Condvar,
+}
+
+impl Semaphore {
+    fn acquire(semaphore: &Arc<Semaphore>) -> Permit {
+        let mut state = semaphore.state.lock().unwrap();
+        while state.in_use >= semaphore.max {
+            state = semaphore.released.wait(state).unwrap();
+        }
+        state.in_use += 1;
+        Permit {
+            semaphore: Arc::clone(semaphore),
+        }
+    }
+
+    /// Wait for a permit without blocking the thread.
+    fn acquire_async(semaphore: Arc<Semaphore>) -> GraphFuture<Permit> {
+        Box::new(future::poll_fn(move || {
+            let mut state = semaphore.state.lock().unwrap();
+            if state.in_use >= semaphore.max {
+                state.waiting.push(task::current());
+                return Ok(Async::NotReady);
+            }
+            state.in_use += 1;
+            Ok(Async::Ready(Permit {
+                semaphore: Arc::clone(&semaphore),
+            }))
+        }))
+    }
+}
+
+struct Permit {
+    semaphore: Arc<Semaphore>,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        let mut state = self.semaphore.state.lock().unwrap();
+        state.in_use -= 1;
+        // Every waiting async request is woken because one that has
+        // been dropped would not take the permit.
+        for task in state.waiting.drain(..) {
+            task.notify();
+        }
+        self.semaphore.released.notify_one();
+    }
+}
+
+/// A response body that keeps the permits of its request until the
+/// whole body has been read or the body is dropped.
+struct PermitReader<P> {
+    body: Box<dyn Read + Send>,
+    permits: Option<P>,
+}
+
+impl<P> Read for PermitReader<P> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = self.body.read(buf)?;
+        if len == 0 && !buf.is_empty() {
+            self.permits.take();
+        }
+        Ok(len)
+    }
+}
+
+/// A stream body that keeps the permits of its request until the
+/// last chunk has been received or the stream is dropped.
+struct PermitStream<P> {
+    body: GraphStream<Vec<u8>>,
+    permits: Option<P>,
+}
+
+impl<P> Stream for PermitStream<P> {
+    type Item = Vec<u8>;
+    type Error = GraphFailure;
+
+    fn poll(&mut self) -> Poll<Option<Vec<u8>>, GraphFailure> {
+        let chunk = self.body.poll()?;
+        if let Async::Ready(None) = chunk {
+            self.permits.take();
+        }
+        Ok(chunk)
+    }
+}
+
+/// Move the permits into the body of the response so a request is
+/// in flight until its body has been read.
+fn hold_permits<P>(mut response: HttpResponse, permits: P) -> HttpResponse
+where
+    P: Send + 'static,
+{
+    let permits = Some(permits);
+    if response.is_stream() {
+        let body = response.take_stream();
+        response.set_stream(PermitStream { body, permits });
+    } else {
+        let body = response.take_body();
+        response.set_body(PermitReader { body, permits });
+    }
+    response
+}
+
+#[derive(Clone, Default)]
+struct Limits {
+    bucket: Option<Arc<Mutex<TokenBucket>>>,
+    concurrency: Option<Arc<Semaphore>>,
+}
+
+impl Limits {
+    fn wait_for_token(&self) {
+        if let Some(bucket) = self.bucket.as_ref() {
+            loop {
+                let wait = bucket.lock().unwrap().take();
+                match wait {
+                    Some(wait) => {
+                        debug!("Rate limit reached. Waiting {:?}", wait);
+                        thread::sleep(wait);
+                    },
+                    None => return,
+                }
+            }
+        }
+    }
+
+    fn permit(&self) -> Option<Permit> {
+        self.concurrency.as_ref().map(Semaphore::acquire)
+    }
+
+    fn wait_for_token_async(&self) -> GraphFuture<()> {
+        let bucket = match self.bucket.clone() {
+            Some(bucket) => bucket,
+            None => return Box::new(future::ok(())),
+        };
+        Box::new(future::loop_fn((), move |_| -> GraphFuture<Loop<(), ()>> {
+            let wait = bucket.lock().unwrap().take();
+            match wait {
+                Some(wait) => {
+                    debug!("Rate limit reached. Waiting {:?}", wait);
+                    Box::new(sleep(wait).map(Loop::Continue))
+                },
+                None => Box::new(future::ok(Loop::Break(()))),
+            }
+        }))
+    }
+
+    fn permit_async(&self) -> GraphFuture<Option<Permit>> {
+        match self.concurrency.clone() {
+            Some(semaphore) => Box::new(Semaphore::acquire_async(semaphore).map(Some)),
+            None => Box::new(future::ok(None)),
+        }
+    }
+}
+
+/// Limits how many requests are sent per period of time and how many
+/// requests are in flight at once. Requests over the limit wait until
+/// they can be sent instead of being throttled by the API.
+///
+/// Limits can be set for all requests and for each workload. A request
+/// has to be within both. Clones of a rate limiter share their limits, so
+/// the same limiter can be set on every client that uses a tenant.
+///
+/// Each attempt of a request that is retried counts towards the limits.
+///
+/// A request counts towards `max_concurrent` until the body of its
+/// response has been read to the end or the response is dropped, not
+/// only until the headers are received.
+///
+/// # Example
+/// ```
+/// use graph_rs::http::{RateLimiter, Workload};
+/// use std::time::Duration;
+///
+/// let limiter = RateLimiter::new()
+///     .rate_limit(100, Duration::from_secs(1))
+///     .max_concurrent(8)
+///     .workload_rate_limit(Workload::Drive, 10, Duration::from_secs(1))
+///     .workload_max_concurrent(Workload::Mail, 4);
+/// ```
+#[derive(Clone, Default)]
+pub struct RateLimiter {
+    limits: Limits,
+    workloads: HashMap<Workload, Limits>,
+}
+
+impl RateLimiter {
+    pub fn new() -> RateLimiter {
+        RateLimiter::default()
+    }
+
+    /// Send at most `requests` requests per period for all requests.
+    pub fn rate_limit(mut self, requests: u32, per: Duration) -> RateLimiter {
+        self.limits.bucket = Some(Arc::new(Mutex::new(TokenBucket::new(requests, per))));
+        self
+    }
+
+    /// Have at most `max` requests in flight at once.
+    pub fn max_concurrent(mut self, max: usize) -> RateLimiter {
+        self.limits.concurrency = Some(RateLimiter::semaphore(max));
+        self
+    }
+
+    /// Send at most `requests` requests per period for a workload.
+    pub fn workload_rate_limit(
+        mut self,
+        workload: Workload,
+        requests: u32,
+        per: Duration,
+    ) -> RateLimiter {
+        self.workloads.entry(workload).or_default().bucket =
+            Some(Arc::new(Mutex::new(TokenBucket::new(requests, per))));
+        self
+    }
+
+    /// Have at most `max` requests for a workload in flight at once.
+    pub fn workload_max_concurrent(mut self, workload: Workload, max: usize) -> RateLimiter {
+        self.workloads.entry(workload).or_default().concurrency = Some(RateLimiter::semaphore(max));
+        self
+    }
+
+    fn semaphore(max: usize) -> Arc<Semaphore> {
+        Arc::new(Semaphore {
+            max: max.max(1),
+            state: Default::default(),
+            released: Condvar::new(),
+        })
+    }
+}
+
+impl Middleware for RateLimiter {
+    fn handle(&self, request: GraphRequestBuilder, next: Next) -> GraphResult<HttpResponse> {
+        let workload = Workload::from_url(&request.to_url());
+        let workload_limits = self.workloads.get(&workload);
+
+        // Wait for a slot before a token so tokens are not used up by
+        // requests that cannot be sent yet. The workload is waited on first
+        // so requests for a busy workload do not hold up other workloads.
+        let workload_permit = workload_limits.and_then(|limits| limits.permit());
+        let permit = self.limits.permit();
+        if let Some(limits) = workload_limits {
+            limits.wait_for_token();
+        }
+        self.limits.wait_for_token();
+        let response = next.run(request)?;
+        Ok(hold_permits(response, (workload_permit, permit)))
+    }
+
+    /// Waits for the limits without blocking. Waiting for a rate limit
+    /// uses a tokio timer, so the future has to be run on a tokio runtime
+    /// when a rate limit is reached.
+    fn handle_async(
+        self: Arc<Self>,
+        request: GraphRequestBuilder,
+        next: AsyncNext,
+    ) -> GraphFuture<HttpResponse> {
+        let workload = Workload::from_url(&request.to_url());
+        let workload_limits = self.workloads.get(&workload).cloned().unwrap_or_default();
+        let limits = self.limits.clone();
+
+        // The same order as blocking requests: the workload permit, the
+        // permit for all requests, and then the tokens.
+        let permits = workload_limits
+            .permit_async()
+            .and_then(move |workload_permit| {
+                limits
+                    .permit_async()
+                    .map(move |permit| (workload_permit, permit, limits))
+            });
+        Box::new(
+            permits
+                .and_then(move |(workload_permit, permit, limits)| {
+                    workload_limits
+                        .wait_for_token_async()
+                        .and_then(move |_| limits.wait_for_token_async())
+                        .map(move |_| (workload_permit, permit))
+                })
+                .and_then(move |permits| {
+                    next.run(request)
+                        .map(move |response| hold_permits(response, permits))
+                }),
+        )
+    }
+}
@@ -1,14 +1,92 @@
-use crate::client::Ident;
-use crate::http::{DownloadClient, GraphResponse, UploadSessionClient};
+use crate::http::{
+    BearerAuth, ClientRequestId, Compression, DownloadClient, GraphResponse, HttpResponse,
+    HttpTransport, Middleware, Pipeline, PreparedRequest, RateLimiter, RequestDefaults,
+    ReqwestTransport, RetryPolicy, Telemetry, TelemetrySink, UploadSessionClient,
+    CLIENT_REQUEST_ID,
+};
+use crate::types::delta::{Delta, NextLink};
 use crate::url::GraphUrl;
 use crate::GRAPH_URL;
 use graph_error::{GraphFailure, GraphResult};
-use reqwest::header::{HeaderMap, HeaderValue, IntoHeaderName, CONTENT_TYPE};
+use graph_oauth::oauth::TokenProvider;
+use reqwest::header::{
+    HeaderMap, HeaderValue, IntoHeaderName, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE,
+};
 use reqwest::multipart;
-use reqwest::{Method, RedirectPolicy, RequestBuilder};
+use reqwest::{Method, StatusCode};
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::Duration;
 use url::Url;
 
+/// Common behavior for the clients that send a built request.
+///
+/// The request builders in this crate are generic over the client so the
+/// same builders can be used for both blocking and async requests.
+pub trait RequestClient {
+    /// The type returned when downloading a file.
+    type Download;
+
+    fn token(&self) -> String;
+
+    fn set_token(&self, token: &str);
+
+    fn download(&self, request: GraphRequestBuilder) -> Self::Download;
+}
+
+/// The body of a request.
+///
+/// Bodies are stored in a form that can be copied and sent again by any
+/// transport. Files are opened when the request is sent.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum RequestBody {
+    Text(String),
+    Bytes(Vec<u8>),
+    File(PathBuf),
+}
+
+impl RequestBody {
+    pub fn into_body(self) -> GraphResult<reqwest::Body> {
+        match self {
+            RequestBody::Text(text) => Ok(reqwest::Body::from(text)),
+            RequestBody::Bytes(bytes) => Ok(reqwest::Body::from(bytes)),
+            RequestBody::File(path) => Ok(reqwest::Body::from(File::open(path)?)),
+        }
+    }
+
+    /// The body for the reqwest async client. Files are read into memory.
+    pub fn into_async_body(self) -> GraphResult<reqwest::r#async::Body> {
+        match self {
+            RequestBody::Text(text) => Ok(reqwest::r#async::Body::from(text)),
+            RequestBody::Bytes(bytes) => Ok(reqwest::r#async::Body::from(bytes)),
+            RequestBody::File(path) => Ok(reqwest::r#async::Body::from(std::fs::read(path)?)),
+        }
+    }
+}
+
+impl From<String> for RequestBody {
+    fn from(text: String) -> Self {
+        RequestBody::Text(text)
+    }
+}
+
+impl From<&str> for RequestBody {
+    fn from(text: &str) -> Self {
+        RequestBody::Text(text.to_string())
+    }
+}
+
+impl From<Vec<u8>> for RequestBody {
+    fn from(bytes: Vec<u8>) -> Self {
+        RequestBody::Bytes(bytes)
+    }
+}
+
 #[derive(Clone, Eq, PartialEq)]
 pub enum GraphRequestType {
     Basic,
@@ -25,12 +103,21 @@ impl Default for GraphRequestType {
 pub struct GraphRequestBuilder {
     pub url: GraphUrl,
     pub method: Method,
-    pub body: Option<reqwest::Body>,
+    pub body: Option<RequestBody>,
     pub headers: HeaderMap<HeaderValue>,
     pub upload_session_file: Option<PathBuf>,
     pub download_dir: Option<PathBuf>,
     pub form: Option<multipart::Form>,
     pub req_type: GraphRequestType,
+    /// The timeout of the client. The reqwest transport is built with the
+    /// timeout and other transports can use it to limit the request.
+    pub timeout: Option<Duration>,
+    bypass_cache: bool,
+    /// The token provider of the client, which caching middleware uses to
+    /// keep responses for different users apart. It is set by the pipeline
+    /// before the first middleware so the token does not have to be added
+    /// to the request headers.
+    pub(crate) token_provider: Option<Arc<dyn TokenProvider>>,
 }
 
 impl GraphRequestBuilder {
@@ -46,6 +133,9 @@ impl GraphRequestBuilder {
             download_dir: None,
             form: None,
             req_type: Default::default(),
+            timeout: None,
+            bypass_cache: false,
+            token_provider: None,
         }
     }
 
@@ -71,15 +161,20 @@ impl GraphRequestBuilder {
         self
     }
 
-    pub fn body(&self) -> Option<&reqwest::Body> {
+    pub fn body(&self) -> Option<&RequestBody> {
         self.body.as_ref()
     }
 
-    pub fn set_body<B: Into<reqwest::Body>>(&mut self, body: B) -> &mut Self {
+    pub fn set_body<B: Into<RequestBody>>(&mut self, body: B) -> &mut Self {
         self.body = Some(body.into());
         self
     }
 
+    pub fn set_body_file<P: AsRef<Path>>(&mut self, file: P) -> &mut Self {
+        self.body = Some(RequestBody::File(file.as_ref().to_path_buf()));
+        self
+    }
+
     pub fn headers(&self) -> &HeaderMap<HeaderValue> {
         &self.headers
     }
@@ -109,6 +204,62 @@ impl GraphRequestBuilder {
         self.req_type = req_type;
         self
     }
+
+    /// Whether response caches should skip this request. This is not sent
+    /// as a header.
+    pub fn bypass_cache(&self) -> bool {
+        self.bypass_cache
+    }
+
+    pub fn set_bypass_cache(&mut self, bypass_cache: bool) -> &mut Self {
+        self.bypass_cache = bypass_cache;
+        self
+    }
+
+    /// The headers to send with the next and delta links of this request.
+    /// Every header is kept, such as Prefer and ConsistencyLevel, except
+    /// for Authorization, Content-Length and the client-request-id.
+    pub fn link_headers(&self) -> HeaderMap<HeaderValue> {
+        let mut headers = self.headers.clone();
+        headers.remove(AUTHORIZATION);
+        headers.remove(CONTENT_LENGTH);
+        headers.remove(CLIENT_REQUEST_ID);
+        headers
+    }
+
+    /// A GET request for a next or delta link that is sent with the
+    /// headers from `link_headers`.
+    pub fn from_link(link: &str, headers: &HeaderMap<HeaderValue>) -> GraphResult<Self> {
+        let mut request = GraphRequestBuilder::from(Url::parse(link)?);
+        for name in headers.keys() {
+            request.headers.remove(name);
+        }
+        for (name, value) in headers.iter() {
+            request.headers.append(name.clone(), value.clone());
+        }
+        Ok(request)
+    }
+
+    /// Copy the request so that it can be sent again. Returns None
+    /// for multipart requests because the form cannot be copied.
+    pub fn try_clone(&self) -> Option<GraphRequestBuilder> {
+        if self.form.is_some() {
+            return None;
+        }
+        Some(GraphRequestBuilder {
+            url: self.url.clone(),
+            method: self.method.clone(),
+            body: self.body.clone(),
+            headers: self.headers.clone(),
+            upload_session_file: self.upload_session_file.clone(),
+            download_dir: self.download_dir.clone(),
+            form: None,
+            req_type: self.req_type.clone(),
+            timeout: self.timeout,
+            bypass_cache: self.bypass_cache,
+            token_provider: self.token_provider.clone(),
+        })
+    }
 }
 
 impl AsRef<GraphUrl> for GraphRequestBuilder {
@@ -136,103 +287,182 @@ impl Default for GraphRequestBuilder {
 }
 
 pub struct GraphRequest {
-    token: String,
-    ident: Ident,
-    client: reqwest::Client,
-    redirect_client: reqwest::Client,
+    token_provider: RwLock<Arc<dyn TokenProvider>>,
+    transport: Arc<dyn HttpTransport>,
+    retry_policy: RwLock<RetryPolicy>,
+    rate_limiter: RwLock<Option<RateLimiter>>,
+    telemetry_sinks: RwLock<Vec<Arc<dyn TelemetrySink>>>,
+    compression: RwLock<bool>,
+    middleware: RwLock<Vec<Arc<dyn Middleware>>>,
+    defaults: RwLock<RequestDefaults>,
 }
 
 impl GraphRequest {
-    pub fn set_token(&mut self, token: &str) -> &mut Self {
-        self.token = token.to_string();
-        self
+    pub fn new(transport: Arc<dyn HttpTransport>) -> GraphRequest {
+        GraphRequest {
+            token_provider: RwLock::new(Arc::new(String::new())),
+            transport,
+            retry_policy: Default::default(),
+            rate_limiter: Default::default(),
+            telemetry_sinks: Default::default(),
+            compression: RwLock::new(true),
+            middleware: Default::default(),
+            defaults: Default::default(),
+        }
     }
 
-    pub fn ident(&self) -> Ident {
-        self.ident
+    /// The transport used to send requests.
+    pub fn transport(&self) -> Arc<dyn HttpTransport> {
+        Arc::clone(&self.transport)
     }
 
-    pub fn set_ident(&mut self, ident: Ident) -> &mut Self {
-        self.ident = ident;
-        self
+    /// The provider of the bearer token used for requests.
+    pub fn token_provider(&self) -> Arc<dyn TokenProvider> {
+        Arc::clone(&self.token_provider.read().unwrap())
+    }
+
+    pub fn set_token_provider(&self, token_provider: Arc<dyn TokenProvider>) {
+        *self.token_provider.write().unwrap() = token_provider;
+    }
+
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy.read().unwrap().clone()
+    }
+
+    pub fn set_retry_policy(&self, retry_policy: RetryPolicy) {
+        *self.retry_policy.write().unwrap() = retry_policy;
+    }
+
+    /// Set the rate limiter. Requests wait for the limiter after the
+    /// retry policy so each attempt counts towards the limits.
+    pub fn set_rate_limiter(&self, rate_limiter: RateLimiter) {
+        *self.rate_limiter.write().unwrap() = Some(rate_limiter);
+    }
+
+    /// Add a sink that receives an event for each request.
+    pub fn add_telemetry_sink(&self, sink: Arc<dyn TelemetrySink>) {
+        self.telemetry_sinks.write().unwrap().push(sink);
+    }
+
+    /// Ask for compressed responses and decompress them. On by default.
+    pub fn set_compression(&self, compression: bool) {
+        *self.compression.write().unwrap() = compression;
+    }
+
+    /// Add a middleware to the end of the middleware that has been added.
+    /// Added middleware runs before the retry policy and auth.
+    pub fn add_middleware(&self, middleware: Arc<dyn Middleware>) {
+        self.middleware.write().unwrap().push(middleware);
+    }
+
+    /// Set the headers, such as the User-Agent, and the timeout
+    /// that are used for every request.
+    pub fn set_request_defaults(&self, defaults: RequestDefaults) {
+        *self.defaults.write().unwrap() = defaults;
     }
 
-    pub(crate) fn token(&mut self) -> &String {
-        &self.token
+    /// The pipeline for Graph API requests: the default headers and timeout
+    /// if any are set, the client-request-id, telemetry if any sinks were
+    /// added, the added middleware, the retry policy, the rate limiter if one
+    /// is set, compression unless it was turned off, and then bearer auth.
+    pub fn pipeline(&self) -> Pipeline {
+        self.build_pipeline(Some(self.token_provider()))
     }
 
-    pub fn download(&mut self, request: GraphRequestBuilder) -> DownloadClient {
-        DownloadClient::new(self.token.as_str(), request)
+    /// The pipeline for urls that should not be sent the access token,
+    /// such as upload session and monitor urls. The client-request-id
+    /// headers are not sent to these urls either.
+    pub fn pipeline_without_auth(&self) -> Pipeline {
+        self.build_pipeline(None)
     }
 
-    pub fn upload_session(
-        &mut self,
-        request: GraphRequestBuilder,
-    ) -> GraphResult<UploadSessionClient> {
+    fn build_pipeline(&self, token_provider: Option<Arc<dyn TokenProvider>>) -> Pipeline {
+        let sinks = self.telemetry_sinks.read().unwrap().clone();
+        let defaults = self.defaults.read().unwrap().clone();
+        let mut middleware: Vec<Arc<dyn Middleware>> = Vec::new();
+        if !defaults.is_empty() {
+            middleware.push(Arc::new(defaults));
+        }
+        if token_provider.is_some() {
+            middleware.push(Arc::new(ClientRequestId));
+        }
+        let mut counter = None;
+        if !sinks.is_empty() {
+            let (telemetry, attempts) = Telemetry::new(sinks);
+            middleware.push(Arc::new(telemetry));
+            counter = Some(attempts);
+        }
+        middleware.extend(self.middleware.read().unwrap().iter().cloned());
+        middleware.push(Arc::new(self.retry_policy()));
+        if let Some(rate_limiter) = self.rate_limiter.read().unwrap().clone() {
+            middleware.push(Arc::new(rate_limiter));
+        }
+        if *self.compression.read().unwrap() {
+            middleware.push(Arc::new(Compression));
+        }
+        if let Some(counter) = counter {
+            middleware.push(Arc::new(counter));
+        }
+        let mut pipeline = Pipeline::new(middleware, self.transport());
+        if let Some(token_provider) = token_provider {
+            pipeline.set_token_provider(Arc::clone(&token_provider));
+            pipeline.push(Arc::new(BearerAuth::new(token_provider)));
+        }
+        pipeline
+    }
+
+    /// Build the request as it would be sent by running it through the
+    /// middleware that adds headers: the default headers, the
+    /// client-request-id and Accept-Encoding. The access token is not
+    /// added and the request is not sent.
+    pub fn prepare(&self, request: &GraphRequestBuilder) -> GraphResult<PreparedRequest> {
+        // Only multipart requests cannot be copied and those
+        // cannot be prepared.
+        let request = match request.try_clone() {
+            Some(request) => request,
+            None => return PreparedRequest::try_from(request),
+        };
+
+        let defaults = self.defaults.read().unwrap().clone();
+        let mut middleware: Vec<Arc<dyn Middleware>> = Vec::new();
+        if !defaults.is_empty() {
+            middleware.push(Arc::new(defaults));
+        }
+        middleware.push(Arc::new(ClientRequestId));
+        if *self.compression.read().unwrap() {
+            middleware.push(Arc::new(Compression));
+        }
+
+        let capture = Arc::new(CaptureTransport::default());
+        Pipeline::new(middleware, Arc::clone(&capture) as Arc<dyn HttpTransport>).send(request)?;
+        let request = capture.0.lock().unwrap().take();
+        match request {
+            Some(request) => PreparedRequest::try_from(&request),
+            None => Err(GraphFailure::invalid("request that can be prepared")),
+        }
+    }
+
+    pub fn upload_session(&self, request: GraphRequestBuilder) -> GraphResult<UploadSessionClient> {
         let file = request
             .upload_session_file
             .clone()
             .ok_or_else(|| GraphFailure::invalid("file for upload session"))?;
         let mut response = self.response(request)?;
-        if let Some(err) = GraphFailure::from_response(&mut response) {
-            return Err(err);
-        }
-
         let upload_session: serde_json::Value = response.json()?;
-        let mut session = UploadSessionClient::new(upload_session)?;
+        let mut session = UploadSessionClient::new(upload_session, self.pipeline_without_auth())?;
         session.set_file(file)?;
         Ok(session)
     }
 
-    pub fn build(&mut self, request: GraphRequestBuilder) -> RequestBuilder {
-        match request.req_type {
-            GraphRequestType::Basic => {
-                if let Some(body) = request.body {
-                    self.client
-                        .request(request.method, request.url.as_str())
-                        .headers(request.headers)
-                        .bearer_auth(self.token.as_str())
-                        .body(body)
-                } else {
-                    self.client
-                        .request(request.method, request.url.as_str())
-                        .headers(request.headers)
-                        .bearer_auth(self.token.as_str())
-                }
-            },
-            GraphRequestType::Redirect => {
-                if let Some(body) = request.body {
-                    self.redirect_client
-                        .request(request.method, request.url.as_str())
-                        .headers(request.headers)
-                        .bearer_auth(self.token.as_str())
-                        .body(body)
-                } else {
-                    self.redirect_client
-                        .request(request.method, request.url.as_str())
-                        .headers(request.headers)
-                        .bearer_auth(self.token.as_str())
-                }
-            },
-            GraphRequestType::Multipart => self
-                .client
-                .request(request.method, request.url.as_str())
-                .headers(request.headers)
-                .multipart(request.form.unwrap())
-                .bearer_auth(self.token.as_str()),
-        }
-    }
-
-    pub fn response(&mut self, request: GraphRequestBuilder) -> GraphResult<reqwest::Response> {
-        let builder = self.build(request);
-        let mut response = builder.send()?;
-        if let Some(err) = GraphFailure::from_response(&mut response) {
+    pub fn response(&self, request: GraphRequestBuilder) -> GraphResult<HttpResponse> {
+        let mut response = self.pipeline().send(request)?;
+        if let Some(err) = response.error() {
             return Err(err);
         }
         Ok(response)
     }
 
-    pub fn execute<T>(&mut self, request: GraphRequestBuilder) -> GraphResult<GraphResponse<T>>
+    pub fn execute<T>(&self, request: GraphRequestBuilder) -> GraphResult<GraphResponse<T>>
     where
         for<'de> T: serde::Deserialize<'de>,
     {
@@ -240,27 +470,113 @@ impl GraphRequest {
         let value: T = response.json()?;
         Ok(GraphResponse::new(response, value))
     }
+
+    /// Sends the request and follows any next links that are returned.
+    /// Each page is sent to the receiver, with the next links being
+    /// requested on a separate thread.
+    pub fn delta<T>(&self, request: GraphRequestBuilder) -> Receiver<Delta<T>>
+    where
+        for<'de> T: serde::Deserialize<'de>,
+        T: 'static + Send + NextLink,
+    {
+        let (sender, receiver) = channel();
+        let headers = request.link_headers();
+        let response: GraphResult<GraphResponse<T>> = self.execute(request);
+        let mut next_link = match response {
+            Ok(response) => {
+                let next_link = response.value().next_link();
+                sender.send(Delta::Next(response)).unwrap();
+                next_link
+            },
+            Err(err) => {
+                sender.send(Delta::Done(Some(err))).unwrap();
+                return receiver;
+            },
+        };
+
+        let client = self.clone();
+        thread::spawn(move || {
+            while let Some(next) = next_link {
+                let response: GraphResult<GraphResponse<T>> =
+                    GraphRequestBuilder::from_link(next.as_str(), &headers)
+                        .and_then(|request| client.execute(request));
+
+                // Stop when the receiver has been dropped.
+                match response {
+                    Ok(response) => {
+                        next_link = response.value().next_link();
+                        if sender.send(Delta::Next(response)).is_err() {
+                            return;
+                        }
+                    },
+                    Err(err) => {
+                        let _ = sender.send(Delta::Done(Some(err)));
+                        return;
+                    },
+                }
+            }
+            let _ = sender.send(Delta::Done(None));
+        });
+
+        receiver
+    }
+}
+
+impl RequestClient for GraphRequest {
+    type Download = DownloadClient;
+
+    fn token(&self) -> String {
+        self.token_provider().bearer_token().unwrap_or_default()
+    }
+
+    fn set_token(&self, token: &str) {
+        self.set_token_provider(Arc::new(token.to_string()));
+    }
+
+    fn download(&self, request: GraphRequestBuilder) -> DownloadClient {
+        DownloadClient::new(self.clone(), request)
+    }
+}
+
+impl Clone for GraphRequest {
+    fn clone(&self) -> Self {
+        let request = GraphRequest::new(self.transport());
+        request.set_token_provider(self.token_provider());
+        request.set_retry_policy(self.retry_policy());
+        *request.rate_limiter.write().unwrap() = self.rate_limiter.read().unwrap().clone();
+        *request.telemetry_sinks.write().unwrap() = self.telemetry_sinks.read().unwrap().clone();
+        request.set_compression(*self.compression.read().unwrap());
+        *request.middleware.write().unwrap() = self.middleware.read().unwrap().clone();
+        request.set_request_defaults(self.defaults.read().unwrap().clone());
+        request
+    }
 }
 
 impl Default for GraphRequest {
     fn default() -> Self {
-        let redirect_client = reqwest::Client::builder()
-            .redirect(RedirectPolicy::custom(|attempt| {
-                // There should be only 1 redirect to download a drive item.
-                if attempt.previous().len() > 1 {
-                    return attempt.too_many_redirects();
-                }
-                attempt.stop()
-            }))
-            .build()
-            .map_err(GraphFailure::from)
-            .unwrap();
+        GraphRequest::new(Arc::new(ReqwestTransport::default()))
+    }
+}
 
-        GraphRequest {
-            token: Default::default(),
-            ident: Default::default(),
-            client: reqwest::Client::new(),
-            redirect_client,
-        }
+/// Keeps the request instead of sending it.
+#[derive(Default)]
+struct CaptureTransport(Mutex<Option<GraphRequestBuilder>>);
+
+impl HttpTransport for CaptureTransport {
+    fn send(&self, request: GraphRequestBuilder) -> GraphResult<HttpResponse> {
+        let response = HttpResponse::new(
+            StatusCode::OK,
+            request.to_url(),
+            HeaderMap::new(),
+            io::empty(),
+        );
+        *self.0.lock().unwrap() = Some(request);
+        Ok(response)
+    }
+}
+
+impl AsRef<GraphRequest> for GraphRequest {
+    fn as_ref(&self) -> &GraphRequest {
+        self
     }
 }
@@ -0,0 +1,220 @@
+use crate::http::{
+    sleep, AsyncNext, GraphFuture, GraphRequestBuilder, HttpResponse, Middleware, Next,
+};
+use futures::future::{self, Loop};
+use futures::Future;
+use graph_error::GraphResult;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Method, StatusCode};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Retries requests that are throttled or fail because the service is
+/// temporarily unavailable. Requests returning 429, 503 or 504 are retried
+/// until the maximum number of attempts is reached.
+///
+/// The delay before the next attempt uses the Retry-After header when the
+/// response has one. Otherwise the delay doubles after each attempt, starting
+/// at the base delay, with jitter added so that many clients do not retry at
+/// the same time, and is capped at the max delay. When the Retry-After delay
+/// is longer than the max delay the response is returned without a retry.
+///
+/// A 504 may be returned after the request was processed, so POST and PATCH
+/// requests are only retried on 504 when `retry_gateway_timeout` is set.
+///
+/// # Example
+/// ```
+/// use graph_rs::http::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let mut policy = RetryPolicy::default();
+/// policy.set_max_attempts(5);
+/// policy.set_base_delay(Duration::from_millis(500));
+/// ```
+#[derive(Debug, Clone, PartialEq, Getters, Setters)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    retry_gateway_timeout: bool,
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32, base_delay: Duration, max_delay: Duration) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts,
+            base_delay,
+            max_delay,
+            jitter: true,
+            retry_gateway_timeout: false,
+        }
+    }
+
+    /// A policy that sends each request once.
+    pub fn none() -> RetryPolicy {
+        RetryPolicy::new(1, Duration::from_secs(0), Duration::from_secs(0))
+    }
+
+    pub fn is_retryable(status: StatusCode) -> bool {
+        match status.as_u16() {
+            429 | 503 | 504 => true,
+            _ => false,
+        }
+    }
+
+    /// The delay before the given attempt is retried. The Retry-After
+    /// delay is returned as given, while the backoff is capped at the
+    /// max delay.
+    pub fn delay(&self, attempt: u32, headers: &HeaderMap) -> Duration {
+        if let Some(retry_after) = RetryPolicy::retry_after(headers) {
+            return retry_after;
+        }
+
+        let exp = 2u32.saturating_pow(attempt.saturating_sub(1));
+        let backoff = self
+            .base_delay
+            .checked_mul(exp)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+
+        if self.jitter {
+            let half = backoff / 2;
+            let nanos = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.subsec_nanos())
+                .unwrap_or_default();
+            half + half.mul_f64(f64::from(nanos) / 1_000_000_000.0)
+        } else {
+            backoff
+        }
+    }
+
+    /// The delay given in the Retry-After header. Only the delay in
+    /// seconds form is used which is what the Graph API returns.
+    pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+        let seconds = headers
+            .get(RETRY_AFTER)?
+            .to_str()
+            .ok()?
+            .trim()
+            .parse()
+            .ok()?;
+        Some(Duration::from_secs(seconds))
+    }
+
+    /// The delay before the request is sent again, or None if the
+    /// response should be returned.
+    fn retry_delay(
+        &self,
+        attempt: u32,
+        method: &Method,
+        response: &HttpResponse,
+    ) -> Option<Duration> {
+        let status = response.status();
+        if !RetryPolicy::is_retryable(status) {
+            return None;
+        }
+        if status == StatusCode::GATEWAY_TIMEOUT &&
+            (*method == Method::POST || *method == Method::PATCH) &&
+            !self.retry_gateway_timeout
+        {
+            return None;
+        }
+
+        let delay = self.delay(attempt, response.headers());
+        if delay > self.max_delay {
+            warn!(
+                "{} {} returned {} with a Retry-After of {:?} which is longer than the max delay",
+                method,
+                response.url(),
+                status,
+                delay
+            );
+            return None;
+        }
+        warn!(
+            "{} {} returned {}. Retrying in {:?} (attempt {} of {})",
+            method,
+            response.url(),
+            response.status(),
+            delay,
+            attempt + 1,
+            self.max_attempts
+        );
+        Some(delay)
+    }
+}
+
+impl Middleware for RetryPolicy {
+    /// Send the request, retrying when needed. Multipart requests
+    /// cannot be sent more than once and are not retried.
+    fn handle(&self, mut request: GraphRequestBuilder, next: Next) -> GraphResult<HttpResponse> {
+        let mut attempt = 1;
+        loop {
+            let retry = if attempt < self.max_attempts {
+                request.try_clone()
+            } else {
+                None
+            };
+
+            let method = request.method.clone();
+            let response = next.run(request)?;
+            let delay = retry
+                .as_ref()
+                .and_then(|_| self.retry_delay(attempt, &method, &response));
+            match (retry, delay) {
+                (Some(retry), Some(delay)) => {
+                    // The response is dropped before waiting so it does not
+                    // hold a slot of a rate limiter.
+                    drop(response);
+                    thread::sleep(delay);
+                    request = retry;
+                    attempt += 1;
+                },
+                _ => return Ok(response),
+            }
+        }
+    }
+
+    /// Waits for the delay between attempts using a tokio timer, so the
+    /// future has to be run on a tokio runtime when a request is retried.
+    fn handle_async(
+        self: Arc<Self>,
+        request: GraphRequestBuilder,
+        next: AsyncNext,
+    ) -> GraphFuture<HttpResponse> {
+        Box::new(future::loop_fn((request, 1), move |(request, attempt)| {
+            let policy = Arc::clone(&self);
+            let retry = if attempt < policy.max_attempts {
+                request.try_clone()
+            } else {
+                None
+            };
+
+            let method = request.method.clone();
+            next.clone().run(request).and_then(
+                move |response| -> GraphFuture<Loop<HttpResponse, (GraphRequestBuilder, u32)>> {
+                    let delay = retry
+                        .as_ref()
+                        .and_then(|_| policy.retry_delay(attempt, &method, &response));
+                    match (retry, delay) {
+                        (Some(retry), Some(delay)) => Box::new(
+                            sleep(delay).map(move |_| Loop::Continue((retry, attempt + 1))),
+                        ),
+                        _ => Box::new(future::ok(Loop::Break(response))),
+                    }
+                },
+            )
+        }))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::new(4, Duration::from_secs(1), Duration::from_secs(32))
+    }
+}
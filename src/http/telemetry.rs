@@ -0,0 +1,277 @@
+use crate::http::{
+    AsyncNext, GraphFuture, GraphRequestBuilder, HttpResponse, Middleware, Next, RequestBody,
+};
+use futures::Future;
+use graph_error::GraphResult;
+use reqwest::header::{HeaderMap, CONTENT_LENGTH};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// The id the API gives each request.
+pub static REQUEST_ID: &str = "request-id";
+/// The id sent by the client for a request, if any, and returned by the API.
+pub static CLIENT_REQUEST_ID: &str = "client-request-id";
+/// Diagnostic information about the data center that handled a request.
+pub static DIAGNOSTIC: &str = "x-ms-ags-diagnostic";
+
+/// A summary of a request that was sent, including all of its retries.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Getters)]
+#[get = "pub"]
+pub struct RequestEvent {
+    method: String,
+    url: String,
+    /// The path of the url relative to the version of the API with
+    /// ids replaced by `{id}`, such as `/me/drive/items/{id}`.
+    path: String,
+    /// The status of the last response. None if no response was returned.
+    status: Option<u16>,
+    duration: Duration,
+    retries: u32,
+    request_bytes: Option<u64>,
+    /// The Content-Length of the response as it was sent, before
+    /// it is decompressed, if given.
+    response_bytes: Option<u64>,
+    request_id: Option<String>,
+    client_request_id: Option<String>,
+    diagnostic: Option<String>,
+    /// The error if the request could not be sent.
+    error: Option<String>,
+}
+
+impl RequestEvent {
+    /// The path of a url relative to the version of the API
+    /// with ids replaced by `{id}`.
+    pub fn path_template(url: &Url) -> String {
+        let segments: Vec<&str> = url
+            .path_segments()
+            .map(|segments| segments.skip(1).collect())
+            .unwrap_or_default();
+        let path: Vec<&str> = segments
+            .iter()
+            .map(|segment| {
+                if RequestEvent::is_id(segment) {
+                    "{id}"
+                } else {
+                    segment
+                }
+            })
+            .collect();
+        format!("/{}", path.join("/"))
+    }
+
+    fn is_id(segment: &str) -> bool {
+        segment.is_empty() ||
+            segment.len() > 20 ||
+            segment.chars().any(|c| {
+                c.is_ascii_digit() || c == '!' || c == '@' || c == '.' || c == '%' || c == '\''
+            })
+    }
+
+    fn header(headers: &HeaderMap, name: &str) -> Option<String> {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.to_string())
+    }
+
+    fn request_bytes(request: &GraphRequestBuilder) -> Option<u64> {
+        match request.body()? {
+            RequestBody::Text(text) => Some(text.len() as u64),
+            RequestBody::Bytes(bytes) => Some(bytes.len() as u64),
+            RequestBody::File(path) => std::fs::metadata(path).ok().map(|m| m.len()),
+        }
+    }
+}
+
+/// Receives an event for each request that is sent.
+///
+/// # Example
+/// ```rust,ignore
+/// struct Metrics;
+///
+/// impl TelemetrySink for Metrics {
+///     fn on_request(&self, event: &RequestEvent) {
+///         histogram!("graph.latency", event.duration(), "path" => event.path());
+///     }
+/// }
+///
+/// client.add_telemetry_sink(Metrics);
+/// ```
+pub trait TelemetrySink: Send + Sync {
+    fn on_request(&self, event: &RequestEvent);
+}
+
+/// Logs each request event using the log crate. Requests that return an
+/// error status or could not be sent are logged as warnings.
+#[derive(Debug, Default, Clone)]
+pub struct LogSink;
+
+impl TelemetrySink for LogSink {
+    fn on_request(&self, event: &RequestEvent) {
+        let status = event
+            .status
+            .map(|status| status.to_string())
+            .unwrap_or_else(|| "no response".into());
+        let message = format!(
+            "{} {} {} in {:?} (retries: {}, request-id: {}, client-request-id: {}, diagnostic: {})",
+            event.method,
+            event.path,
+            status,
+            event.duration,
+            event.retries,
+            event.request_id.as_ref().map_or("none", |id| id.as_str()),
+            event
+                .client_request_id
+                .as_ref()
+                .map_or("none", |id| id.as_str()),
+            event.diagnostic.as_ref().map_or("none", |d| d.as_str()),
+        );
+        if event.error.is_some() || event.status.map_or(false, |status| status >= 400) {
+            warn!("{}", message);
+        } else {
+            info!("{}", message);
+        }
+    }
+}
+
+/// Counts the attempts of a request and records the Content-Length of
+/// the last response. Runs after the retry policy and compression so the
+/// length is the size of the response before it is decompressed.
+pub(crate) struct AttemptCounter {
+    attempts: Arc<AtomicU32>,
+    response_bytes: Arc<Mutex<Option<u64>>>,
+}
+
+impl AttemptCounter {
+    fn start(&self) {
+        self.attempts.fetch_add(1, Ordering::SeqCst);
+        *self.response_bytes.lock().unwrap() = None;
+    }
+
+    fn record(&self, response: &HttpResponse) {
+        *self.response_bytes.lock().unwrap() =
+            RequestEvent::header(response.headers(), CONTENT_LENGTH.as_str())
+                .and_then(|len| len.parse().ok());
+    }
+}
+
+impl Middleware for AttemptCounter {
+    fn handle(&self, request: GraphRequestBuilder, next: Next) -> GraphResult<HttpResponse> {
+        self.start();
+        let response = next.run(request)?;
+        self.record(&response);
+        Ok(response)
+    }
+
+    fn handle_async(
+        self: Arc<Self>,
+        request: GraphRequestBuilder,
+        next: AsyncNext,
+    ) -> GraphFuture<HttpResponse> {
+        self.start();
+        Box::new(next.run(request).map(move |response| {
+            self.record(&response);
+            response
+        }))
+    }
+}
+
+/// Sends a `RequestEvent` to each sink after a request and its
+/// retries are done. Runs before any other middleware.
+pub(crate) struct Telemetry {
+    sinks: Vec<Arc<dyn TelemetrySink>>,
+    attempts: Arc<AtomicU32>,
+    response_bytes: Arc<Mutex<Option<u64>>>,
+}
+
+impl Telemetry {
+    /// The telemetry middleware and the attempt counter that goes
+    /// after the retry policy and compression.
+    pub(crate) fn new(sinks: Vec<Arc<dyn TelemetrySink>>) -> (Telemetry, AttemptCounter) {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let response_bytes = Arc::new(Mutex::new(None));
+        let counter = AttemptCounter {
+            attempts: Arc::clone(&attempts),
+            response_bytes: Arc::clone(&response_bytes),
+        };
+        let telemetry = Telemetry {
+            sinks,
+            attempts,
+            response_bytes,
+        };
+        (telemetry, counter)
+    }
+
+    /// The event for a request that is about to be sent.
+    fn start(&self, request: &GraphRequestBuilder) -> RequestEvent {
+        let url = request.to_url();
+        self.attempts.store(0, Ordering::SeqCst);
+        *self.response_bytes.lock().unwrap() = None;
+        RequestEvent {
+            method: request.method.to_string(),
+            path: RequestEvent::path_template(&url),
+            url: url.to_string(),
+            status: None,
+            duration: Duration::from_secs(0),
+            retries: 0,
+            request_bytes: RequestEvent::request_bytes(request),
+            response_bytes: None,
+            request_id: None,
+            client_request_id: RequestEvent::header(&request.headers, CLIENT_REQUEST_ID),
+            diagnostic: None,
+            error: None,
+        }
+    }
+
+    /// Add the result of the request to the event and send it to each sink.
+    fn finish(
+        &self,
+        mut event: RequestEvent,
+        start: Instant,
+        response: &GraphResult<HttpResponse>,
+    ) {
+        event.duration = start.elapsed();
+        event.retries = self.attempts.load(Ordering::SeqCst).saturating_sub(1);
+        match response.as_ref() {
+            Ok(response) => {
+                let headers = response.headers();
+                event.status = Some(response.status().as_u16());
+                event.response_bytes = *self.response_bytes.lock().unwrap();
+                event.request_id = RequestEvent::header(headers, REQUEST_ID);
+                if let Some(id) = RequestEvent::header(headers, CLIENT_REQUEST_ID) {
+                    event.client_request_id = Some(id);
+                }
+                event.diagnostic = RequestEvent::header(headers, DIAGNOSTIC);
+            },
+            Err(err) => event.error = Some(err.to_string()),
+        }
+        for sink in self.sinks.iter() {
+            sink.on_request(&event);
+        }
+    }
+}
+
+impl Middleware for Telemetry {
+    fn handle(&self, request: GraphRequestBuilder, next: Next) -> GraphResult<HttpResponse> {
+        let event = self.start(&request);
+        let start = Instant::now();
+        let response = next.run(request);
+        self.finish(event, start, &response);
+        response
+    }
+
+    fn handle_async(
+        self: Arc<Self>,
+        request: GraphRequestBuilder,
+        next: AsyncNext,
+    ) -> GraphFuture<HttpResponse> {
+        let event = self.start(&request);
+        let start = Instant::now();
+        Box::new(next.run(request).then(move |response| {
+            self.finish(event, start, &response);
+            response
+        }))
+    }
+}
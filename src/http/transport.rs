@@ -0,0 +1,547 @@
+use crate::http::{
+    GraphFuture, GraphRequestBuilder, GraphRequestType, GraphStream, CLIENT_REQUEST_ID,
+};
+use futures::sync::{mpsc, oneshot};
+use futures::{future, stream, Future, Sink, Stream};
+use graph_error::{GraphError, GraphFailure, GraphHeaders, GraphResult};
+use reqwest::header::HeaderMap;
+use reqwest::{RedirectPolicy, StatusCode};
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{BufReader, Cursor, Read, Write};
+use std::sync::mpsc::RecvError;
+use std::sync::Arc;
+use std::thread;
+use url::Url;
+
+/// The size of the chunks that a body is read in when it is
+/// turned into a stream.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Sends the requests built by the blocking and async clients.
+///
+/// Every request goes through a transport, including downloads, upload
+/// session byte ranges, delta and next link requests, and async job status
+/// checks. Blocking requests use `send` and async requests use `send_async`.
+/// A transport that only implements `send` still works with the async
+/// client: each async request is sent with `send` on a separate thread and
+/// the body is passed back as a stream. The default transport uses reqwest
+/// and sends async requests with the reqwest async client. A different
+/// transport can be used for mocking requests in tests or for using another
+/// HTTP stack.
+///
+/// # Example
+/// ```rust,ignore
+/// struct MyTransport;
+///
+/// impl HttpTransport for MyTransport {
+///     fn send(&self, request: GraphRequestBuilder) -> GraphResult<HttpResponse> {
+///         // Send the request using any HTTP client.
+///     }
+/// }
+///
+/// let client = Graph::with_transport("ACCESS_TOKEN", MyTransport);
+/// let async_client = Graph::new_async_with_transport("ACCESS_TOKEN", MyTransport);
+/// ```
+pub trait HttpTransport: Send + Sync + 'static {
+    /// Send the request and return the response. Requests with a request type
+    /// of `GraphRequestType::Redirect` should not follow redirects.
+    fn send(&self, request: GraphRequestBuilder) -> GraphResult<HttpResponse>;
+
+    /// Send the request without blocking. The default sends the request
+    /// with `send` on a separate thread, which also reads the body.
+    /// Transports built on an async HTTP client should return a response
+    /// with a stream body using `HttpResponse::from_stream`.
+    fn send_async(self: Arc<Self>, request: GraphRequestBuilder) -> GraphFuture<HttpResponse> {
+        send_on_thread(move || self.send(request))
+    }
+}
+
+/// Run a blocking send on a separate thread. The thread reads the body
+/// of the response and passes it back in chunks, so reading the body
+/// does not block the executor.
+pub(crate) fn send_on_thread<F>(send: F) -> GraphFuture<HttpResponse>
+where
+    F: FnOnce() -> GraphResult<HttpResponse> + Send + 'static,
+{
+    let (sender, receiver) = oneshot::channel();
+    thread::spawn(move || {
+        let mut response = match send() {
+            Ok(response) => response,
+            Err(err) => {
+                let _ = sender.send(Err(err));
+                return;
+            },
+        };
+
+        let body = response.take_body();
+        let (mut chunks, body_stream) = mpsc::channel(1);
+        response.set_stream(
+            body_stream
+                .map_err(|_| GraphFailure::from(RecvError))
+                .and_then(|chunk| chunk),
+        );
+        // The future may have been dropped before the response was sent.
+        if sender.send(Ok(response)).is_err() {
+            return;
+        }
+        for chunk in ReadChunks::new(body) {
+            chunks = match chunks.send(chunk).wait() {
+                Ok(chunks) => chunks,
+                // The body was dropped before it was read.
+                Err(_) => return,
+            };
+        }
+    });
+    Box::new(
+        receiver
+            .map_err(|_| GraphFailure::from(RecvError))
+            .and_then(|result| result),
+    )
+}
+
+/// Reads a body in chunks until the end of the body or an error.
+struct ReadChunks {
+    body: Box<dyn Read + Send>,
+    done: bool,
+}
+
+impl ReadChunks {
+    fn new(body: Box<dyn Read + Send>) -> ReadChunks {
+        ReadChunks { body, done: false }
+    }
+}
+
+impl Iterator for ReadChunks {
+    type Item = GraphResult<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let mut chunk = vec![0; CHUNK_SIZE];
+        match self.body.read(&mut chunk) {
+            Ok(0) => {
+                self.done = true;
+                None
+            },
+            Ok(len) => {
+                chunk.truncate(len);
+                Some(Ok(chunk))
+            },
+            Err(err) => {
+                self.done = true;
+                Some(Err(GraphFailure::from(err)))
+            },
+        }
+    }
+}
+
+/// Reads a stream body by blocking the current thread until
+/// each chunk arrives.
+struct StreamReader {
+    chunks: stream::Wait<GraphStream<Vec<u8>>>,
+    chunk: Cursor<Vec<u8>>,
+}
+
+impl Read for StreamReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            let len = self.chunk.read(buf)?;
+            if len > 0 || buf.is_empty() {
+                return Ok(len);
+            }
+            match self.chunks.next() {
+                Some(Ok(chunk)) => self.chunk = Cursor::new(chunk),
+                Some(Err(err)) => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::Other,
+                        err.to_string(),
+                    ))
+                },
+                None => return Ok(0),
+            }
+        }
+    }
+}
+
+enum Body {
+    Reader(Box<dyn Read + Send>),
+    Stream(GraphStream<Vec<u8>>),
+}
+
+/// The response returned by an `HttpTransport`.
+///
+/// The body is either a reader, which blocking transports return, or a
+/// stream of chunks, which async transports return. Either kind of body
+/// can be read as the other: a stream is read as a reader by blocking
+/// until each chunk arrives, and a reader is read as a stream by reading
+/// a chunk each time the stream is polled.
+pub struct HttpResponse {
+    status: StatusCode,
+    url: Url,
+    headers: HeaderMap,
+    body: Body,
+}
+
+impl HttpResponse {
+    pub fn new<R>(status: StatusCode, url: Url, headers: HeaderMap, body: R) -> HttpResponse
+    where
+        R: Read + Send + 'static,
+    {
+        HttpResponse {
+            status,
+            url,
+            headers,
+            body: Body::Reader(Box::new(body)),
+        }
+    }
+
+    /// A response with a body that is a stream of chunks.
+    pub fn from_stream<S>(status: StatusCode, url: Url, headers: HeaderMap, body: S) -> HttpResponse
+    where
+        S: Stream<Item = Vec<u8>, Error = GraphFailure> + Send + 'static,
+    {
+        HttpResponse {
+            status,
+            url,
+            headers,
+            body: Body::Stream(Box::new(body)),
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
+    }
+
+    /// Returns true if the body is a stream of chunks.
+    pub fn is_stream(&self) -> bool {
+        match self.body {
+            Body::Stream(_) => true,
+            Body::Reader(_) => false,
+        }
+    }
+
+    /// Replace the body of the response.
+    pub fn set_body<R>(&mut self, body: R)
+    where
+        R: Read + Send + 'static,
+    {
+        self.body = Body::Reader(Box::new(body));
+    }
+
+    /// Replace the body of the response with a stream of chunks.
+    pub fn set_stream<S>(&mut self, body: S)
+    where
+        S: Stream<Item = Vec<u8>, Error = GraphFailure> + Send + 'static,
+    {
+        self.body = Body::Stream(Box::new(body));
+    }
+
+    /// Take the body of the response, leaving an empty body. Reading a
+    /// stream body blocks the current thread until each chunk arrives.
+    pub fn take_body(&mut self) -> Box<dyn Read + Send> {
+        match std::mem::replace(&mut self.body, Body::Reader(Box::new(std::io::empty()))) {
+            Body::Reader(reader) => reader,
+            Body::Stream(stream) => Box::new(StreamReader {
+                chunks: stream.wait(),
+                chunk: Cursor::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Take the body of the response as a stream of chunks, leaving an
+    /// empty body. A reader body is read on the thread that polls the
+    /// stream.
+    pub fn take_stream(&mut self) -> GraphStream<Vec<u8>> {
+        match std::mem::replace(&mut self.body, Body::Reader(Box::new(std::io::empty()))) {
+            Body::Reader(reader) => Box::new(stream::iter_result(ReadChunks::new(reader))),
+            Body::Stream(stream) => stream,
+        }
+    }
+
+    /// Read a stream body into memory without blocking, so the body can
+    /// then be read using `json`, `text` or `copy_to`. A reader body is
+    /// left as it is.
+    pub fn into_buffered(mut self) -> GraphFuture<HttpResponse> {
+        if !self.is_stream() {
+            return Box::new(future::ok(self));
+        }
+        let id = self.client_request_id();
+        let body = self.take_stream();
+        Box::new(
+            body.concat2()
+                .map_err(move |err| HttpResponse::with_id(err, id))
+                .map(move |body| {
+                    self.set_body(Cursor::new(body));
+                    self
+                }),
+        )
+    }
+
+    fn reader(&mut self) -> &mut Box<dyn Read + Send> {
+        if self.is_stream() {
+            let reader = self.take_body();
+            self.body = Body::Reader(reader);
+        }
+        match self.body {
+            Body::Reader(ref mut reader) => reader,
+            Body::Stream(_) => unreachable!("the stream body was replaced by a reader"),
+        }
+    }
+
+    /// Deserialize the body as it is read instead of reading
+    /// the whole body into memory first.
+    pub fn json<T>(&mut self) -> GraphResult<T>
+    where
+        for<'de> T: serde::Deserialize<'de>,
+    {
+        serde_json::from_reader(BufReader::new(self.reader()))
+            .map_err(|err| self.failure(GraphFailure::from(err)))
+    }
+
+    pub fn text(&mut self) -> GraphResult<String> {
+        let mut text = String::new();
+        self.reader()
+            .read_to_string(&mut text)
+            .map_err(|err| self.failure(GraphFailure::from(err)))?;
+        Ok(text)
+    }
+
+    pub fn copy_to<W: Write + ?Sized>(&mut self, writer: &mut W) -> GraphResult<u64> {
+        std::io::copy(self.reader(), writer).map_err(|err| self.failure(GraphFailure::from(err)))
+    }
+
+    fn client_request_id(&self) -> Option<String> {
+        self.headers
+            .get(CLIENT_REQUEST_ID)
+            .and_then(|id| id.to_str().ok())
+            .map(|id| id.to_string())
+    }
+
+    fn with_id(err: GraphFailure, id: Option<String>) -> GraphFailure {
+        match id {
+            Some(id) => err.with_client_request_id(id.as_str()),
+            None => err,
+        }
+    }
+
+    /// The failure to read the body, with the client-request-id
+    /// of the response if it has one.
+    fn failure(&self, err: GraphFailure) -> GraphFailure {
+        HttpResponse::with_id(err, self.client_request_id())
+    }
+
+    /// Returns the error for the response, if any, including the
+    /// error message in the body.
+    pub fn error(&mut self) -> Option<GraphFailure> {
+        let status = self.status.as_u16();
+        let mut error = GraphError::try_from(status).ok()?;
+        error.set_headers(GraphHeaders::new(
+            self.url.as_str(),
+            status,
+            self.headers.clone(),
+        ));
+        let text = self.text().unwrap_or_default();
+        Some(GraphFailure::from_graph_error(error, text.as_str()))
+    }
+}
+
+impl Read for HttpResponse {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.reader().read(buf)
+    }
+}
+
+impl fmt::Debug for HttpResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpResponse")
+            .field("status", &self.status)
+            .field("url", &self.url)
+            .field("headers", &self.headers)
+            .finish()
+    }
+}
+
+impl From<reqwest::Response> for HttpResponse {
+    fn from(response: reqwest::Response) -> Self {
+        HttpResponse {
+            status: response.status(),
+            url: response.url().clone(),
+            headers: response.headers().to_owned(),
+            body: Body::Reader(Box::new(response)),
+        }
+    }
+}
+
+impl From<reqwest::r#async::Response> for HttpResponse {
+    fn from(response: reqwest::r#async::Response) -> Self {
+        let status = response.status();
+        let url = response.url().clone();
+        let headers = response.headers().to_owned();
+        let body = response
+            .into_body()
+            .map(|chunk| chunk.to_vec())
+            .map_err(GraphFailure::from);
+        HttpResponse::from_stream(status, url, headers, body)
+    }
+}
+
+/// The reqwest async clients used to send async requests.
+#[derive(Clone)]
+struct AsyncClients {
+    client: reqwest::r#async::Client,
+    redirect_client: reqwest::r#async::Client,
+}
+
+impl AsyncClients {
+    fn send(&self, request: GraphRequestBuilder) -> GraphFuture<HttpResponse> {
+        let client = match request.req_type {
+            GraphRequestType::Redirect => &self.redirect_client,
+            _ => &self.client,
+        };
+        let mut builder = client
+            .request(request.method, request.url.as_str())
+            .headers(request.headers);
+        if let Some(body) = request.body {
+            match body.into_async_body() {
+                Ok(body) => builder = builder.body(body),
+                Err(err) => return Box::new(future::err(err)),
+            }
+        }
+        Box::new(
+            builder
+                .send()
+                .map(HttpResponse::from)
+                .map_err(GraphFailure::from),
+        )
+    }
+}
+
+/// The default transport which sends requests using reqwest.
+///
+/// Blocking requests are sent with the blocking reqwest clients. Async
+/// requests are sent with the reqwest async clients when they are set
+/// using `with_async_clients`. Otherwise, and for multipart requests
+/// whose form can only be sent by the blocking client, async requests
+/// are sent with the blocking clients on a separate thread.
+pub struct ReqwestTransport {
+    client: reqwest::Client,
+    redirect_client: reqwest::Client,
+    async_clients: Option<AsyncClients>,
+}
+
+impl ReqwestTransport {
+    pub fn new(client: reqwest::Client, redirect_client: reqwest::Client) -> ReqwestTransport {
+        ReqwestTransport {
+            client,
+            redirect_client,
+            async_clients: None,
+        }
+    }
+
+    /// Send async requests with the reqwest async clients. The redirect
+    /// client is used for requests that should not follow redirects.
+    pub fn with_async_clients(
+        mut self,
+        client: reqwest::r#async::Client,
+        redirect_client: reqwest::r#async::Client,
+    ) -> ReqwestTransport {
+        self.async_clients = Some(AsyncClients {
+            client,
+            redirect_client,
+        });
+        self
+    }
+
+    /// The default transport with the default reqwest async clients.
+    pub fn default_async() -> ReqwestTransport {
+        let redirect_client = reqwest::r#async::Client::builder()
+            .redirect(download_redirect_policy())
+            .build()
+            .map_err(GraphFailure::from)
+            .unwrap();
+
+        ReqwestTransport::default()
+            .with_async_clients(reqwest::r#async::Client::new(), redirect_client)
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    fn send(&self, request: GraphRequestBuilder) -> GraphResult<HttpResponse> {
+        let builder = match request.req_type {
+            GraphRequestType::Basic => self
+                .client
+                .request(request.method, request.url.as_str())
+                .headers(request.headers),
+            GraphRequestType::Redirect => self
+                .redirect_client
+                .request(request.method, request.url.as_str())
+                .headers(request.headers),
+            GraphRequestType::Multipart => {
+                let form = request
+                    .form
+                    .ok_or_else(|| GraphFailure::invalid("multipart form"))?;
+                let response = self
+                    .client
+                    .request(request.method, request.url.as_str())
+                    .headers(request.headers)
+                    .multipart(form)
+                    .send()?;
+                return Ok(HttpResponse::from(response));
+            },
+        };
+
+        let builder = if let Some(body) = request.body {
+            builder.body(body.into_body()?)
+        } else {
+            builder
+        };
+        Ok(HttpResponse::from(builder.send()?))
+    }
+
+    fn send_async(self: Arc<Self>, request: GraphRequestBuilder) -> GraphFuture<HttpResponse> {
+        match self.async_clients.clone() {
+            Some(clients) if request.req_type != GraphRequestType::Multipart => {
+                clients.send(request)
+            },
+            _ => send_on_thread(move || self.send(request)),
+        }
+    }
+}
+
+impl Default for ReqwestTransport {
+    fn default() -> Self {
+        let redirect_client = reqwest::Client::builder()
+            .redirect(download_redirect_policy())
+            .build()
+            .map_err(GraphFailure::from)
+            .unwrap();
+
+        ReqwestTransport::new(reqwest::Client::new(), redirect_client)
+    }
+}
+
+/// The redirect policy for clients that request the download url
+/// of a drive item.
+pub(crate) fn download_redirect_policy() -> RedirectPolicy {
+    RedirectPolicy::custom(|attempt| {
+        // There should be only 1 redirect to download a drive item.
+        if attempt.previous().len() > 1 {
+            return attempt.too_many_redirects();
+        }
+        attempt.stop()
+    })
+}
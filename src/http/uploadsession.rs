@@ -1,10 +1,14 @@
-use crate::http::HttpByteRange;
+use crate::http::{
+    GraphFuture, GraphRequestBuilder, GraphResponse, HttpByteRange, HttpResponse, Pipeline,
+};
 use from_as::*;
+use futures::{future, Async, Future, Poll, Stream};
 use graph_error::{GraphFailure, GraphResult};
-use reqwest::header::{CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE};
-use reqwest::{RequestBuilder, Response};
+use reqwest::header::{HeaderValue, CONTENT_LENGTH, CONTENT_RANGE};
+use reqwest::Method;
 use std::convert::TryFrom;
 use std::path::{Path, PathBuf};
+use url::Url;
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, AsFile, FromFile)]
 pub struct Session {
@@ -25,23 +29,45 @@ pub trait StartUploadSession {
 }
 
 pub enum NextSession {
-    Next((serde_json::Value, Response)),
-    Done((serde_json::Value, Response)),
+    Next((serde_json::Value, HttpResponse)),
+    Done((serde_json::Value, HttpResponse)),
+}
+
+/// A request to cancel an upload session that can be sent at any time,
+/// even after the upload session client has been consumed.
+pub struct CancelUploadSession {
+    upload_session_url: String,
+    pipeline: Pipeline,
+}
+
+impl CancelUploadSession {
+    pub fn send(&self) -> GraphResult<HttpResponse> {
+        self.pipeline.send(self.request()?)
+    }
+
+    fn request(&self) -> GraphResult<GraphRequestBuilder> {
+        let mut request = GraphRequestBuilder::from(Url::parse(&self.upload_session_url)?);
+        request.set_method(Method::DELETE);
+        Ok(request)
+    }
 }
 
 pub struct UploadSessionClient {
     upload_session_url: String,
     byte_ranges: HttpByteRange,
-    client: reqwest::Client,
+    pipeline: Pipeline,
 }
 
 impl UploadSessionClient {
-    pub fn new(upload_session: serde_json::Value) -> GraphResult<UploadSessionClient> {
+    pub fn new(
+        upload_session: serde_json::Value,
+        pipeline: Pipeline,
+    ) -> GraphResult<UploadSessionClient> {
         let url = upload_session["uploadUrl"].as_str()?;
         Ok(UploadSessionClient {
             upload_session_url: url.to_string(),
             byte_ranges: Default::default(),
-            client: reqwest::Client::new(),
+            pipeline,
         })
     }
 
@@ -59,18 +85,66 @@ impl UploadSessionClient {
         Ok(())
     }
 
-    pub fn cancel(&mut self) -> RequestBuilder {
-        self.client
-            .delete(self.upload_session_url.as_str())
-            .header(CONTENT_TYPE, "application/json")
+    pub fn cancel(&mut self) -> CancelUploadSession {
+        CancelUploadSession {
+            upload_session_url: self.upload_session_url.clone(),
+            pipeline: self.pipeline.clone(),
+        }
+    }
+
+    pub fn status(&mut self) -> GraphResult<HttpResponse> {
+        let request = GraphRequestBuilder::from(Url::parse(&self.upload_session_url)?);
+        self.pipeline.send(request)
+    }
+
+    fn upload_range(
+        &self,
+        body: Vec<u8>,
+        content_length: u64,
+        content_range: String,
+    ) -> GraphResult<NextSession> {
+        let request = range_request(
+            self.upload_session_url.as_str(),
+            body,
+            content_length,
+            content_range,
+        )?;
+        next_session(self.pipeline.send(request)?)
+    }
+}
+
+fn range_request(
+    upload_session_url: &str,
+    body: Vec<u8>,
+    content_length: u64,
+    content_range: String,
+) -> GraphResult<GraphRequestBuilder> {
+    // The Authorization header and bearer token should only be sent
+    // when issuing the POST during the first step.
+    let mut request = GraphRequestBuilder::from(Url::parse(upload_session_url)?);
+    request
+        .set_method(Method::PUT)
+        .header(CONTENT_LENGTH, HeaderValue::from(content_length))
+        .header(
+            CONTENT_RANGE,
+            HeaderValue::from_str(content_range.as_str())
+                .map_err(|_| GraphFailure::invalid("content range"))?,
+        )
+        .set_body(body);
+    Ok(request)
+}
+
+fn next_session(mut response: HttpResponse) -> GraphResult<NextSession> {
+    if let Some(e) = response.error() {
+        return Err(e);
     }
 
-    pub fn status(&mut self) -> GraphResult<Response> {
-        self.client
-            .get(self.upload_session_url.as_str())
-            .header(CONTENT_TYPE, "application/json")
-            .send()
-            .map_err(GraphFailure::from)
+    let status = response.status().as_u16();
+    let value: serde_json::Value = response.json()?;
+    if status.eq(&200) || status.eq(&201) {
+        Ok(NextSession::Done((value, response)))
+    } else {
+        Ok(NextSession::Next((value, response)))
     }
 }
 
@@ -79,43 +153,136 @@ impl Iterator for UploadSessionClient {
 
     fn next(&mut self) -> Option<Self::Item> {
         let (body, content_length, content_range) = self.byte_ranges.pop_front()?;
+        Some(self.upload_range(body, content_length, content_range))
+    }
+}
+
+/// The next item returned by an `AsyncUploadSessionClient`.
+pub enum AsyncNextSession {
+    Next(GraphResponse<serde_json::Value>),
+    Done(GraphResponse<serde_json::Value>),
+}
 
-        // The Authorization header and bearer token should only be sent
-        // when issuing the POST during the first step.
-        let response = self
-            .client
-            .put(self.upload_session_url.as_str())
-            .header(CONTENT_TYPE, "application/json")
-            .header(CONTENT_LENGTH, content_length)
-            .header(CONTENT_RANGE, content_range)
-            .body(body)
-            .send()
+/// The async counterpart to `UploadSessionClient`. Each byte range
+/// is uploaded when the stream is polled for the next item.
+pub struct AsyncUploadSessionClient {
+    upload_session_url: String,
+    byte_ranges: HttpByteRange,
+    pipeline: Pipeline,
+    pending: Option<GraphFuture<AsyncNextSession>>,
+}
+
+impl AsyncUploadSessionClient {
+    pub fn new(
+        upload_session: serde_json::Value,
+        pipeline: Pipeline,
+    ) -> GraphResult<AsyncUploadSessionClient> {
+        let url = upload_session["uploadUrl"].as_str()?;
+        Ok(AsyncUploadSessionClient {
+            upload_session_url: url.to_string(),
+            byte_ranges: Default::default(),
+            pipeline,
+            pending: None,
+        })
+    }
+
+    pub fn from_range<P: AsRef<Path>>(&mut self, start: u64, end: u64, file: P) -> GraphResult<()> {
+        self.byte_ranges = HttpByteRange::from_range(start, end, file)?;
+        Ok(())
+    }
+
+    pub fn has_next(&self) -> bool {
+        !self.byte_ranges.is_empty()
+    }
+
+    pub fn set_file(&mut self, file: PathBuf) -> GraphResult<()> {
+        self.byte_ranges = HttpByteRange::try_from(file)?;
+        Ok(())
+    }
+
+    pub fn cancel(&self) -> GraphFuture<HttpResponse> {
+        let cancel = CancelUploadSession {
+            upload_session_url: self.upload_session_url.clone(),
+            pipeline: self.pipeline.clone(),
+        };
+        self.send(cancel.request())
+    }
+
+    pub fn status(&self) -> GraphFuture<HttpResponse> {
+        let request = Url::parse(&self.upload_session_url)
+            .map(GraphRequestBuilder::from)
             .map_err(GraphFailure::from);
+        self.send(request)
+    }
 
-        if let Ok(mut response) = response {
-            if let Some(e) = GraphFailure::from_response(&mut response) {
-                return Some(Err(e));
-            }
+    /// Send the request and read the response body into memory.
+    fn send(&self, request: GraphResult<GraphRequestBuilder>) -> GraphFuture<HttpResponse> {
+        match request {
+            Ok(request) => Box::new(
+                self.pipeline
+                    .send_async(request)
+                    .and_then(HttpResponse::into_buffered),
+            ),
+            Err(err) => Box::new(future::err(err)),
+        }
+    }
 
-            let status = response.status().as_u16();
-            if status.eq(&200) || status.eq(&201) {
-                let result: GraphResult<serde_json::Value> =
-                    response.json().map_err(GraphFailure::from);
-                match result {
-                    Ok(value) => return Some(Ok(NextSession::Done((value, response)))),
-                    Err(e) => return Some(Err(e)),
-                }
-            } else {
-                let result: GraphResult<serde_json::Value> =
-                    response.json().map_err(GraphFailure::from);
-                match result {
-                    Ok(next) => return Some(Ok(NextSession::Next((next, response)))),
-                    Err(e) => return Some(Err(e)),
-                }
+    fn upload_range(
+        &self,
+        body: Vec<u8>,
+        content_length: u64,
+        content_range: String,
+    ) -> GraphFuture<AsyncNextSession> {
+        let request = range_request(
+            self.upload_session_url.as_str(),
+            body,
+            content_length,
+            content_range,
+        );
+        Box::new(
+            self.send(request)
+                .and_then(next_session)
+                .map(|next| match next {
+                    NextSession::Next((value, response)) => {
+                        AsyncNextSession::Next(GraphResponse::new(response, value))
+                    },
+                    NextSession::Done((value, response)) => {
+                        AsyncNextSession::Done(GraphResponse::new(response, value))
+                    },
+                }),
+        )
+    }
+}
+
+impl Stream for AsyncUploadSessionClient {
+    type Item = AsyncNextSession;
+    type Error = GraphFailure;
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        if self.pending.is_none() {
+            match self.byte_ranges.pop_front() {
+                Some((body, content_length, content_range)) => {
+                    self.pending = Some(self.upload_range(body, content_length, content_range));
+                },
+                None => return Ok(Async::Ready(None)),
             }
-        } else if let Err(e) = response {
-            return Some(Err(e));
         }
-        None
+
+        let poll = match self.pending.as_mut() {
+            Some(pending) => pending.poll(),
+            None => return Ok(Async::Ready(None)),
+        };
+
+        match poll {
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Ok(Async::Ready(next)) => {
+                self.pending = None;
+                Ok(Async::Ready(Some(next)))
+            },
+            Err(err) => {
+                self.pending = None;
+                Err(err)
+            },
+        }
     }
 }
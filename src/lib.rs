@@ -52,10 +52,31 @@
 //! println!("{:#?}", response.value());
 //!
 //! ```
+//!
+//! Use the async client to get futures instead of blocking responses.
+//! # Example
+//! ```rust,ignore
+//! use graph_rs::prelude::*;
+//! use futures::Future;
+//!
+//! let client = Graph::new_async("TOKEN");
+//!
+//! let future = client.v1()
+//!     .me()
+//!     .drive()
+//!     .root_children()
+//!     .send()
+//!     .map(|response| println!("{:#?}", response.value()))
+//!     .map_err(|err| println!("{:#?}", err));
+//!
+//! tokio::run(future);
+//! ```
 
 pub extern crate from_as;
+pub extern crate futures;
 extern crate graph_error;
 extern crate graph_oauth;
+#[macro_use]
 extern crate log;
 extern crate pretty_env_logger;
 extern crate reqwest;
@@ -104,6 +125,10 @@ pub mod prelude {
     pub use crate::http::ToResponse;
     pub use crate::types::collection::Collection;
     pub use crate::types::delta::*;
+    pub use crate::types::drive::Drive;
+    pub use crate::types::driveitem::DriveItem;
+    pub use crate::types::etag::ETag;
+    pub use crate::types::prefer::{BodyContentType, Prefer, Preference};
 }
 
 /// Reexport of graph-oauth crate.
@@ -1,5 +1,4 @@
-use crate::client::Graph;
-use crate::http::{DownloadClient, GraphRequestType, GraphResponse, IntoResponse};
+use crate::http::{GraphRequestType, GraphResponse, IntoResponse, RequestClient};
 use crate::types::collection::Collection;
 use crate::types::content::Content;
 use graph_error::GraphRsError;
@@ -19,33 +18,39 @@ register_client!(
     pages => "onenote/pages",
 );
 
-impl<'a> OnenoteRequest<'a> {
+impl<'a, Client> OnenoteRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( list_sections, Collection<serde_json::Value> => "{{section}}" );
     get!( list_section_groups, Collection<serde_json::Value> => "{{section_group}}" );
     get!( list_pages, Collection<serde_json::Value> => "{{pages}}" );
 
-    pub fn notebooks(&self) -> OnenoteNotebookRequest<'a> {
-        OnenoteNotebookRequest::new(self.client)
+    pub fn notebooks(&self) -> OnenoteNotebookRequest<'a, Client> {
+        OnenoteNotebookRequest::new(&self.client)
     }
 
-    pub fn sections(&self) -> OnenoteSectionRequest<'a> {
-        OnenoteSectionRequest::new(self.client)
+    pub fn sections(&self) -> OnenoteSectionRequest<'a, Client> {
+        OnenoteSectionRequest::new(&self.client)
     }
 
-    pub fn section_group(&self) -> OnenoteSectionGroupRequest<'a> {
-        OnenoteSectionGroupRequest::new(self.client)
+    pub fn section_group(&self) -> OnenoteSectionGroupRequest<'a, Client> {
+        OnenoteSectionGroupRequest::new(&self.client)
     }
 
-    pub fn pages(&self) -> OnenotePageRequest<'a> {
-        OnenotePageRequest::new(self.client)
+    pub fn pages(&self) -> OnenotePageRequest<'a, Client> {
+        OnenotePageRequest::new(&self.client)
     }
 
-    pub fn create_page<P: AsRef<Path>>(&self, file: P) -> IntoResponse<'a, serde_json::Value> {
+    pub fn create_page<P: AsRef<Path>>(
+        &self,
+        file: P,
+    ) -> IntoResponse<'a, serde_json::Value, Client> {
         render_path!(self.client, "{{pages}}");
 
         if !file.as_ref().extension().eq(&Some(OsStr::new("html"))) {
             return IntoResponse::new_error(
-                self.client,
+                &self.client,
                 GraphRsError::InvalidFileExtension {
                     requires: "html".to_string(),
                     found: file
@@ -59,23 +64,25 @@ impl<'a> OnenoteRequest<'a> {
             );
         }
 
-        let file = File::open(file.as_ref());
-        if let Err(e) = file {
-            IntoResponse::new_error(self.client, GraphFailure::from(e))
+        if let Err(e) = File::open(file.as_ref()) {
+            IntoResponse::new_error(&self.client, GraphFailure::from(e))
         } else {
             self.client
                 .builder()
-                .set_body(file.unwrap())
+                .set_body_file(file)
                 .header(CONTENT_TYPE, HeaderValue::from_static("text/html"))
                 .set_method(Method::POST);
-            IntoResponse::new(self.client)
+            IntoResponse::new(&self.client)
         }
     }
 }
 
 register_client!(OnenoteNotebookRequest,);
 
-impl<'a> OnenoteNotebookRequest<'a> {
+impl<'a, Client> OnenoteNotebookRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( list, Collection<serde_json::Value> => "{{notebook}}" );
     get!( | list_sections, Collection<serde_json::Value> => "{{notebook}}/{{id}}/sections" );
     get!( | get, serde_json::Value => "{{notebook}}/{{id}}" );
@@ -83,7 +90,10 @@ impl<'a> OnenoteNotebookRequest<'a> {
     post!( [ | copy, serde_json::Value => "{{notebook}}/{{id}}/copyNotebook" ] );
     post!( [ | create_section, serde_json::Value => "{{notebook}}/{{id}}/sections" ] );
 
-    pub fn recent(&self, include_personal_notebooks: bool) -> IntoResponse<'a, serde_json::Value> {
+    pub fn recent(
+        &self,
+        include_personal_notebooks: bool,
+    ) -> IntoResponse<'a, serde_json::Value, Client> {
         render_path!(
             self.client,
             format!(
@@ -92,13 +102,16 @@ impl<'a> OnenoteNotebookRequest<'a> {
             ).as_str()
         );
         self.client.builder().set_method(Method::GET);
-        IntoResponse::new(self.client)
+        IntoResponse::new(&self.client)
     }
 }
 
 register_client!(OnenoteSectionRequest,);
 
-impl<'a> OnenoteSectionRequest<'a> {
+impl<'a, Client> OnenoteSectionRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( list, Collection<serde_json::Value> => "{{section}}" );
     get!( | list_pages, Collection<serde_json::Value> => "{{section}}/{{id}}/pages" );
     get!( | get, serde_json::Value => "{{section}}/{{id}}" );
@@ -109,7 +122,7 @@ impl<'a> OnenoteSectionRequest<'a> {
         &self,
         id: S,
         file: P,
-    ) -> IntoResponse<'a, serde_json::Value> {
+    ) -> IntoResponse<'a, serde_json::Value, Client> {
         render_path!(
             self.client,
             "{{section}}/{{id}}/pages",
@@ -118,7 +131,7 @@ impl<'a> OnenoteSectionRequest<'a> {
 
         if !file.as_ref().extension().eq(&Some(OsStr::new("html"))) {
             return IntoResponse::new_error(
-                self.client,
+                &self.client,
                 GraphRsError::InvalidFileExtension {
                     requires: "html".to_string(),
                     found: file
@@ -132,23 +145,25 @@ impl<'a> OnenoteSectionRequest<'a> {
             );
         }
 
-        let file = File::open(file.as_ref());
-        if let Err(e) = file {
-            IntoResponse::new_error(self.client, GraphFailure::from(e))
+        if let Err(e) = File::open(file.as_ref()) {
+            IntoResponse::new_error(&self.client, GraphFailure::from(e))
         } else {
             self.client
                 .builder()
-                .set_body(file.unwrap())
+                .set_body_file(file)
                 .header(CONTENT_TYPE, HeaderValue::from_static("text/html"))
                 .set_method(Method::POST);
-            IntoResponse::new(self.client)
+            IntoResponse::new(&self.client)
         }
     }
 }
 
 register_client!(OnenoteSectionGroupRequest,);
 
-impl<'a> OnenoteSectionGroupRequest<'a> {
+impl<'a, Client> OnenoteSectionGroupRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( list, Collection<serde_json::Value> => "{{section_group}}" );
     get!( | list_sections, Collection<serde_json::Value> => "{{section_group}}/{{id}}/sections" );
     get!( | get, serde_json::Value => "{{section_group}}/{{id}}" );
@@ -158,7 +173,10 @@ impl<'a> OnenoteSectionGroupRequest<'a> {
 
 register_client!(OnenotePageRequest,);
 
-impl<'a> OnenotePageRequest<'a> {
+impl<'a, Client> OnenotePageRequest<'a, Client>
+where
+    Client: RequestClient,
+{
     get!( list, Collection<serde_json::Value> => "{{pages}}" );
     get!( | get, serde_json::Value => "{{pages}}/{{id}}" );
     get!( | content, GraphResponse<Content> => "{{pages}}/{{id}}/content" );
@@ -166,11 +184,7 @@ impl<'a> OnenotePageRequest<'a> {
     post!( [ | copy_to_section, GraphResponse<Content> => "{{pages}}/{{id}}/copyToSection" ] );
     delete!( | delete, GraphResponse<Content> => "{{pages}}/{{id}}" );
 
-    pub fn download<S: AsRef<str>, P: AsRef<Path>>(
-        &'a self,
-        id: S,
-        directory: P,
-    ) -> DownloadClient {
+    pub fn download<S: AsRef<str>, P: AsRef<Path>>(&self, id: S, directory: P) -> Client::Download {
         render_path!(
             self.client,
             "{{pages}}/{{id}}/content",
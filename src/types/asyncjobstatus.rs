@@ -18,4 +18,23 @@ pub struct AsyncJobStatus {
     #[serde(rename = "statusDescription")]
     #[serde(skip_serializing_if = "Option::is_none")]
     status_description: Option<String>,
+    #[serde(rename = "resourceLocation")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resource_location: Option<String>,
+}
+
+impl AsyncJobStatus {
+    fn is_status(&self, status: &str) -> bool {
+        self.status
+            .as_ref()
+            .map_or(false, |s| s.eq_ignore_ascii_case(status))
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.is_status("completed")
+    }
+
+    pub fn is_failed(&self) -> bool {
+        self.is_status("failed") || self.is_status("deleteFailed")
+    }
 }
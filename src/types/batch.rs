@@ -0,0 +1,520 @@
+use crate::http::{
+    GraphResponse, HttpResponse, IntoResponse, PreparedRequest, RequestBody, RequestClient,
+};
+use crate::types::content::Content;
+use graph_error::{GraphError, GraphFailure, GraphHeaders, GraphResult};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::{Method, StatusCode};
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::io::Cursor;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use url::Url;
+
+/// The maximum number of requests in a single JSON batch.
+pub const BATCH_LIMIT: usize = 20;
+
+/// A single request in a JSON batch.
+///
+/// The url is relative to the version of the API, such as `/me/drive`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Getters)]
+#[get = "pub"]
+pub struct BatchStep {
+    id: String,
+    method: String,
+    url: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    headers: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body: Option<serde_json::Value>,
+    #[serde(rename = "dependsOn")]
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    depends_on: Vec<String>,
+}
+
+impl BatchStep {
+    pub fn new(id: &str, method: Method, url: &str) -> BatchStep {
+        let url = if url.starts_with('/') {
+            url.to_string()
+        } else {
+            format!("/{}", url)
+        };
+        BatchStep {
+            id: id.to_string(),
+            method: method.to_string(),
+            url,
+            headers: BTreeMap::new(),
+            body: None,
+            depends_on: Vec::new(),
+        }
+    }
+
+    pub fn get(id: &str, url: &str) -> BatchStep {
+        BatchStep::new(id, Method::GET, url)
+    }
+
+    pub fn post(id: &str, url: &str, body: serde_json::Value) -> BatchStep {
+        BatchStep::new(id, Method::POST, url).body(body)
+    }
+
+    pub fn patch(id: &str, url: &str, body: serde_json::Value) -> BatchStep {
+        BatchStep::new(id, Method::PATCH, url).body(body)
+    }
+
+    pub fn put(id: &str, url: &str, body: serde_json::Value) -> BatchStep {
+        BatchStep::new(id, Method::PUT, url).body(body)
+    }
+
+    pub fn delete(id: &str, url: &str) -> BatchStep {
+        BatchStep::new(id, Method::DELETE, url)
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> BatchStep {
+        self.headers.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    /// Set the JSON body. The Content-Type header, which batch requests
+    /// require for a body, is set to application/json if it is not set.
+    pub fn body(mut self, body: serde_json::Value) -> BatchStep {
+        let has_content_type = self
+            .headers
+            .keys()
+            .any(|name| name.eq_ignore_ascii_case("content-type"));
+        if !has_content_type {
+            self.headers
+                .insert("Content-Type".into(), "application/json".into());
+        }
+        self.body = Some(body);
+        self
+    }
+
+    /// Run this step after the step with the given id. Steps that
+    /// depend on each other are always sent in the same batch.
+    pub fn depends_on(mut self, id: &str) -> BatchStep {
+        self.depends_on.push(id.to_string());
+        self
+    }
+
+    /// A step with the method, headers and body of a prepared request.
+    /// The url is made relative to the version of the API by removing
+    /// the base url of the client, such as `https://graph.microsoft.com/v1.0`.
+    ///
+    /// Returns an error if the url of the request does not start with
+    /// the base url.
+    pub fn from_prepared(
+        id: &str,
+        prepared: &PreparedRequest,
+        base: &str,
+    ) -> GraphResult<BatchStep> {
+        let base = base.trim_end_matches('/');
+        let url = prepared.url().as_str();
+        let relative = match url.get(base.len()..) {
+            Some(relative)
+                if url.starts_with(base) &&
+                    (relative.is_empty() ||
+                        relative.starts_with('/') ||
+                        relative.starts_with('?')) =>
+            {
+                relative
+            },
+            _ => {
+                return Err(GraphFailure::invalid(&format!(
+                    "batch step url. The url {} does not start with {}",
+                    url, base
+                )));
+            },
+        };
+
+        let mut step = BatchStep::new(id, Method::GET, relative);
+        step.method = prepared.method().to_string();
+        for (name, value) in prepared.headers().iter() {
+            // Only a step with a body needs a Content-Type.
+            if name.eq_ignore_ascii_case(AUTHORIZATION.as_str()) ||
+                (prepared.body().is_none() && name.eq_ignore_ascii_case(CONTENT_TYPE.as_str()))
+            {
+                continue;
+            }
+            step.headers.insert(name.to_string(), value.to_string());
+        }
+        match prepared.body() {
+            Some(RequestBody::Text(text)) => {
+                let body = serde_json::from_str(text.as_str())
+                    .unwrap_or_else(|_| serde_json::Value::String(text.to_string()));
+                step = step.body(body);
+            },
+            Some(_) => {
+                return Err(GraphFailure::invalid(
+                    "batch step body. Only text and json bodies can be batched",
+                ));
+            },
+            None => {},
+        }
+        Ok(step)
+    }
+}
+
+/// The id of a step added from a request chain. The key is used to get
+/// the response of the step as the type `send` would have returned.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchKey<T> {
+    id: String,
+    phantom: PhantomData<T>,
+}
+
+impl<T> BatchKey<T> {
+    pub fn id(&self) -> &str {
+        self.id.as_str()
+    }
+}
+
+/// A JSON batch of requests.
+///
+/// Batches with more than 20 steps are split into several batches when
+/// sent. Steps that depend on each other are kept in the same batch.
+///
+/// # Example
+/// ```
+/// use graph_rs::types::batch::{BatchRequest, BatchStep};
+///
+/// let mut batch = BatchRequest::new();
+/// batch
+///     .add(BatchStep::get("1", "/me/drive"))
+///     .add(BatchStep::post("2", "/me/drive/root/children", serde_json::json!({
+///         "name": "New Folder",
+///         "folder": {}
+///     })))
+///     .add(BatchStep::get("3", "/me/drive/root/children").depends_on("2"));
+///
+/// assert_eq!(1, batch.chunks().unwrap().len());
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchRequest {
+    requests: Vec<BatchStep>,
+}
+
+impl BatchRequest {
+    pub fn new() -> BatchRequest {
+        BatchRequest::default()
+    }
+
+    pub fn add(&mut self, step: BatchStep) -> &mut BatchRequest {
+        self.requests.push(step);
+        self
+    }
+
+    /// Add a request chain as a step. The request is not sent.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// let mut batch = BatchRequest::new();
+    /// let drive = batch.add_request("1", &client.v1().me().drive().drive())?;
+    /// let children = batch.add_request("2", &client.v1().me().drive().root_children())?;
+    ///
+    /// let response = client.v1().send_batch(&batch)?;
+    /// let drive: GraphResponse<Drive> = response.response(&drive)?;
+    /// let children: GraphResponse<Collection<DriveItem>> = response.response(&children)?;
+    /// ```
+    pub fn add_request<T, Client>(
+        &mut self,
+        id: &str,
+        request: &IntoResponse<T, Client>,
+    ) -> GraphResult<BatchKey<T>>
+    where
+        Client: RequestClient,
+    {
+        self.add(BatchStep::from_prepared(
+            id,
+            &request.built()?,
+            request.base_url().as_str(),
+        )?);
+        Ok(BatchKey {
+            id: id.to_string(),
+            phantom: PhantomData,
+        })
+    }
+
+    pub fn steps(&self) -> &[BatchStep] {
+        self.requests.as_slice()
+    }
+
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Split the batch into batches of at most 20 steps. Steps that are
+    /// connected by `dependsOn` are kept in the same batch and the order of
+    /// the steps is kept.
+    ///
+    /// Returns an error if an id is used twice, a step depends on an id
+    /// that is not in the batch, or more than 20 steps depend on each other.
+    pub fn chunks(&self) -> GraphResult<Vec<BatchRequest>> {
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        for (i, step) in self.requests.iter().enumerate() {
+            if index.insert(step.id.as_str(), i).is_some() {
+                return Err(GraphFailure::invalid(&format!(
+                    "batch step id. The id {} is used more than once",
+                    step.id
+                )));
+            }
+        }
+
+        // Group steps connected by dependsOn using the
+        // first step of each group as the root.
+        let mut root: Vec<usize> = (0..self.requests.len()).collect();
+        fn find(root: &mut Vec<usize>, i: usize) -> usize {
+            let mut i = i;
+            while root[i] != i {
+                root[i] = root[root[i]];
+                i = root[i];
+            }
+            i
+        }
+        for (i, step) in self.requests.iter().enumerate() {
+            for id in step.depends_on.iter() {
+                let j = *index.get(id.as_str()).ok_or_else(|| {
+                    GraphFailure::invalid(&format!(
+                        "batch step id. Step {} depends on {} which is not in the batch",
+                        step.id, id
+                    ))
+                })?;
+                let (a, b) = (find(&mut root, i), find(&mut root, j));
+                root[a.max(b)] = a.min(b);
+            }
+        }
+
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        let mut group_of: HashMap<usize, usize> = HashMap::new();
+        for i in 0..self.requests.len() {
+            let r = find(&mut root, i);
+            match group_of.get(&r) {
+                Some(g) => groups[*g].push(i),
+                None => {
+                    group_of.insert(r, groups.len());
+                    groups.push(vec![i]);
+                },
+            }
+        }
+
+        let mut chunks: Vec<BatchRequest> = Vec::new();
+        let mut current = BatchRequest::new();
+        for group in groups {
+            if group.len() > BATCH_LIMIT {
+                return Err(GraphFailure::invalid(&format!(
+                    "batch. More than {} steps depend on each other",
+                    BATCH_LIMIT
+                )));
+            }
+            if current.len() + group.len() > BATCH_LIMIT {
+                chunks.push(std::mem::replace(&mut current, BatchRequest::new()));
+            }
+            for i in group {
+                current.add(self.requests[i].clone());
+            }
+        }
+        if !current.is_empty() {
+            chunks.push(current);
+        }
+        Ok(chunks)
+    }
+}
+
+/// The response to a single step of a JSON batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Getters)]
+#[get = "pub"]
+pub struct BatchStepResponse {
+    id: String,
+    status: u16,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    #[serde(default)]
+    body: Option<serde_json::Value>,
+}
+
+impl BatchStepResponse {
+    pub fn is_success(&self) -> bool {
+        self.status >= 200 && self.status < 300
+    }
+
+    /// The error for the step, if any, including the error message
+    /// in the body.
+    pub fn error(&self) -> Option<GraphFailure> {
+        let mut error = GraphError::try_from(self.status).ok()?;
+        error.set_headers(GraphHeaders::new("", self.status, self.header_map()));
+        let body = self
+            .body
+            .as_ref()
+            .map(|body| body.to_string())
+            .unwrap_or_default();
+        Some(GraphFailure::from_graph_error(error, body.as_str()))
+    }
+
+    /// Deserialize the body of the step. Returns the error for
+    /// the step if it was not successful.
+    pub fn json<T>(&self) -> GraphResult<T>
+    where
+        for<'de> T: serde::Deserialize<'de>,
+    {
+        if let Some(err) = self.error() {
+            return Err(err);
+        }
+        let body = self.body.clone().unwrap_or(serde_json::Value::Null);
+        Ok(serde_json::from_value(body)?)
+    }
+
+    fn header_map(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in self.headers.iter() {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::from_str(name.as_str()),
+                HeaderValue::from_str(value.as_str()),
+            ) {
+                headers.insert(name, value);
+            }
+        }
+        headers
+    }
+
+    /// The step as a response to the url of the step.
+    pub fn to_http_response(&self, url: Url) -> GraphResult<HttpResponse> {
+        let status =
+            StatusCode::from_u16(self.status).map_err(|_| GraphFailure::invalid("status"))?;
+        let headers = self.header_map();
+        let body = match self.body.as_ref() {
+            Some(serde_json::Value::String(text)) => text.as_bytes().to_vec(),
+            Some(body) => serde_json::to_vec(body)?,
+            None => Vec::new(),
+        };
+        Ok(HttpResponse::new(status, url, headers, Cursor::new(body)))
+    }
+}
+
+/// Converts the response of a batch step to the type that
+/// `send` returns for the same request.
+pub trait FromBatchStep {
+    type Output;
+
+    fn from_batch_step(response: HttpResponse) -> GraphResult<Self::Output>;
+}
+
+impl<T> FromBatchStep for T
+where
+    for<'de> T: serde::Deserialize<'de>,
+{
+    type Output = GraphResponse<T>;
+
+    fn from_batch_step(mut response: HttpResponse) -> GraphResult<Self::Output> {
+        if let Some(err) = response.error() {
+            return Err(err);
+        }
+        let value: T = response.json()?;
+        Ok(GraphResponse::new(response, value))
+    }
+}
+
+impl FromBatchStep for GraphResponse<Content> {
+    type Output = GraphResponse<Content>;
+
+    fn from_batch_step(mut response: HttpResponse) -> GraphResult<Self::Output> {
+        if let Some(err) = response.error() {
+            return Err(err);
+        }
+        GraphResponse::try_from(response)
+    }
+}
+
+/// The body returned for a single JSON batch request.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct BatchResponseBody {
+    #[serde(default)]
+    pub responses: Vec<BatchStepResponse>,
+}
+
+/// The responses to all of the steps of a batch, in the order the
+/// steps were added.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BatchResponse {
+    responses: Vec<BatchStepResponse>,
+    urls: HashMap<String, String>,
+}
+
+impl BatchResponse {
+    pub(crate) fn new(
+        batch: &BatchRequest,
+        responses: Vec<BatchStepResponse>,
+        base: &Url,
+    ) -> BatchResponse {
+        let base = base.as_str().trim_end_matches('/');
+        let urls = batch
+            .steps()
+            .iter()
+            .map(|step| (step.id.to_string(), format!("{}{}", base, step.url)))
+            .collect();
+        let order: HashMap<&str, usize> = batch
+            .steps()
+            .iter()
+            .enumerate()
+            .map(|(i, step)| (step.id.as_str(), i))
+            .collect();
+        let mut responses = responses;
+        responses.sort_by_key(|response| {
+            order
+                .get(response.id.as_str())
+                .cloned()
+                .unwrap_or(usize::max_value())
+        });
+        BatchResponse { responses, urls }
+    }
+
+    pub fn responses(&self) -> &[BatchStepResponse] {
+        self.responses.as_slice()
+    }
+
+    /// The response for the step with the id.
+    pub fn get(&self, id: &str) -> Option<&BatchStepResponse> {
+        self.responses.iter().find(|response| response.id == id)
+    }
+
+    /// Deserialize the body of the step with the id.
+    pub fn json<T>(&self, id: &str) -> GraphResult<T>
+    where
+        for<'de> T: serde::Deserialize<'de>,
+    {
+        self.get(id)
+            .ok_or_else(|| GraphFailure::not_found(&format!("batch response for step {}", id)))?
+            .json()
+    }
+
+    /// The response of a step added from a request chain, as the
+    /// type `send` would have returned for the chain.
+    pub fn response<T>(&self, key: &BatchKey<T>) -> GraphResult<T::Output>
+    where
+        T: FromBatchStep,
+    {
+        let response = self.get(key.id()).ok_or_else(|| {
+            GraphFailure::not_found(&format!("batch response for step {}", key.id()))
+        })?;
+        let url = self
+            .urls
+            .get(key.id())
+            .ok_or_else(|| GraphFailure::not_found(&format!("batch step {}", key.id())))?;
+        let url = Url::parse(url.as_str())?;
+        T::from_batch_step(response.to_http_response(url)?)
+    }
+
+    /// The ids of the steps that were not successful.
+    pub fn failed(&self) -> Vec<&str> {
+        self.responses
+            .iter()
+            .filter(|response| !response.is_success())
+            .map(|response| response.id.as_str())
+            .collect()
+    }
+}
@@ -1,3 +1,4 @@
+use crate::http::HttpResponse;
 use from_as::TryFrom;
 use graph_error::GraphFailure;
 
@@ -36,10 +37,10 @@ impl ToString for Content {
     }
 }
 
-impl TryFrom<&mut reqwest::Response> for Content {
+impl TryFrom<&mut HttpResponse> for Content {
     type Error = GraphFailure;
 
-    fn try_from(value: &mut reqwest::Response) -> Result<Self, Self::Error> {
+    fn try_from(value: &mut HttpResponse) -> Result<Self, Self::Error> {
         let content: String = value.text()?;
         Ok(Content { content })
     }
@@ -0,0 +1,78 @@
+use crate::types::driveitem::SharePointIds;
+use crate::types::identity::IdentitySet;
+use from_as::*;
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/drive?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct Drive {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    /// One of personal, business or documentLibrary.
+    #[serde(rename = "driveType")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    drive_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owner: Option<IdentitySet>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quota: Option<Quota>,
+    #[serde(rename = "createdBy")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created_by: Option<IdentitySet>,
+    #[serde(rename = "createdDateTime")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created_date_time: Option<String>,
+    #[serde(rename = "lastModifiedBy")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_modified_by: Option<IdentitySet>,
+    #[serde(rename = "lastModifiedDateTime")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_modified_date_time: Option<String>,
+    #[serde(rename = "webUrl")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    web_url: Option<String>,
+    #[serde(rename = "sharepointIds")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sharepoint_ids: Option<SharePointIds>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/quota?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct Quota {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    used: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remaining: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deleted: Option<i64>,
+    /// One of normal, nearing, critical or exceeded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    state: Option<String>,
+    #[serde(rename = "storagePlanInformation")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    storage_plan_information: Option<StoragePlanInformation>,
+}
+
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct StoragePlanInformation {
+    #[serde(rename = "upgradeAvailable")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    upgrade_available: Option<bool>,
+}
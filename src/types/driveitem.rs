@@ -0,0 +1,477 @@
+use crate::types::etag::ETag;
+use crate::types::identity::IdentitySet;
+use crate::types::permission::Permission;
+use crate::types::thumbnail::ThumbnailSet;
+use from_as::*;
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/driveitem?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct DriveItem {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(rename = "eTag")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    e_tag: Option<String>,
+    /// Changes when the content of the item changes.
+    #[serde(rename = "cTag")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    c_tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    size: Option<i64>,
+    #[serde(rename = "webUrl")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    web_url: Option<String>,
+    #[serde(rename = "webDavUrl")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    web_dav_url: Option<String>,
+    /// A short lived url to download the content of a file.
+    #[serde(rename = "@microsoft.graph.downloadUrl")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    download_url: Option<String>,
+    #[serde(rename = "createdBy")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created_by: Option<IdentitySet>,
+    #[serde(rename = "createdDateTime")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created_date_time: Option<String>,
+    #[serde(rename = "lastModifiedBy")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_modified_by: Option<IdentitySet>,
+    #[serde(rename = "lastModifiedDateTime")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_modified_date_time: Option<String>,
+    #[serde(rename = "parentReference")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_reference: Option<ItemReference>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<File>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    folder: Option<Folder>,
+    #[serde(rename = "fileSystemInfo")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file_system_info: Option<FileSystemInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<Image>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    photo: Option<Photo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    audio: Option<Audio>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    video: Option<Video>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location: Option<GeoCoordinates>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    package: Option<Package>,
+    /// Set if the item is the root of a drive.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    root: Option<Root>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deleted: Option<Deleted>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shared: Option<Shared>,
+    #[serde(rename = "specialFolder")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    special_folder: Option<SpecialFolder>,
+    /// The item in another drive that this item refers to.
+    #[serde(rename = "remoteItem")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remote_item: Option<Box<DriveItem>>,
+    #[serde(rename = "sharepointIds")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sharepoint_ids: Option<SharePointIds>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    children: Option<Vec<DriveItem>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thumbnails: Option<Vec<ThumbnailSet>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    permissions: Option<Vec<Permission>>,
+}
+
+impl DriveItem {
+    pub fn is_file(&self) -> bool {
+        self.file.is_some()
+    }
+
+    pub fn is_folder(&self) -> bool {
+        self.folder.is_some()
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.root.is_some()
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted.is_some()
+    }
+}
+
+impl ETag for DriveItem {
+    fn odata_etag(&self) -> Option<String> {
+        None
+    }
+
+    fn e_tag(&self) -> Option<String> {
+        self.e_tag.clone()
+    }
+
+    fn c_tag(&self) -> Option<String> {
+        self.c_tag.clone()
+    }
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/folder?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct Folder {
+    #[serde(rename = "childCount")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    child_count: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    view: Option<FolderView>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/folderview?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct FolderView {
+    #[serde(rename = "sortBy")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sort_by: Option<String>,
+    #[serde(rename = "sortOrder")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sort_order: Option<String>,
+    #[serde(rename = "viewType")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    view_type: Option<String>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/file?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct File {
+    #[serde(rename = "mimeType")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mime_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hashes: Option<Hashes>,
+    #[serde(rename = "processingMetadata")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    processing_metadata: Option<bool>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/hashes?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct Hashes {
+    #[serde(rename = "crc32Hash")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    crc32_hash: Option<String>,
+    #[serde(rename = "sha1Hash")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sha1_hash: Option<String>,
+    #[serde(rename = "sha256Hash")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sha256_hash: Option<String>,
+    #[serde(rename = "quickXorHash")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quick_xor_hash: Option<String>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/filesysteminfo?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct FileSystemInfo {
+    #[serde(rename = "createdDateTime")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created_date_time: Option<String>,
+    #[serde(rename = "lastAccessedDateTime")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_accessed_date_time: Option<String>,
+    #[serde(rename = "lastModifiedDateTime")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_modified_date_time: Option<String>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/itemreference?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct ItemReference {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    #[serde(rename = "driveId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    drive_id: Option<String>,
+    #[serde(rename = "driveType")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    drive_type: Option<String>,
+    #[serde(rename = "shareId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    share_id: Option<String>,
+    #[serde(rename = "siteId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    site_id: Option<String>,
+    #[serde(rename = "sharepointIds")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sharepoint_ids: Option<SharePointIds>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/sharepointids?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct SharePointIds {
+    #[serde(rename = "listId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    list_id: Option<String>,
+    #[serde(rename = "listItemId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    list_item_id: Option<String>,
+    #[serde(rename = "listItemUniqueId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    list_item_unique_id: Option<String>,
+    #[serde(rename = "siteId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    site_id: Option<String>,
+    #[serde(rename = "siteUrl")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    site_url: Option<String>,
+    #[serde(rename = "tenantId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tenant_id: Option<String>,
+    #[serde(rename = "webId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    web_id: Option<String>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/image?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct Image {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    width: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    height: Option<i64>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/photo?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct Photo {
+    #[serde(rename = "cameraMake")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    camera_make: Option<String>,
+    #[serde(rename = "cameraModel")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    camera_model: Option<String>,
+    #[serde(rename = "exposureDenominator")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exposure_denominator: Option<f64>,
+    #[serde(rename = "exposureNumerator")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exposure_numerator: Option<f64>,
+    #[serde(rename = "fNumber")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    f_number: Option<f64>,
+    #[serde(rename = "focalLength")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    focal_length: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    iso: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    orientation: Option<i64>,
+    #[serde(rename = "takenDateTime")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    taken_date_time: Option<String>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/audio?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct Audio {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    album: Option<String>,
+    #[serde(rename = "albumArtist")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    album_artist: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    artist: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bitrate: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    composers: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    copyright: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    disc: Option<i64>,
+    #[serde(rename = "discCount")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    disc_count: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    genre: Option<String>,
+    #[serde(rename = "hasDrm")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    has_drm: Option<bool>,
+    #[serde(rename = "isVariableBitrate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_variable_bitrate: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    track: Option<i64>,
+    #[serde(rename = "trackCount")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    track_count: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    year: Option<i64>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/video?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct Video {
+    #[serde(rename = "audioBitsPerSample")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    audio_bits_per_sample: Option<i64>,
+    #[serde(rename = "audioChannels")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    audio_channels: Option<i64>,
+    #[serde(rename = "audioFormat")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    audio_format: Option<String>,
+    #[serde(rename = "audioSamplesPerSecond")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    audio_samples_per_second: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bitrate: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration: Option<i64>,
+    #[serde(rename = "fourCC")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    four_cc: Option<String>,
+    #[serde(rename = "frameRate")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frame_rate: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    height: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    width: Option<i64>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/geocoordinates?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct GeoCoordinates {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    altitude: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    latitude: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    longitude: Option<f64>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/package?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct Package {
+    /// Set to oneNote for OneNote notebooks.
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    package_type: Option<String>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/deleted?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct Deleted {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    state: Option<String>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/shared?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct Shared {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owner: Option<IdentitySet>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<String>,
+    #[serde(rename = "sharedBy")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shared_by: Option<IdentitySet>,
+    #[serde(rename = "sharedDateTime")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shared_date_time: Option<String>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/specialfolder?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct SpecialFolder {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/root?view=odsp-graph-online
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile)]
+pub struct Root {}
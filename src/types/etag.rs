@@ -0,0 +1,52 @@
+/// ETags of a resource used for conditional requests.
+///
+/// The ETag can be sent back in an If-Match header so an update or delete
+/// only succeeds if the resource has not changed since it was read.
+///
+/// # Example
+/// ```rust,ignore
+/// let item = client.v1().me().drive().get_item("ITEM_ID").send()?;
+/// let etag = item.etag().unwrap();
+///
+/// let response = client.v1()
+///     .me()
+///     .drive()
+///     .update("ITEM_ID", &serde_json::json!({ "name": "new name" }))
+///     .if_match(etag.as_str())
+///     .send();
+///
+/// if let Err(GraphFailure::PreconditionFailed(err)) = response {
+///     println!("The item was changed. Current ETag: {:#?}", err.etag);
+/// }
+/// ```
+pub trait ETag<RHS = Self> {
+    /// The `@odata.etag` property.
+    fn odata_etag(&self) -> Option<String>;
+
+    /// The `eTag` property of drive items and list items.
+    fn e_tag(&self) -> Option<String>;
+
+    /// The `cTag` property of drive items. The cTag only
+    /// changes when the content of the item changes.
+    fn c_tag(&self) -> Option<String>;
+
+    /// The ETag to use in an If-Match header: `@odata.etag`
+    /// if there is one and otherwise `eTag`.
+    fn etag(&self) -> Option<String> {
+        self.odata_etag().or_else(|| self.e_tag())
+    }
+}
+
+impl ETag for serde_json::Value {
+    fn odata_etag(&self) -> Option<String> {
+        self["@odata.etag"].as_str().map(|s| s.to_string())
+    }
+
+    fn e_tag(&self) -> Option<String> {
+        self["eTag"].as_str().map(|s| s.to_string())
+    }
+
+    fn c_tag(&self) -> Option<String> {
+        self["cTag"].as_str().map(|s| s.to_string())
+    }
+}
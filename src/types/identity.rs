@@ -0,0 +1,34 @@
+use from_as::*;
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/identity?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct Identity {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(rename = "displayName")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    display_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/identityset?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct IdentitySet {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    application: Option<Identity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    device: Option<Identity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    group: Option<Identity>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user: Option<Identity>,
+}
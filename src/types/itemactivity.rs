@@ -0,0 +1,163 @@
+use crate::types::driveitem::DriveItem;
+use crate::types::identity::IdentitySet;
+use from_as::*;
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/itemactivity?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct ItemActivity {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    action: Option<ItemActionSet>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    actor: Option<IdentitySet>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    times: Option<ActivityTimeSet>,
+    #[serde(rename = "driveItem")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    drive_item: Option<DriveItem>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/itemactionset?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct ItemActionSet {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<CommentAction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    create: Option<CreateAction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delete: Option<DeleteAction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    edit: Option<EditAction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mention: Option<MentionAction>,
+    #[serde(rename = "move")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    move_action: Option<MoveAction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rename: Option<RenameAction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    restore: Option<RestoreAction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    share: Option<ShareAction>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<VersionAction>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/activitytimeset?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct ActivityTimeSet {
+    #[serde(rename = "observedDateTime")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    observed_date_time: Option<String>,
+    #[serde(rename = "recordedDateTime")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recorded_date_time: Option<String>,
+}
+
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct CommentAction {
+    #[serde(rename = "isReply")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_reply: Option<bool>,
+    #[serde(rename = "parentAuthor")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parent_author: Option<IdentitySet>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    participants: Option<Vec<IdentitySet>>,
+}
+
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct DeleteAction {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(rename = "objectType")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    object_type: Option<String>,
+}
+
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct MentionAction {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mentionees: Option<Vec<IdentitySet>>,
+}
+
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct MoveAction {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to: Option<String>,
+}
+
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct RenameAction {
+    #[serde(rename = "oldName")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    old_name: Option<String>,
+    #[serde(rename = "newName")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_name: Option<String>,
+}
+
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct ShareAction {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recipients: Option<Vec<IdentitySet>>,
+}
+
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct VersionAction {
+    #[serde(rename = "newVersion")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    new_version: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile)]
+pub struct CreateAction {}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile)]
+pub struct EditAction {}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile)]
+pub struct RestoreAction {}
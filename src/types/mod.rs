@@ -1,6 +1,15 @@
 pub mod asyncjobstatus;
+pub mod batch;
 pub mod boolresponse;
 pub mod collection;
 pub mod content;
 pub mod delta;
+pub mod drive;
+pub mod driveitem;
 pub mod embeddableurl;
+pub mod etag;
+pub mod identity;
+pub mod itemactivity;
+pub mod permission;
+pub mod prefer;
+pub mod thumbnail;
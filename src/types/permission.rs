@@ -0,0 +1,83 @@
+use crate::types::driveitem::ItemReference;
+use crate::types::identity::{Identity, IdentitySet};
+use from_as::*;
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/permission?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct Permission {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    /// Such as read, write, owner or sp.owner.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    roles: Option<Vec<String>>,
+    #[serde(rename = "shareId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    share_id: Option<String>,
+    #[serde(rename = "expirationDateTime")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expiration_date_time: Option<String>,
+    #[serde(rename = "hasPassword")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    has_password: Option<bool>,
+    #[serde(rename = "grantedTo")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    granted_to: Option<IdentitySet>,
+    #[serde(rename = "grantedToIdentities")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    granted_to_identities: Option<Vec<IdentitySet>>,
+    #[serde(rename = "inheritedFrom")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inherited_from: Option<ItemReference>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    invitation: Option<SharingInvitation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    link: Option<SharingLink>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/sharinglink?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct SharingLink {
+    /// One of view, edit or embed.
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    link_type: Option<String>,
+    /// One of anonymous, organization or users.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<String>,
+    #[serde(rename = "webUrl")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    web_url: Option<String>,
+    #[serde(rename = "webHtml")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    web_html: Option<String>,
+    #[serde(rename = "preventsDownload")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prevents_download: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    application: Option<Identity>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/sharinginvitation?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct SharingInvitation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+    #[serde(rename = "invitedBy")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    invited_by: Option<IdentitySet>,
+    #[serde(rename = "signInRequired")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sign_in_required: Option<bool>,
+}
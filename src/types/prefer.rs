@@ -0,0 +1,236 @@
+use reqwest::header::{HeaderMap, HeaderValue};
+use std::fmt;
+
+/// The header used to ask the API for optional behavior.
+pub static PREFER: &str = "prefer";
+/// The header the API uses to list the preferences that were applied.
+pub static PREFERENCE_APPLIED: &str = "preference-applied";
+
+/// The format of the body of messages and events.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum BodyContentType {
+    Text,
+    Html,
+}
+
+impl AsRef<str> for BodyContentType {
+    fn as_ref(&self) -> &str {
+        match self {
+            BodyContentType::Text => "text",
+            BodyContentType::Html => "html",
+        }
+    }
+}
+
+/// A preference sent in the Prefer header.
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Preference {
+    /// `return=minimal`: return 204 No Content for an update
+    /// instead of the updated resource.
+    ReturnMinimal,
+    /// `return=representation`: return the created or updated resource.
+    ReturnRepresentation,
+    /// `odata.maxpagesize`: the maximum number of items in each page.
+    MaxPageSize(u32),
+    /// `outlook.timezone`: the time zone of event times in the response.
+    TimeZone(String),
+    /// `outlook.body-content-type`: the format of message and event bodies.
+    BodyContentType(BodyContentType),
+    /// `respond-async`: process the request as a long running operation.
+    RespondAsync,
+    /// `IdType="ImmutableId"`: return Outlook ids that do not change
+    /// when an item is moved.
+    ImmutableId,
+    /// Any other preference, such as `odata.track-changes`.
+    Other(String),
+}
+
+impl Preference {
+    /// Parse a single preference such as `odata.maxpagesize=50`.
+    pub fn parse(value: &str) -> Preference {
+        let value = value.trim();
+        let (name, param) = match value.find('=') {
+            Some(i) => (
+                value[..i].trim(),
+                Some(value[i + 1..].trim().trim_matches('"')),
+            ),
+            None => (value, None),
+        };
+
+        match (name.to_ascii_lowercase().as_str(), param) {
+            ("return", Some("minimal")) => Preference::ReturnMinimal,
+            ("return", Some("representation")) => Preference::ReturnRepresentation,
+            ("odata.maxpagesize", Some(size)) => match size.parse() {
+                Ok(size) => Preference::MaxPageSize(size),
+                Err(_) => Preference::Other(value.to_string()),
+            },
+            ("outlook.timezone", Some(time_zone)) => Preference::TimeZone(time_zone.to_string()),
+            ("outlook.body-content-type", Some(content_type)) => {
+                match content_type.to_ascii_lowercase().as_str() {
+                    "text" => Preference::BodyContentType(BodyContentType::Text),
+                    "html" => Preference::BodyContentType(BodyContentType::Html),
+                    _ => Preference::Other(value.to_string()),
+                }
+            },
+            ("respond-async", None) => Preference::RespondAsync,
+            ("idtype", Some("ImmutableId")) => Preference::ImmutableId,
+            _ => Preference::Other(value.to_string()),
+        }
+    }
+
+    /// The name of the preference. A preference replaces any
+    /// preference with the same name.
+    pub fn name(&self) -> String {
+        match self {
+            Preference::ReturnMinimal | Preference::ReturnRepresentation => "return".into(),
+            Preference::MaxPageSize(_) => "odata.maxpagesize".into(),
+            Preference::TimeZone(_) => "outlook.timezone".into(),
+            Preference::BodyContentType(_) => "outlook.body-content-type".into(),
+            Preference::RespondAsync => "respond-async".into(),
+            Preference::ImmutableId => "idtype".into(),
+            Preference::Other(value) => value
+                .split('=')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase(),
+        }
+    }
+}
+
+impl fmt::Display for Preference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Preference::ReturnMinimal => write!(f, "return=minimal"),
+            Preference::ReturnRepresentation => write!(f, "return=representation"),
+            Preference::MaxPageSize(size) => write!(f, "odata.maxpagesize={}", size),
+            Preference::TimeZone(time_zone) => write!(f, "outlook.timezone=\"{}\"", time_zone),
+            Preference::BodyContentType(content_type) => {
+                write!(f, "outlook.body-content-type=\"{}\"", content_type.as_ref())
+            },
+            Preference::RespondAsync => write!(f, "respond-async"),
+            Preference::ImmutableId => write!(f, "IdType=\"ImmutableId\""),
+            Preference::Other(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// The preferences of a request, or the preferences the API applied.
+///
+/// # Example
+/// ```
+/// use graph_rs::types::prefer::{BodyContentType, Prefer, Preference};
+///
+/// let mut prefer = Prefer::new();
+/// prefer
+///     .add(Preference::MaxPageSize(50))
+///     .add(Preference::BodyContentType(BodyContentType::Text));
+/// assert_eq!(
+///     "odata.maxpagesize=50, outlook.body-content-type=\"text\"",
+///     prefer.to_string()
+/// );
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Prefer {
+    preferences: Vec<Preference>,
+}
+
+impl Prefer {
+    pub fn new() -> Prefer {
+        Prefer::default()
+    }
+
+    /// Parse the value of a Prefer or Preference-Applied header.
+    pub fn parse(value: &str) -> Prefer {
+        let mut prefer = Prefer::new();
+        let mut quoted = false;
+        let mut start = 0;
+        for (i, c) in value.char_indices() {
+            match c {
+                '"' => quoted = !quoted,
+                ',' if !quoted => {
+                    prefer.add_parsed(&value[start..i]);
+                    start = i + 1;
+                },
+                _ => {},
+            }
+        }
+        prefer.add_parsed(&value[start..]);
+        prefer
+    }
+
+    /// The preferences in every header with the given name.
+    pub fn from_headers(headers: &HeaderMap, name: &str) -> Prefer {
+        let mut prefer = Prefer::new();
+        for value in headers.get_all(name).iter() {
+            if let Ok(value) = value.to_str() {
+                prefer.extend(&Prefer::parse(value));
+            }
+        }
+        prefer
+    }
+
+    /// Add a preference, replacing any preference with the same name.
+    pub fn add(&mut self, preference: Preference) -> &mut Prefer {
+        let name = preference.name();
+        match self.preferences.iter().position(|p| p.name() == name) {
+            Some(i) => self.preferences[i] = preference,
+            None => self.preferences.push(preference),
+        }
+        self
+    }
+
+    /// Add each preference of another set.
+    pub fn extend(&mut self, other: &Prefer) -> &mut Prefer {
+        for preference in other.preferences.iter() {
+            self.add(preference.clone());
+        }
+        self
+    }
+
+    pub fn preferences(&self) -> &[Preference] {
+        self.preferences.as_slice()
+    }
+
+    pub fn contains(&self, preference: &Preference) -> bool {
+        self.preferences.contains(preference)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.preferences.is_empty()
+    }
+
+    /// Add the preferences to the Prefer header, replacing any
+    /// preferences in the header with the same name.
+    pub fn apply(&self, headers: &mut HeaderMap) {
+        if self.is_empty() {
+            return;
+        }
+        let mut prefer = Prefer::from_headers(headers, PREFER);
+        prefer.extend(self);
+        if let Ok(value) = HeaderValue::from_str(prefer.to_string().as_str()) {
+            headers.insert(PREFER, value);
+        }
+    }
+
+    fn add_parsed(&mut self, value: &str) {
+        if !value.trim().is_empty() {
+            self.add(Preference::parse(value));
+        }
+    }
+}
+
+impl fmt::Display for Prefer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let preferences: Vec<String> = self.preferences.iter().map(|p| p.to_string()).collect();
+        write!(f, "{}", preferences.join(", "))
+    }
+}
+
+impl From<Preference> for Prefer {
+    fn from(preference: Preference) -> Self {
+        Prefer {
+            preferences: vec![preference],
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use from_as::*;
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/thumbnail?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct Thumbnail {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    width: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    height: Option<i64>,
+    /// A url to fetch the content of the thumbnail.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(rename = "sourceItemId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_item_id: Option<String>,
+}
+
+// https://docs.microsoft.com/en-us/onedrive/developer/rest-api/resources/thumbnailset?view=odsp-graph-online
+#[derive(
+    Default, Debug, Clone, PartialEq, Serialize, Deserialize, FromFile, AsFile, Setters, Getters,
+)]
+#[set = "pub set"]
+#[get = "pub"]
+pub struct ThumbnailSet {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    small: Option<Thumbnail>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    medium: Option<Thumbnail>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    large: Option<Thumbnail>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<Thumbnail>,
+}
@@ -0,0 +1,99 @@
+use crate::url::Filter;
+use std::fmt;
+
+/// A property for `$expand` with its own nested query options.
+///
+/// # Example
+/// ```
+/// use graph_rs::url::{Expand, Filter};
+///
+/// let expand = Expand::new("children")
+///     .select(&["id", "name"])
+///     .filter(Filter::gt("size", 0))
+///     .top(5);
+/// assert_eq!(
+///     "children($select=id,name;$filter=size gt 0;$top=5)",
+///     expand.to_string()
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expand {
+    property: String,
+    select: Vec<String>,
+    expand: Vec<Expand>,
+    filter: Option<Filter>,
+    order_by: Vec<String>,
+    top: Option<u32>,
+}
+
+impl Expand {
+    pub fn new(property: &str) -> Expand {
+        Expand {
+            property: property.to_string(),
+            select: Vec::new(),
+            expand: Vec::new(),
+            filter: None,
+            order_by: Vec::new(),
+            top: None,
+        }
+    }
+
+    pub fn select(mut self, value: &[&str]) -> Expand {
+        self.select.extend(value.iter().map(|s| s.to_string()));
+        self
+    }
+
+    pub fn expand(mut self, value: Expand) -> Expand {
+        self.expand.push(value);
+        self
+    }
+
+    pub fn filter(mut self, value: Filter) -> Expand {
+        self.filter = Some(value);
+        self
+    }
+
+    pub fn order_by(mut self, value: &[&str]) -> Expand {
+        self.order_by.extend(value.iter().map(|s| s.to_string()));
+        self
+    }
+
+    pub fn top(mut self, value: u32) -> Expand {
+        self.top = Some(value);
+        self
+    }
+}
+
+impl fmt::Display for Expand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut options = Vec::new();
+        if !self.select.is_empty() {
+            options.push(format!("$select={}", self.select.join(",")));
+        }
+        if !self.expand.is_empty() {
+            let expand: Vec<String> = self.expand.iter().map(|e| e.to_string()).collect();
+            options.push(format!("$expand={}", expand.join(",")));
+        }
+        if let Some(filter) = self.filter.as_ref() {
+            options.push(format!("$filter={}", filter));
+        }
+        if !self.order_by.is_empty() {
+            options.push(format!("$orderby={}", self.order_by.join(",")));
+        }
+        if let Some(top) = self.top {
+            options.push(format!("$top={}", top));
+        }
+
+        if options.is_empty() {
+            write!(f, "{}", self.property)
+        } else {
+            write!(f, "{}({})", self.property, options.join(";"))
+        }
+    }
+}
+
+impl From<&str> for Expand {
+    fn from(property: &str) -> Self {
+        Expand::new(property)
+    }
+}
@@ -0,0 +1,287 @@
+use graph_error::{GraphFailure, GraphResult};
+use std::fmt;
+
+/// A value used in a filter expression.
+///
+/// Strings are quoted with single quotes escaped. GUIDs, dates and
+/// numbers are written without quotes and are checked when created so
+/// they cannot change the meaning of the expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    String(String),
+    Guid(String),
+    Date(String),
+    DateTimeOffset(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Null,
+}
+
+impl Literal {
+    /// A GUID such as `01234567-89ab-cdef-0123-456789abcdef`.
+    pub fn guid(value: &str) -> GraphResult<Literal> {
+        let groups: Vec<&str> = value.split('-').collect();
+        let lengths: Vec<usize> = groups.iter().map(|group| group.len()).collect();
+        if lengths != [8, 4, 4, 4, 12] || !value.chars().all(|c| c == '-' || c.is_ascii_hexdigit())
+        {
+            return Err(GraphFailure::invalid("GUID"));
+        }
+        Ok(Literal::Guid(value.to_string()))
+    }
+
+    /// A date such as `2019-12-31`.
+    pub fn date(value: &str) -> GraphResult<Literal> {
+        let groups: Vec<&str> = value.split('-').collect();
+        let lengths: Vec<usize> = groups.iter().map(|group| group.len()).collect();
+        if lengths != [4, 2, 2] || !value.chars().all(|c| c == '-' || c.is_ascii_digit()) {
+            return Err(GraphFailure::invalid("date"));
+        }
+        Ok(Literal::Date(value.to_string()))
+    }
+
+    /// A date and time with an offset such as `2019-12-31T23:59:59Z`.
+    pub fn date_time_offset(value: &str) -> GraphResult<Literal> {
+        let mut parts = value.splitn(2, 'T');
+        let date = parts.next().unwrap_or_default();
+        let time = parts.next().unwrap_or_default();
+        if Literal::date(date).is_err() ||
+            time.is_empty() ||
+            !time
+                .chars()
+                .all(|c| c.is_ascii_digit() || ":.+-Z".contains(c))
+        {
+            return Err(GraphFailure::invalid("date time offset"));
+        }
+        Ok(Literal::DateTimeOffset(value.to_string()))
+    }
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Literal::String(value) => write!(f, "'{}'", value.replace('\'', "''")),
+            Literal::Guid(value) | Literal::Date(value) | Literal::DateTimeOffset(value) => {
+                write!(f, "{}", value)
+            },
+            Literal::Int(value) => write!(f, "{}", value),
+            Literal::Float(value) => write!(f, "{}", value),
+            Literal::Bool(value) => write!(f, "{}", value),
+            Literal::Null => write!(f, "null"),
+        }
+    }
+}
+
+impl From<&str> for Literal {
+    fn from(value: &str) -> Self {
+        Literal::String(value.to_string())
+    }
+}
+
+impl From<String> for Literal {
+    fn from(value: String) -> Self {
+        Literal::String(value)
+    }
+}
+
+impl From<i32> for Literal {
+    fn from(value: i32) -> Self {
+        Literal::Int(i64::from(value))
+    }
+}
+
+impl From<i64> for Literal {
+    fn from(value: i64) -> Self {
+        Literal::Int(value)
+    }
+}
+
+impl From<f64> for Literal {
+    fn from(value: f64) -> Self {
+        Literal::Float(value)
+    }
+}
+
+impl From<bool> for Literal {
+    fn from(value: bool) -> Self {
+        Literal::Bool(value)
+    }
+}
+
+/// An OData `$filter` expression.
+///
+/// Values are written as literals so strings are always quoted and
+/// escaped. Expressions combined with `and` and `or` are wrapped in
+/// parentheses where needed.
+///
+/// # Example
+/// ```
+/// use graph_rs::url::{Filter, Literal};
+///
+/// let filter = Filter::eq("displayName", "O'Neil")
+///     .and(Filter::starts_with("mail", "a").or(Filter::eq("accountEnabled", false)))
+///     .and(Filter::any("assignedLicenses", "l", Filter::ne("l/skuId", Literal::Null)));
+///
+/// assert_eq!(
+///     "displayName eq 'O''Neil' and (startswith(mail,'a') or accountEnabled eq false) \
+///      and assignedLicenses/any(l:l/skuId ne null)",
+///     filter.to_string()
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    Compare {
+        property: String,
+        operator: &'static str,
+        value: Literal,
+    },
+    Function {
+        name: &'static str,
+        property: String,
+        value: Literal,
+    },
+    Lambda {
+        collection: String,
+        operator: &'static str,
+        variable: String,
+        filter: Box<Filter>,
+    },
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+    /// An expression that is used as is.
+    Raw(String),
+}
+
+impl Filter {
+    fn compare<V: Into<Literal>>(property: &str, operator: &'static str, value: V) -> Filter {
+        Filter::Compare {
+            property: property.to_string(),
+            operator,
+            value: value.into(),
+        }
+    }
+
+    fn lambda(collection: &str, operator: &'static str, variable: &str, filter: Filter) -> Filter {
+        Filter::Lambda {
+            collection: collection.to_string(),
+            operator,
+            variable: variable.to_string(),
+            filter: Box::new(filter),
+        }
+    }
+
+    pub fn eq<V: Into<Literal>>(property: &str, value: V) -> Filter {
+        Filter::compare(property, "eq", value)
+    }
+
+    pub fn ne<V: Into<Literal>>(property: &str, value: V) -> Filter {
+        Filter::compare(property, "ne", value)
+    }
+
+    pub fn gt<V: Into<Literal>>(property: &str, value: V) -> Filter {
+        Filter::compare(property, "gt", value)
+    }
+
+    pub fn ge<V: Into<Literal>>(property: &str, value: V) -> Filter {
+        Filter::compare(property, "ge", value)
+    }
+
+    pub fn lt<V: Into<Literal>>(property: &str, value: V) -> Filter {
+        Filter::compare(property, "lt", value)
+    }
+
+    pub fn le<V: Into<Literal>>(property: &str, value: V) -> Filter {
+        Filter::compare(property, "le", value)
+    }
+
+    pub fn starts_with(property: &str, value: &str) -> Filter {
+        Filter::Function {
+            name: "startswith",
+            property: property.to_string(),
+            value: Literal::from(value),
+        }
+    }
+
+    pub fn ends_with(property: &str, value: &str) -> Filter {
+        Filter::Function {
+            name: "endswith",
+            property: property.to_string(),
+            value: Literal::from(value),
+        }
+    }
+
+    /// True if any item in the collection matches the filter. The filter
+    /// refers to the item using the variable, such as `Filter::eq("m/id", id)`.
+    pub fn any(collection: &str, variable: &str, filter: Filter) -> Filter {
+        Filter::lambda(collection, "any", variable, filter)
+    }
+
+    /// True if all items in the collection match the filter.
+    pub fn all(collection: &str, variable: &str, filter: Filter) -> Filter {
+        Filter::lambda(collection, "all", variable, filter)
+    }
+
+    pub fn raw(expression: &str) -> Filter {
+        Filter::Raw(expression.to_string())
+    }
+
+    pub fn and(self, other: Filter) -> Filter {
+        Filter::And(Box::new(self), Box::new(other))
+    }
+
+    pub fn or(self, other: Filter) -> Filter {
+        Filter::Or(Box::new(self), Box::new(other))
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Filter {
+        Filter::Not(Box::new(self))
+    }
+
+    // Writes the operand of an and/or expression, using parentheses
+    // when the operand is a different kind of combined expression.
+    fn fmt_operand(&self, f: &mut fmt::Formatter<'_>, parent_is_and: bool) -> fmt::Result {
+        match self {
+            Filter::Or(..) if parent_is_and => write!(f, "({})", self),
+            Filter::And(..) if !parent_is_and => write!(f, "({})", self),
+            Filter::Raw(_) => write!(f, "({})", self),
+            _ => write!(f, "{}", self),
+        }
+    }
+}
+
+impl fmt::Display for Filter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Filter::Compare {
+                property,
+                operator,
+                value,
+            } => write!(f, "{} {} {}", property, operator, value),
+            Filter::Function {
+                name,
+                property,
+                value,
+            } => write!(f, "{}({},{})", name, property, value),
+            Filter::Lambda {
+                collection,
+                operator,
+                variable,
+                filter,
+            } => write!(f, "{}/{}({}:{})", collection, operator, variable, filter),
+            Filter::And(left, right) => {
+                left.fmt_operand(f, true)?;
+                write!(f, " and ")?;
+                right.fmt_operand(f, true)
+            },
+            Filter::Or(left, right) => {
+                left.fmt_operand(f, false)?;
+                write!(f, " or ")?;
+                right.fmt_operand(f, false)
+            },
+            Filter::Not(filter) => write!(f, "not ({})", filter),
+            Filter::Raw(expression) => write!(f, "{}", expression),
+        }
+    }
+}
@@ -1,9 +1,10 @@
+use crate::url::{Expand, Filter};
 use graph_error::GraphFailure;
 use std::ffi::OsStr;
 use std::iter::Iterator;
 use std::ops::{Deref, Index, Range, RangeFrom, RangeFull, RangeTo};
 use std::str::FromStr;
-use url::form_urlencoded::Serializer;
+use url::form_urlencoded::{byte_serialize, Serializer};
 use url::{PathSegmentsMut, Position, Url, UrlQuery};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -90,44 +91,86 @@ impl GraphUrl {
         Ok(())
     }
 
+    /// Append an OData system query option such as `$select`. The key
+    /// is written as is so the `$` prefix is not percent encoded.
+    pub fn append_query_option(&mut self, key: &str, value: &str) {
+        let value: String = byte_serialize(value.as_bytes()).collect();
+        let query = match self.url.query() {
+            Some(query) if !query.is_empty() => format!("{}&{}={}", query, key, value),
+            _ => format!("{}={}", key, value),
+        };
+        self.url.set_query(Some(query.as_str()));
+    }
+
+    #[deprecated(note = "use count_items which takes a bool")]
     pub fn count(&mut self, value: &str) {
-        self.append_query_pair("count", value);
+        self.append_query_option("$count", value);
+    }
+
+    /// Add the `$count` query option.
+    pub fn count_items(&mut self, value: bool) {
+        self.append_query_option("$count", &value.to_string());
     }
 
     pub fn select(&mut self, value: &[&str]) {
         let s = value.join(",");
-        self.append_query_pair("select", &s);
+        self.append_query_option("$select", &s);
     }
 
     pub fn expand(&mut self, value: &[&str]) {
-        let s = value.join(" ");
-        self.append_query_pair("expand", &s);
+        let s = value.join(",");
+        self.append_query_option("$expand", &s);
+    }
+
+    /// Expand properties using nested query options such as
+    /// `children($select=id)`.
+    pub fn expand_with(&mut self, value: &[Expand]) {
+        let expand: Vec<String> = value.iter().map(|e| e.to_string()).collect();
+        self.append_query_option("$expand", &expand.join(","));
     }
 
     pub fn filter(&mut self, value: &[&str]) {
         let s = value.join(",");
-        self.append_query_pair("filter", &s);
+        self.append_query_option("$filter", &s);
+    }
+
+    pub fn filter_by(&mut self, filter: &Filter) {
+        self.append_query_option("$filter", &filter.to_string());
     }
 
     pub fn order_by(&mut self, value: &[&str]) {
-        let s = value.join(" ");
-        self.append_query_pair("orderby", &s);
+        let s = value.join(",");
+        self.append_query_option("$orderby", &s);
     }
 
+    /// Search using `$search`. The value is wrapped in double quotes
+    /// unless it already starts and ends with them, such as
+    /// `"displayName:a" OR "mail:a"`.
     pub fn search(&mut self, value: &str) {
-        self.append_query_pair("search", value);
+        if value.len() > 1 && value.starts_with('"') && value.ends_with('"') {
+            self.append_query_option("$search", value);
+        } else {
+            let quoted = format!("\"{}\"", value.replace('"', "\\\""));
+            self.append_query_option("$search", &quoted);
+        }
     }
 
+    /// The format of a downloaded file, such as `pdf`. This is the
+    /// format parameter of the download url and not `$format`.
     pub fn format(&mut self, value: &str) {
         self.append_query_pair("format", value);
     }
 
     pub fn skip(&mut self, value: &str) {
-        self.append_query_pair("skip", value.as_ref());
+        self.append_query_option("$skip", value);
+    }
+
+    pub fn skip_token(&mut self, value: &str) {
+        self.append_query_option("$skiptoken", value);
     }
 
     pub fn top(&mut self, value: &str) {
-        self.append_query_pair("top", value.as_ref());
+        self.append_query_option("$top", value);
     }
 }
 
@@ -1,3 +1,7 @@
+mod expand;
+mod filter;
 mod graphurl;
 
+pub use expand::Expand;
+pub use filter::{Filter, Literal};
 pub use graphurl::GraphUrl;
@@ -99,14 +99,14 @@ impl ToString for SpecialFolder {
     }
 }
 
-pub fn assert_url_special(client: &Graph, endpoint: SpecialFolder) {
+pub fn assert_url_special(client: &Graph, ident: Ident, endpoint: SpecialFolder) {
     client.url_ref(|url| {
-        if client.ident().eq(&Ident::Me) {
+        if ident.eq(&Ident::Me) {
             assert_eq!(
                 format!("{}/me/drive/{}", GRAPH_URL, endpoint.as_str()),
                 url.to_string(),
             )
-        } else if client.ident().eq(&Ident::Drives) {
+        } else if ident.eq(&Ident::Drives) {
             assert_eq!(
                 format!("{}/{}", GRAPH_URL, endpoint.as_str()),
                 url.to_string()
@@ -120,19 +120,19 @@ pub fn assert_url_special(client: &Graph, endpoint: SpecialFolder) {
     })
 }
 
-pub fn assert_url_id_equals(client: &Graph, item_id: &str, endpoint: SpecialFolder) {
+pub fn assert_url_id_equals(client: &Graph, ident: Ident, item_id: &str, endpoint: SpecialFolder) {
     client.url_ref(|url| {
-        if client.ident().eq(&Ident::Me) {
+        if ident.eq(&Ident::Me) {
             assert_eq!(
                 format!("{}/me/drive/{}", GRAPH_URL, endpoint.as_str()),
                 url.to_string(),
             )
-        } else if client.ident().eq(&Ident::Drives) {
+        } else if ident.eq(&Ident::Drives) {
             assert_eq!(
                 format!(
                     "{}/{}/{}/{}",
                     GRAPH_URL,
-                    client.ident().as_ref(),
+                    ident.as_ref(),
                     item_id,
                     endpoint.as_str()
                 ),
@@ -143,7 +143,7 @@ pub fn assert_url_id_equals(client: &Graph, item_id: &str, endpoint: SpecialFold
                 format!(
                     "{}/{}/{}/drive/{}",
                     GRAPH_URL,
-                    client.ident().as_ref(),
+                    ident.as_ref(),
                     item_id,
                     endpoint.as_str()
                 ),
@@ -153,9 +153,9 @@ pub fn assert_url_id_equals(client: &Graph, item_id: &str, endpoint: SpecialFold
     })
 }
 
-pub fn assert_url_special_beta(client: &Graph, endpoint: SpecialFolder) {
+pub fn assert_url_special_beta(client: &Graph, ident: Ident, endpoint: SpecialFolder) {
     client.url_ref(|url| {
-        if client.ident().eq(&Ident::Me) {
+        if ident.eq(&Ident::Me) {
             if endpoint.eq(&SpecialFolder::Drive) {
                 assert_eq!(
                     format!("{}/me/{}", GRAPH_URL_BETA, endpoint.as_str()),
@@ -181,19 +181,24 @@ pub fn assert_url_special_beta(client: &Graph, endpoint: SpecialFolder) {
     })
 }
 
-pub fn assert_url_id_equals_beta(client: &Graph, item_id: &str, endpoint: SpecialFolder) {
+pub fn assert_url_id_equals_beta(
+    client: &Graph,
+    ident: Ident,
+    item_id: &str,
+    endpoint: SpecialFolder,
+) {
     client.url_ref(|url| {
-        if client.ident().eq(&Ident::Me) {
+        if ident.eq(&Ident::Me) {
             assert_eq!(
                 format!("{}/me/drive/{}", GRAPH_URL_BETA, endpoint.as_str()),
                 url.to_string(),
             )
-        } else if client.ident().eq(&Ident::Drives) {
+        } else if ident.eq(&Ident::Drives) {
             assert_eq!(
                 format!(
                     "{}/{}/{}/{}",
                     GRAPH_URL_BETA,
-                    client.ident().as_ref(),
+                    ident.as_ref(),
                     item_id,
                     endpoint.as_str()
                 ),
@@ -204,7 +209,7 @@ pub fn assert_url_id_equals_beta(client: &Graph, item_id: &str, endpoint: Specia
                 format!(
                     "{}/{}/{}/drive/{}",
                     GRAPH_URL_BETA,
-                    client.ident().as_ref(),
+                    ident.as_ref(),
                     item_id,
                     endpoint.as_str()
                 ),
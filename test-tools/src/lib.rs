@@ -11,6 +11,7 @@ pub mod drive;
 pub mod oauth;
 pub mod oauthrequest;
 pub mod support;
+pub mod transport;
 
 use graph_rs::client::Graph;
 use graph_rs::{GRAPH_URL, GRAPH_URL_BETA};
@@ -0,0 +1,97 @@
+use graph_error::{GraphFailure, GraphResult};
+use graph_rs::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use graph_rs::http::{GraphRequestBuilder, HttpResponse, HttpTransport, RequestBody};
+use reqwest::{Method, StatusCode};
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// A request sent through a `MockTransport`.
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    pub method: Method,
+    pub url: String,
+    pub headers: HeaderMap,
+    pub body: Option<RequestBody>,
+    pub timeout: Option<Duration>,
+}
+
+impl MockRequest {
+    pub fn bearer(&self) -> Option<&str> {
+        self.headers.get(AUTHORIZATION)?.to_str().ok()
+    }
+}
+
+/// A canned response returned by a `MockTransport`.
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+}
+
+impl MockResponse {
+    pub fn json(status: StatusCode, value: serde_json::Value) -> MockResponse {
+        MockResponse {
+            status,
+            headers: HeaderMap::new(),
+            body: serde_json::to_vec(&value).unwrap(),
+        }
+    }
+
+    pub fn header(mut self, name: &'static str, value: &str) -> MockResponse {
+        self.headers.insert(
+            HeaderName::from_static(name),
+            HeaderValue::from_str(value).unwrap(),
+        );
+        self
+    }
+}
+
+/// A transport that records each request and returns the given
+/// responses in order.
+#[derive(Debug, Clone, Default)]
+pub struct MockTransport {
+    requests: Arc<Mutex<Vec<MockRequest>>>,
+    responses: Arc<Mutex<Vec<MockResponse>>>,
+}
+
+impl MockTransport {
+    pub fn new(mut responses: Vec<MockResponse>) -> MockTransport {
+        responses.reverse();
+        MockTransport {
+            requests: Default::default(),
+            responses: Arc::new(Mutex::new(responses)),
+        }
+    }
+
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl HttpTransport for MockTransport {
+    fn send(&self, request: GraphRequestBuilder) -> GraphResult<HttpResponse> {
+        let url = request.to_url();
+        self.requests.lock().unwrap().push(MockRequest {
+            method: request.method.clone(),
+            url: url.to_string(),
+            headers: request.headers.clone(),
+            body: request.body.clone(),
+            timeout: request.timeout,
+        });
+
+        let response = self
+            .responses
+            .lock()
+            .unwrap()
+            .pop()
+            .ok_or_else(|| GraphFailure::invalid("mock response"))?;
+        Ok(HttpResponse::new(
+            response.status,
+            url,
+            response.headers,
+            Cursor::new(response.body),
+        ))
+    }
+}
@@ -0,0 +1,156 @@
+use graph_error::{GraphFailure, GraphRsError};
+use graph_rs::prelude::*;
+use reqwest::StatusCode;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use test_tools::transport::{MockResponse, MockTransport};
+
+static MONITOR_URL: &str =
+    "https://api.onedrive.com/v1.0/monitor/4A3407B5-88FC-4504-8B21-0AABD3412717";
+
+fn accepted() -> MockResponse {
+    MockResponse::json(StatusCode::ACCEPTED, serde_json::json!({})).header("location", MONITOR_URL)
+}
+
+fn status(status: &str, percentage: f64) -> MockResponse {
+    MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({
+            "operation": "itemCopy",
+            "percentageComplete": percentage,
+            "status": status,
+        }),
+    )
+}
+
+fn completed() -> MockResponse {
+    MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({
+            "operation": "itemCopy",
+            "percentageComplete": 100.0,
+            "resourceId": "NEW_ITEM_ID",
+            "status": "completed",
+        }),
+    )
+}
+
+#[test]
+fn wait_for_resource() {
+    let transport = MockTransport::new(vec![
+        accepted(),
+        status("inProgress", 25.0),
+        status("inProgress", 75.0),
+        completed(),
+        MockResponse::json(StatusCode::OK, serde_json::json!({ "id": "NEW_ITEM_ID" })),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    let response = client
+        .v1()
+        .me()
+        .drive()
+        .copy("ITEM_ID", &serde_json::json!({ "name": "copy.txt" }))
+        .send()
+        .unwrap();
+
+    let progress = Arc::new(Mutex::new(Vec::new()));
+    let on_progress = Arc::clone(&progress);
+    let mut poller = response
+        .async_job(&client)
+        .unwrap()
+        .delay(Duration::from_millis(1), Duration::from_millis(2))
+        .on_progress(move |status| {
+            on_progress
+                .lock()
+                .unwrap()
+                .push(status.percentage_complete().unwrap())
+        });
+    let item: GraphResponse<serde_json::Value> = poller.wait_for_resource().unwrap();
+    assert_eq!("NEW_ITEM_ID", item.value()["id"].as_str().unwrap());
+    assert_eq!(vec![25.0, 75.0, 100.0], *progress.lock().unwrap());
+
+    let requests = transport.requests();
+    assert_eq!(5, requests.len());
+    assert_eq!(MONITOR_URL, requests[1].url);
+    assert_eq!(None, requests[1].bearer());
+    assert_eq!(
+        "https://graph.microsoft.com/v1.0/me/drive/items/NEW_ITEM_ID",
+        requests[4].url
+    );
+    assert_eq!(Some("Bearer ACCESS_TOKEN"), requests[4].bearer());
+}
+
+#[test]
+fn failed_job() {
+    let transport = MockTransport::new(vec![accepted(), status("failed", 10.0)]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport);
+
+    let response = client
+        .v1()
+        .me()
+        .drive()
+        .copy("ITEM_ID", &serde_json::json!({}))
+        .send()
+        .unwrap();
+    match response.async_job(&client).unwrap().wait() {
+        Err(GraphFailure::GraphRsError(GraphRsError::AsyncJobFailed { .. })) => {},
+        other => panic!("Expected AsyncJobFailed. Got: {:#?}", other),
+    }
+}
+
+#[test]
+fn timeout() {
+    let transport = MockTransport::new(vec![
+        accepted(),
+        status("inProgress", 10.0),
+        status("inProgress", 10.0),
+        status("inProgress", 10.0),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport);
+
+    let response = client
+        .v1()
+        .me()
+        .drive()
+        .copy("ITEM_ID", &serde_json::json!({}))
+        .send()
+        .unwrap();
+    let mut poller = response
+        .async_job(&client)
+        .unwrap()
+        .delay(Duration::from_millis(20), Duration::from_millis(20))
+        .timeout(Duration::from_millis(30));
+    match poller.wait() {
+        Err(GraphFailure::GraphRsError(GraphRsError::AsyncJobTimedOut { .. })) => {},
+        other => panic!("Expected AsyncJobTimedOut. Got: {:#?}", other),
+    }
+}
+
+#[test]
+fn cancel() {
+    let transport = MockTransport::new(vec![accepted(), status("inProgress", 10.0)]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    let response = client
+        .v1()
+        .me()
+        .drive()
+        .copy("ITEM_ID", &serde_json::json!({}))
+        .send()
+        .unwrap();
+    let mut poller = response
+        .async_job(&client)
+        .unwrap()
+        .delay(Duration::from_secs(60), Duration::from_secs(60));
+    let handle = poller.cancel_handle();
+    std::thread::spawn(move || {
+        std::thread::sleep(Duration::from_millis(20));
+        handle.cancel();
+    });
+    match poller.wait() {
+        Err(GraphFailure::GraphRsError(GraphRsError::AsyncJobCancelled)) => {},
+        other => panic!("Expected AsyncJobCancelled. Got: {:#?}", other),
+    }
+    assert_eq!(2, transport.requests().len());
+}
@@ -0,0 +1,270 @@
+use graph_rs::http::RequestBody;
+use graph_rs::oauth::AzureCloud;
+use graph_rs::prelude::*;
+use graph_rs::types::batch::{BatchRequest, BatchStep};
+use reqwest::{Method, StatusCode};
+use serde_derive::Deserialize;
+use test_tools::transport::{MockResponse, MockTransport};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Drive {
+    id: String,
+}
+
+fn sent_ids(body: &Option<RequestBody>) -> Vec<String> {
+    let value: serde_json::Value = match body {
+        Some(RequestBody::Text(text)) => serde_json::from_str(text).unwrap(),
+        _ => panic!("expected a json body"),
+    };
+    value["requests"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|step| step["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+fn ok_responses(ids: &[String]) -> MockResponse {
+    let responses: Vec<serde_json::Value> = ids
+        .iter()
+        .rev()
+        .map(|id| serde_json::json!({ "id": id, "status": 200, "body": { "id": id } }))
+        .collect();
+    MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({ "responses": responses }),
+    )
+}
+
+#[test]
+fn batch_step_json() {
+    let step = BatchStep::post(
+        "2",
+        "me/drive/root/children",
+        serde_json::json!({ "name": "folder" }),
+    )
+    .depends_on("1");
+    assert_eq!(
+        serde_json::json!({
+            "id": "2",
+            "method": "POST",
+            "url": "/me/drive/root/children",
+            "headers": { "Content-Type": "application/json" },
+            "body": { "name": "folder" },
+            "dependsOn": ["1"]
+        }),
+        serde_json::to_value(&step).unwrap()
+    );
+
+    let step = BatchStep::get("1", "/me/drive");
+    assert_eq!(
+        serde_json::json!({ "id": "1", "method": "GET", "url": "/me/drive" }),
+        serde_json::to_value(&step).unwrap()
+    );
+}
+
+#[test]
+fn batch_chunks_keep_dependencies_together() {
+    let mut batch = BatchRequest::new();
+    for i in 0..19 {
+        batch.add(BatchStep::get(&i.to_string(), "/me"));
+    }
+    batch.add(BatchStep::get("a", "/me/drive"));
+    batch.add(BatchStep::get("b", "/me/drive/root").depends_on("a"));
+
+    let chunks = batch.chunks().unwrap();
+    assert_eq!(2, chunks.len());
+    assert_eq!(19, chunks[0].len());
+    assert_eq!("a", chunks[1].steps()[0].id());
+    assert_eq!("b", chunks[1].steps()[1].id());
+}
+
+#[test]
+fn batch_chunks_errors() {
+    let mut batch = BatchRequest::new();
+    batch.add(BatchStep::get("1", "/me"));
+    batch.add(BatchStep::get("1", "/me/drive"));
+    assert!(batch.chunks().is_err());
+
+    let mut batch = BatchRequest::new();
+    batch.add(BatchStep::get("1", "/me").depends_on("2"));
+    assert!(batch.chunks().is_err());
+
+    let mut batch = BatchRequest::new();
+    batch.add(BatchStep::get("0", "/me"));
+    for i in 1..21 {
+        batch.add(BatchStep::get(&i.to_string(), "/me").depends_on(&(i - 1).to_string()));
+    }
+    assert!(batch.chunks().is_err());
+}
+
+#[test]
+fn send_batch_in_chunks() {
+    let mut batch = BatchRequest::new();
+    for i in 0..25 {
+        batch.add(BatchStep::get(&i.to_string(), "/me/drive"));
+    }
+    let first: Vec<String> = (0..20).map(|i| i.to_string()).collect();
+    let second: Vec<String> = (20..25).map(|i| i.to_string()).collect();
+    let transport = MockTransport::new(vec![ok_responses(&first), ok_responses(&second)]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    let response = client.v1().send_batch(&batch).unwrap();
+    assert_eq!(25, response.responses().len());
+    assert_eq!("0", response.responses()[0].id());
+    assert_eq!("24", response.responses()[24].id());
+
+    let drive: Drive = response.json("7").unwrap();
+    assert_eq!(
+        Drive {
+            id: "7".to_string()
+        },
+        drive
+    );
+
+    let requests = transport.requests();
+    assert_eq!(2, requests.len());
+    assert_eq!("https://graph.microsoft.com/v1.0/$batch", requests[0].url);
+    assert_eq!(Method::POST, requests[0].method);
+    assert_eq!(first, sent_ids(&requests[0].body));
+    assert_eq!(second, sent_ids(&requests[1].body));
+}
+
+#[test]
+fn batch_step_errors() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({
+            "responses": [
+                { "id": "1", "status": 200, "body": { "id": "drive" } },
+                {
+                    "id": "2",
+                    "status": 404,
+                    "body": { "error": { "code": "itemNotFound", "message": "Not found" } }
+                }
+            ]
+        }),
+    )]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport);
+    let mut batch = BatchRequest::new();
+    batch
+        .add(BatchStep::get("1", "/me/drive"))
+        .add(BatchStep::get("2", "/me/drive/items/missing"));
+
+    let response = client.v1().send_batch(&batch).unwrap();
+    assert_eq!(vec!["2"], response.failed());
+    assert!(response.get("1").unwrap().is_success());
+    assert_eq!(&404, response.get("2").unwrap().status());
+    assert!(response.json::<Drive>("2").is_err());
+    assert!(response.json::<Drive>("3").is_err());
+}
+
+#[test]
+fn batch_steps_from_request_chains() {
+    let client = Graph::new("ACCESS_TOKEN");
+    let mut batch = BatchRequest::new();
+    let item = batch
+        .add_request(
+            "1",
+            &client
+                .v1()
+                .me()
+                .drive()
+                .get_item("ITEM_ID")
+                .select(&["name"]),
+        )
+        .unwrap();
+    let children = batch
+        .add_request("2", &client.v1().me().drive().root_children())
+        .unwrap();
+    let deleted = batch
+        .add_request("3", &client.v1().me().drive().delete("OLD_ID"))
+        .unwrap();
+    assert_eq!("1", item.id());
+
+    let steps = batch.steps();
+    assert_eq!("/me/drive/items/ITEM_ID?$select=name", steps[0].url());
+    assert_eq!("GET", steps[0].method());
+    assert!(steps[0]
+        .headers()
+        .keys()
+        .all(|name| name != "authorization"));
+    assert_eq!("/me/drive/root/children", steps[1].url());
+    assert_eq!("DELETE", steps[2].method());
+
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({
+            "responses": [
+                { "id": "3", "status": 204 },
+                { "id": "2", "status": 200, "body": { "value": [{ "name": "a" }] } },
+                { "id": "1", "status": 200, "body": { "name": "item" } }
+            ]
+        }),
+    )]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport);
+    let response = client.v1().send_batch(&batch).unwrap();
+
+    let item: GraphResponse<DriveItem> = response.response(&item).unwrap();
+    assert_eq!(Some("item"), item.value().name().as_deref());
+    assert_eq!(
+        "https://graph.microsoft.com/v1.0/me/drive/items/ITEM_ID?$select=name",
+        item.url().as_str()
+    );
+    let children: GraphResponse<Collection<DriveItem>> = response.response(&children).unwrap();
+    assert_eq!(1, children.value().value().unwrap().len());
+    let deleted = response.response(&deleted).unwrap();
+    assert_eq!(StatusCode::NO_CONTENT, deleted.status());
+}
+
+#[test]
+fn batch_steps_from_custom_cloud_with_path() {
+    let client = GraphClientBuilder::new()
+        .cloud(AzureCloud::custom(
+            "http://localhost:8000/graph",
+            "http://localhost:8001",
+        ))
+        .build("ACCESS_TOKEN")
+        .unwrap();
+    let mut batch = BatchRequest::new();
+    batch
+        .add_request("1", &client.v1().me().drive().root_children())
+        .unwrap();
+    batch
+        .add_request("2", &client.beta().me().drive().get_item("ITEM_ID"))
+        .unwrap();
+
+    let steps = batch.steps();
+    assert_eq!("/me/drive/root/children", steps[0].url());
+    assert_eq!("/me/drive/items/ITEM_ID", steps[1].url());
+}
+
+#[test]
+fn batch_steps_without_body_have_no_content_type() {
+    let client = Graph::new("ACCESS_TOKEN");
+    let mut batch = BatchRequest::new();
+    batch
+        .add_request("1", &client.v1().me().drive().root_children())
+        .unwrap();
+    batch
+        .add_request(
+            "2",
+            &client
+                .v1()
+                .me()
+                .drive()
+                .create_folder("", &serde_json::json!({ "name": "docs", "folder": {} })),
+        )
+        .unwrap();
+
+    let steps = batch.steps();
+    assert!(steps[0]
+        .headers()
+        .keys()
+        .all(|name| !name.eq_ignore_ascii_case("content-type")));
+    assert!(steps[1].body().is_some());
+    assert!(steps[1]
+        .headers()
+        .keys()
+        .any(|name| name.eq_ignore_ascii_case("content-type")));
+}
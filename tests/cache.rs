@@ -0,0 +1,192 @@
+use graph_rs::http::{CacheStorage, ResponseCache};
+use graph_rs::prelude::*;
+use reqwest::header::{CACHE_CONTROL, IF_NONE_MATCH};
+use reqwest::StatusCode;
+use std::time::Duration;
+use test_tools::transport::{MockResponse, MockTransport};
+
+static ETAG: &str = "\"{ITEM},1\"";
+
+fn item() -> MockResponse {
+    MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({ "id": "ITEM_ID", "name": "item" }),
+    )
+    .header("etag", ETAG)
+}
+
+fn not_modified() -> MockResponse {
+    MockResponse::json(StatusCode::NOT_MODIFIED, serde_json::json!({}))
+}
+
+#[test]
+fn cache_revalidates_with_etag() {
+    let transport = MockTransport::new(vec![item(), not_modified()]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.add_middleware(ResponseCache::in_memory(10));
+
+    let first = client.v1().me().drive().get_item("ITEM_ID").send().unwrap();
+    let second = client.v1().me().drive().get_item("ITEM_ID").send().unwrap();
+    assert_eq!(first.value(), second.value());
+    assert_eq!(StatusCode::OK, second.status());
+
+    let requests = transport.requests();
+    assert_eq!(2, requests.len());
+    assert!(requests[0].headers.get(IF_NONE_MATCH).is_none());
+    assert_eq!(ETAG, requests[1].headers.get(IF_NONE_MATCH).unwrap());
+}
+
+#[test]
+fn cache_bypass_and_writes() {
+    let transport = MockTransport::new(vec![
+        item(),
+        item(),
+        MockResponse::json(StatusCode::OK, serde_json::json!({})),
+        not_modified(),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.add_middleware(ResponseCache::in_memory(10));
+
+    client.v1().me().drive().get_item("ITEM_ID").send().unwrap();
+    client
+        .v1()
+        .me()
+        .drive()
+        .get_item("ITEM_ID")
+        .bypass_cache()
+        .send()
+        .unwrap();
+    client
+        .v1()
+        .me()
+        .drive()
+        .update("ITEM_ID", &serde_json::json!({ "name": "name" }))
+        .send()
+        .unwrap();
+    client.v1().me().drive().get_item("ITEM_ID").send().unwrap();
+
+    let requests = transport.requests();
+    assert!(requests[1].headers.get(IF_NONE_MATCH).is_none());
+    assert!(requests[1].headers.get(CACHE_CONTROL).is_none());
+    assert!(requests[2].headers.get(IF_NONE_MATCH).is_none());
+    assert_eq!(ETAG, requests[3].headers.get(IF_NONE_MATCH).unwrap());
+}
+
+#[test]
+fn cache_ttl_and_size_limits() {
+    let transport = MockTransport::new(vec![item(), item()]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.add_middleware(ResponseCache::in_memory(10).ttl(Duration::from_secs(0)));
+    client.v1().me().drive().get_item("ITEM_ID").send().unwrap();
+    client.v1().me().drive().get_item("ITEM_ID").send().unwrap();
+    assert!(transport.requests()[1].headers.get(IF_NONE_MATCH).is_none());
+
+    let transport = MockTransport::new(vec![item(), item()]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.add_middleware(ResponseCache::in_memory(10).max_body_size(8));
+    client.v1().me().drive().get_item("ITEM_ID").send().unwrap();
+    client.v1().me().drive().get_item("ITEM_ID").send().unwrap();
+    assert!(transport.requests()[1].headers.get(IF_NONE_MATCH).is_none());
+
+    let transport = MockTransport::new(vec![item(), item(), item(), item()]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.add_middleware(ResponseCache::in_memory(1));
+    client.v1().me().drive().get_item("ONE").send().unwrap();
+    client.v1().me().drive().get_item("TWO").send().unwrap();
+    client.v1().me().drive().get_item("ONE").send().unwrap();
+    client.v1().me().drive().get_item("TWO").send().unwrap();
+    let requests = transport.requests();
+    assert!(requests[2].headers.get(IF_NONE_MATCH).is_none());
+    assert!(requests[3].headers.get(IF_NONE_MATCH).is_none());
+}
+
+#[test]
+fn cache_on_disk() {
+    let dir = std::env::temp_dir().join("graph-rs-response-cache");
+    let cache = ResponseCache::on_disk(&dir);
+    cache.storage().clear();
+
+    let transport = MockTransport::new(vec![item(), not_modified()]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.add_middleware(cache);
+    client.v1().me().drive().get_item("ITEM_ID").send().unwrap();
+
+    // A new client using the same directory revalidates the saved response.
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.add_middleware(ResponseCache::on_disk(&dir));
+    let response = client.v1().me().drive().get_item("ITEM_ID").send().unwrap();
+    assert_eq!(Some("item"), response.value().name().as_deref());
+    assert_eq!(
+        ETAG,
+        transport.requests()[1].headers.get(IF_NONE_MATCH).unwrap()
+    );
+
+    ResponseCache::on_disk(&dir).storage().clear();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn cache_skips_redirects() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::FOUND, serde_json::json!({}))
+            .header(
+                "location",
+                "https://download.example.com/file?tempauth=secret",
+            )
+            .header("etag", ETAG),
+        item(),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.add_middleware(ResponseCache::in_memory(10));
+
+    let _ = client.v1().me().drive().get_item("ITEM_ID").send();
+    client.v1().me().drive().get_item("ITEM_ID").send().unwrap();
+    assert!(transport.requests()[1].headers.get(IF_NONE_MATCH).is_none());
+}
+
+#[test]
+fn cache_keys_by_headers_and_token() {
+    let transport = MockTransport::new(vec![item(), item(), not_modified()]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.add_middleware(ResponseCache::in_memory(10));
+
+    client.v1().me().drive().get_item("ITEM_ID").send().unwrap();
+    client
+        .v1()
+        .me()
+        .drive()
+        .get_item("ITEM_ID")
+        .prefer(Preference::BodyContentType(BodyContentType::Html))
+        .send()
+        .unwrap();
+    client.v1().me().drive().get_item("ITEM_ID").send().unwrap();
+
+    let requests = transport.requests();
+    assert!(requests[1].headers.get(IF_NONE_MATCH).is_none());
+    assert_eq!(ETAG, requests[2].headers.get(IF_NONE_MATCH).unwrap());
+
+    // Clients with different tokens sharing a directory do not
+    // revalidate each other's responses.
+    let dir = std::env::temp_dir().join("graph-rs-response-cache-token");
+    ResponseCache::on_disk(&dir).storage().clear();
+
+    let transport = MockTransport::new(vec![item(), item()]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.add_middleware(ResponseCache::on_disk(&dir));
+    client.v1().me().drive().get_item("ITEM_ID").send().unwrap();
+
+    let client = Graph::with_transport("OTHER_ACCESS_TOKEN", transport.clone());
+    client.add_middleware(ResponseCache::on_disk(&dir));
+    client.v1().me().drive().get_item("ITEM_ID").send().unwrap();
+    assert!(transport.requests()[1].headers.get(IF_NONE_MATCH).is_none());
+
+    let saved = std::fs::read_dir(&dir)
+        .unwrap()
+        .filter_map(Result::ok)
+        .map(|entry| std::fs::read_to_string(entry.path()).unwrap())
+        .collect::<Vec<String>>();
+    assert_eq!(2, saved.len());
+    assert!(saved.iter().all(|entry| !entry.contains("ACCESS_TOKEN")));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
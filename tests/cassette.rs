@@ -0,0 +1,91 @@
+use graph_rs::http::{CassetteMode, CassetteTransport};
+use graph_rs::prelude::*;
+use reqwest::StatusCode;
+use std::fs;
+use std::path::PathBuf;
+use test_tools::transport::{MockResponse, MockTransport};
+
+static ID: &str = "b!CbtYWrofwUGBJWnaJkNwoNrBLp_kC3RKklSXPwrdeP3yH8_qmH9xT5Y6RODPNfYI";
+
+fn cassette_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("graph-rs-cassette-{}.json", name))
+}
+
+fn record(path: &PathBuf) {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(
+            StatusCode::OK,
+            serde_json::json!({ "id": ID, "name": "file.txt" }),
+        ),
+        MockResponse::json(StatusCode::OK, serde_json::json!({ "value": [] })),
+    ]);
+    let mut cassette = CassetteTransport::record(path, transport.clone());
+    cassette.scrub(ID);
+    let client = Graph::with_transport("ACCESS_TOKEN", cassette);
+
+    let response = client.v1().me().drive().get_item(ID).send().unwrap();
+    assert_eq!(Some(ID), response.value().id().as_deref());
+    client
+        .v1()
+        .me()
+        .drive()
+        .root_children()
+        .select(&["id", "name"])
+        .top("5")
+        .send()
+        .unwrap();
+    assert_eq!(2, transport.requests().len());
+}
+
+#[test]
+fn record_scrubs_secrets() {
+    let path = cassette_path("scrub");
+    record(&path);
+
+    let text = fs::read_to_string(&path).unwrap();
+    assert!(!text.contains("ACCESS_TOKEN"));
+    assert!(!text.contains(ID));
+    assert!(text.contains("[REDACTED]"));
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn replay_recorded_requests() {
+    let path = cassette_path("replay");
+    record(&path);
+
+    let mut cassette = CassetteTransport::replay(&path).unwrap();
+    cassette.scrub(ID);
+    assert_eq!(CassetteMode::Replay, cassette.mode());
+    assert_eq!(2, cassette.cassette().interactions().len());
+    let client = Graph::with_transport("OTHER_TOKEN", cassette);
+
+    // The query pairs can be in a different order.
+    client
+        .v1()
+        .me()
+        .drive()
+        .root_children()
+        .top("5")
+        .select(&["id", "name"])
+        .send()
+        .unwrap();
+    let response = client.v1().me().drive().get_item(ID).send().unwrap();
+    assert_eq!(Some("[REDACTED]"), response.value().id().as_deref());
+    assert_eq!(Some("file.txt"), response.value().name().as_deref());
+
+    // Each interaction is only replayed once.
+    assert!(client.v1().me().drive().get_item(ID).send().is_err());
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn replay_unrecorded_request() {
+    let path = cassette_path("unrecorded");
+    record(&path);
+
+    let client = Graph::with_transport("ACCESS_TOKEN", CassetteTransport::replay(&path).unwrap());
+    assert!(client.v1().me().drive().root_children().send().is_err());
+    assert!(client.v1().me().drive().drive().send().is_err());
+    fs::remove_file(&path).unwrap();
+}
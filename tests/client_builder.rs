@@ -0,0 +1,113 @@
+use graph_rs::client::GraphClientBuilder;
+use graph_rs::futures::Future;
+use graph_rs::header::{HeaderValue, ACCEPT_LANGUAGE};
+use graph_rs::http::RetryPolicy;
+use graph_rs::GRAPH_URL;
+use reqwest::StatusCode;
+use std::time::Duration;
+use test_tools::transport::{MockRequest, MockResponse, MockTransport};
+
+static ID: &str = "b!CbtYWrofwUGBJWnaJkNwoNrBLp_kC3RKklSXPwrdeP3yH8_qmH9xT5Y6RODPNfYI";
+
+#[test]
+fn build_client() {
+    let client = GraphClientBuilder::new()
+        .timeout(Duration::from_secs(60))
+        .connect_timeout(Duration::from_secs(10))
+        .user_agent("graph-rs-tests")
+        .default_header(ACCEPT_LANGUAGE, HeaderValue::from_static("en-US"))
+        .max_idle_per_host(4)
+        .retry_policy(RetryPolicy::none())
+        .build("ACCESS_TOKEN")
+        .unwrap();
+
+    let request = client.v1().me().drive().get_item(ID);
+    request.url_ref(|url| {
+        assert_eq!(
+            format!("{}/me/drive/items/{}", GRAPH_URL, ID),
+            url.to_string()
+        );
+    });
+}
+
+#[test]
+fn build_async_client() {
+    let client = GraphClientBuilder::new()
+        .timeout(Duration::from_secs(60))
+        .proxy(reqwest::Proxy::all("http://localhost:8080").unwrap())
+        .build_async("ACCESS_TOKEN");
+    assert!(client.is_ok());
+}
+
+fn header<'a>(request: &'a MockRequest, name: &str) -> Option<&'a str> {
+    request.headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+#[test]
+fn settings_are_sent_with_requests() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::OK, serde_json::json!({})),
+        MockResponse::json(StatusCode::OK, serde_json::json!({})),
+    ]);
+    let client = GraphClientBuilder::new()
+        .timeout(Duration::from_secs(60))
+        .user_agent("graph-rs-tests")
+        .default_header(ACCEPT_LANGUAGE, HeaderValue::from_static("en-US"))
+        .transport(transport.clone())
+        .build("ACCESS_TOKEN")
+        .unwrap();
+
+    client.v1().me().drive().get_item(ID).send().unwrap();
+    client
+        .v1()
+        .me()
+        .drive()
+        .get_item(ID)
+        .header(ACCEPT_LANGUAGE, HeaderValue::from_static("de-DE"))
+        .send()
+        .unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(2, requests.len());
+    for request in requests.iter() {
+        assert_eq!(Some(Duration::from_secs(60)), request.timeout);
+        assert_eq!(Some("graph-rs-tests"), header(request, "user-agent"));
+        assert_eq!(Some("Bearer ACCESS_TOKEN"), request.bearer());
+    }
+    assert_eq!(Some("en-US"), header(&requests[0], "accept-language"));
+    // A header set on the request is not replaced by the default.
+    assert_eq!(Some("de-DE"), header(&requests[1], "accept-language"));
+}
+
+#[test]
+fn async_settings_are_sent_with_requests() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({}),
+    )]);
+    let client = GraphClientBuilder::new()
+        .timeout(Duration::from_secs(30))
+        .user_agent("graph-rs-tests")
+        .default_header(ACCEPT_LANGUAGE, HeaderValue::from_static("en-US"))
+        .transport(transport.clone())
+        .build_async("ACCESS_TOKEN")
+        .unwrap();
+
+    client.v1().me().drive().get_item(ID).send().wait().unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(Some(Duration::from_secs(30)), requests[0].timeout);
+    assert_eq!(Some("graph-rs-tests"), header(&requests[0], "user-agent"));
+    assert_eq!(Some("en-US"), header(&requests[0], "accept-language"));
+}
+
+#[test]
+fn invalid_user_agent() {
+    let mut builder = GraphClientBuilder::new();
+    builder.user_agent("graph-rs\ntests");
+    assert!(builder.build("ACCESS_TOKEN").is_err());
+    assert!(builder.build_async("ACCESS_TOKEN").is_err());
+
+    builder.user_agent("graph-rs-tests");
+    assert!(builder.build("ACCESS_TOKEN").is_ok());
+}
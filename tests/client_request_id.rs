@@ -0,0 +1,184 @@
+use graph_error::GraphFailure;
+use graph_rs::prelude::*;
+use reqwest::StatusCode;
+use test_tools::transport::{MockRequest, MockResponse, MockTransport};
+
+static ID: &str = "01BYE5RZ6QN3ZWBTUFOFD3GSPGOHDJD36K";
+static NEXT_LINK: &str = "https://graph.microsoft.com/v1.0/users/delta?$skiptoken=page2";
+
+fn client_request_id(request: &MockRequest) -> Option<&str> {
+    request
+        .headers
+        .get("client-request-id")
+        .and_then(|v| v.to_str().ok())
+}
+
+#[test]
+fn generated_per_request() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::OK, serde_json::json!({})),
+        MockResponse::json(StatusCode::OK, serde_json::json!({})),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    let first = client.v1().me().drive().get_item(ID).send().unwrap();
+    let second = client.v1().me().drive().get_item(ID).send().unwrap();
+
+    let requests = transport.requests();
+    let id = client_request_id(&requests[0]).unwrap();
+    assert_eq!(36, id.len());
+    assert_eq!(Some(id.to_string()), first.client_request_id());
+    assert_ne!(first.client_request_id(), second.client_request_id());
+    assert_eq!(
+        Some("true"),
+        requests[0]
+            .headers
+            .get("return-client-request-id")
+            .and_then(|v| v.to_str().ok())
+    );
+}
+
+#[test]
+fn caller_id_and_retries() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::SERVICE_UNAVAILABLE, serde_json::json!({}))
+            .header("retry-after", "0"),
+        MockResponse::json(StatusCode::OK, serde_json::json!({}))
+            .header("client-request-id", "MY_ID")
+            .header("request-id", "REQUEST_ID"),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    let response = client
+        .v1()
+        .me()
+        .drive()
+        .get_item(ID)
+        .client_request_id("MY_ID")
+        .send()
+        .unwrap();
+    assert_eq!(Some("MY_ID".to_string()), response.client_request_id());
+    assert_eq!(Some("REQUEST_ID".to_string()), response.request_id());
+
+    let requests = transport.requests();
+    assert_eq!(2, requests.len());
+    assert_eq!(Some("MY_ID"), client_request_id(&requests[0]));
+    assert_eq!(Some("MY_ID"), client_request_id(&requests[1]));
+}
+
+#[test]
+fn error_responses() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::NOT_FOUND,
+        serde_json::json!({ "error": { "code": "itemNotFound", "message": "Not found" } }),
+    )]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    let err = client.v1().me().drive().get_item(ID).send().unwrap_err();
+    let id = client_request_id(&transport.requests()[0])
+        .unwrap()
+        .to_string();
+    assert_eq!(Some(id.clone()), err.client_request_id());
+    assert!(err.to_string().contains(id.as_str()));
+}
+
+#[test]
+fn delta_thread_errors() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(
+            StatusCode::OK,
+            serde_json::json!({ "value": [], "@odata.nextLink": NEXT_LINK }),
+        ),
+        MockResponse::json(StatusCode::BAD_REQUEST, serde_json::json!({})),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    let receiver = client.v1().users("USER_ID").delta().send();
+    let mut error = None;
+    while let Ok(delta) = receiver.recv() {
+        if let Delta::Done(err) = delta {
+            error = err;
+            break;
+        }
+    }
+
+    let requests = transport.requests();
+    assert_eq!(NEXT_LINK, requests[1].url);
+    assert_eq!(
+        client_request_id(&requests[1]).map(|id| id.to_string()),
+        error.unwrap().client_request_id()
+    );
+}
+
+#[test]
+fn transport_and_parse_failures() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!("not a drive item"),
+    )]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    let parse_err = client.v1().me().drive().get_item(ID).send().unwrap_err();
+    // The mock transport has no responses left so sending fails.
+    let transport_err = client.v1().me().drive().get_item(ID).send().unwrap_err();
+
+    let requests = transport.requests();
+    assert_eq!(2, requests.len());
+    match parse_err.failure() {
+        GraphFailure::SerdeError(_) => {},
+        err => panic!("Expected a serde error. Got: {:#?}", err),
+    }
+    assert_eq!(
+        client_request_id(&requests[0]).map(|id| id.to_string()),
+        parse_err.client_request_id()
+    );
+    match transport_err.failure() {
+        GraphFailure::GraphRsError(_) => {},
+        err => panic!("Expected an internal error. Got: {:#?}", err),
+    }
+    assert_eq!(
+        client_request_id(&requests[1]).map(|id| id.to_string()),
+        transport_err.client_request_id()
+    );
+    assert_ne!(
+        parse_err.client_request_id(),
+        transport_err.client_request_id()
+    );
+}
+
+#[test]
+fn not_sent_to_monitor_urls() {
+    let monitor_url = "https://api.onedrive.com/v1.0/monitor/4A3407B5-88FC-4504-8B21-0AABD3412717";
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::ACCEPTED, serde_json::json!({}))
+            .header("location", monitor_url),
+        MockResponse::json(
+            StatusCode::OK,
+            serde_json::json!({
+                "operation": "itemCopy",
+                "percentageComplete": 100.0,
+                "resourceId": "NEW_ITEM_ID",
+                "status": "completed",
+            }),
+        ),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    let response = client
+        .v1()
+        .me()
+        .drive()
+        .copy(ID, &serde_json::json!({ "name": "copy.txt" }))
+        .send()
+        .unwrap();
+    response.async_job(&client).unwrap().wait().unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(monitor_url, requests[1].url);
+    assert!(client_request_id(&requests[0]).is_some());
+    assert_eq!(None, client_request_id(&requests[1]));
+    assert!(requests[1]
+        .headers
+        .get("return-client-request-id")
+        .is_none());
+}
@@ -0,0 +1,81 @@
+use graph_rs::client::GraphClientBuilder;
+use graph_rs::oauth::graphdiscovery::GraphDiscovery;
+use graph_rs::oauth::{AzureCloud, OAuth, OAuthCredential};
+
+static ID: &str = "b!CbtYWrofwUGBJWnaJkNwoNrBLp_kC3RKklSXPwrdeP3yH8_qmH9xT5Y6RODPNfYI";
+
+#[test]
+fn national_cloud_urls() {
+    let client = GraphClientBuilder::new()
+        .cloud(AzureCloud::China)
+        .build("ACCESS_TOKEN")
+        .unwrap();
+
+    client.v1().me().drive().get_item(ID).url_ref(|url| {
+        assert_eq!(
+            format!(
+                "https://microsoftgraph.chinacloudapi.cn/v1.0/me/drive/items/{}",
+                ID
+            ),
+            url.to_string()
+        );
+    });
+    client.beta().me().drive().root().url_ref(|url| {
+        assert_eq!(
+            "https://microsoftgraph.chinacloudapi.cn/beta/me/drive/root",
+            url.to_string()
+        );
+    });
+}
+
+#[test]
+fn custom_cloud_urls() {
+    let cloud = AzureCloud::custom("http://localhost:8000/", "http://localhost:8001");
+    let client = GraphClientBuilder::new()
+        .cloud(cloud.clone())
+        .build_async("ACCESS_TOKEN")
+        .unwrap();
+
+    assert_eq!(&cloud, client.cloud());
+    client.v1().me().drive().root().url_ref(|url| {
+        assert_eq!("http://localhost:8000/v1.0/me/drive/root", url.to_string());
+    });
+    assert_eq!(
+        "http://localhost:8001/common/.well-known/openid-configuration",
+        GraphDiscovery::Cloud(cloud, "common".into()).url()
+    );
+}
+
+#[test]
+fn invalid_custom_cloud() {
+    let client = GraphClientBuilder::new()
+        .cloud(AzureCloud::custom("localhost", "localhost"))
+        .build("ACCESS_TOKEN");
+    assert!(client.is_err());
+}
+
+#[test]
+fn oauth_cloud_urls() {
+    let mut oauth = OAuth::new();
+    oauth.cloud(&AzureCloud::UsGovL5, "organizations");
+    assert_eq!(
+        Some("https://login.microsoftonline.us/organizations/oauth2/v2.0/authorize".to_string()),
+        oauth.get(OAuthCredential::AuthorizeURL)
+    );
+    assert_eq!(
+        Some("https://login.microsoftonline.us/organizations/oauth2/v2.0/token".to_string()),
+        oauth.get(OAuthCredential::AccessTokenURL)
+    );
+    assert_eq!(
+        Some("https://login.microsoftonline.us/organizations/oauth2/v2.0/token".to_string()),
+        oauth.get(OAuthCredential::RefreshTokenURL)
+    );
+    assert_eq!(
+        Some("https://login.microsoftonline.us/organizations/oauth2/v2.0/logout".to_string()),
+        oauth.get(OAuthCredential::LogoutURL)
+    );
+    assert_eq!(
+        "https://dod-graph.microsoft.us/v1.0",
+        AzureCloud::UsGovL5.graph_url()
+    );
+}
@@ -0,0 +1,140 @@
+use flate2::write::{GzEncoder, ZlibEncoder};
+use graph_rs::futures::Future;
+use graph_rs::prelude::*;
+use reqwest::StatusCode;
+use std::io::Write;
+use test_tools::transport::{MockResponse, MockTransport};
+
+fn items(count: usize) -> serde_json::Value {
+    let value: Vec<serde_json::Value> = (0..count)
+        .map(|i| serde_json::json!({ "id": format!("ITEM_ID_{}", i), "name": "file.txt" }))
+        .collect();
+    serde_json::json!({ "value": value })
+}
+
+fn gzip(body: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(body).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn deflate(body: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(body).unwrap();
+    encoder.finish().unwrap()
+}
+
+fn encoded(encoding: &'static str, encode: fn(&[u8]) -> Vec<u8>) -> MockResponse {
+    let mut response =
+        MockResponse::json(StatusCode::OK, items(500)).header("content-encoding", encoding);
+    response.body = encode(&response.body);
+    let len = response.body.len().to_string();
+    response.header("content-length", len.as_str())
+}
+
+#[test]
+fn accept_encoding_header() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({}),
+    )]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.v1().me().drive().root_children().send().unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(
+        Some("gzip, deflate"),
+        requests[0]
+            .headers
+            .get("accept-encoding")
+            .and_then(|v| v.to_str().ok())
+    );
+}
+
+#[test]
+fn decompress_gzip() {
+    let transport = MockTransport::new(vec![encoded("gzip", gzip)]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport);
+
+    let response = client.v1().me().drive().root_children().send().unwrap();
+    assert_eq!(500, response.value().len());
+    assert_eq!(
+        Some("ITEM_ID_499"),
+        response
+            .value()
+            .index(499)
+            .and_then(|item| item.id().as_deref())
+    );
+    assert!(!response.headers().contains_key("content-encoding"));
+    assert!(!response.headers().contains_key("content-length"));
+}
+
+#[test]
+fn decompress_deflate() {
+    let transport = MockTransport::new(vec![encoded("deflate", deflate)]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport);
+
+    let response = client.v1().me().drive().root_children().send().unwrap();
+    assert_eq!(500, response.value().len());
+}
+
+#[test]
+fn compression_off() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({}),
+    )]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.set_compression(false);
+    client.v1().me().drive().root_children().send().unwrap();
+
+    assert!(!transport.requests()[0]
+        .headers
+        .contains_key("accept-encoding"));
+}
+
+#[test]
+fn async_decompress_gzip() {
+    let transport = MockTransport::new(vec![encoded("gzip", gzip)]);
+    let client = Graph::new_async_with_transport("ACCESS_TOKEN", transport.clone());
+
+    let response = client
+        .v1()
+        .me()
+        .drive()
+        .root_children()
+        .send()
+        .wait()
+        .unwrap();
+    assert_eq!(500, response.value().len());
+    assert!(!response.headers().contains_key("content-encoding"));
+    assert_eq!(
+        Some("gzip, deflate"),
+        transport.requests()[0]
+            .headers
+            .get("accept-encoding")
+            .and_then(|v| v.to_str().ok())
+    );
+}
+
+#[test]
+fn async_compression_off() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({}),
+    )]);
+    let client = Graph::new_async_with_transport("ACCESS_TOKEN", transport.clone());
+    client.set_compression(false);
+    client
+        .v1()
+        .me()
+        .drive()
+        .root_children()
+        .send()
+        .wait()
+        .unwrap();
+
+    assert!(!transport.requests()[0]
+        .headers
+        .contains_key("accept-encoding"));
+}
@@ -0,0 +1,207 @@
+use from_as::*;
+use graph_rs::http::{DeltaEvent, DeltaState};
+use graph_rs::prelude::*;
+use reqwest::header::{HeaderName, HeaderValue};
+use reqwest::StatusCode;
+use test_tools::transport::{MockResponse, MockTransport};
+
+static DELTA_URL: &str = "https://graph.microsoft.com/v1.0/me/drive/root/delta";
+static NEXT_LINK: &str = "https://graph.microsoft.com/v1.0/me/drive/root/delta?$skiptoken=page2";
+static DELTA_LINK: &str = "https://graph.microsoft.com/v1.0/me/drive/root/delta?token=latest";
+
+fn full_sync() -> Vec<MockResponse> {
+    vec![
+        MockResponse::json(
+            StatusCode::OK,
+            serde_json::json!({
+                "@odata.nextLink": NEXT_LINK,
+                "value": [{ "id": "1" }, { "id": "2", "@removed": { "reason": "deleted" } }]
+            }),
+        ),
+        MockResponse::json(
+            StatusCode::OK,
+            serde_json::json!({
+                "@odata.deltaLink": DELTA_LINK,
+                "value": [{ "id": "3" }]
+            }),
+        ),
+    ]
+}
+
+#[test]
+fn delta_sync_events() {
+    let transport = MockTransport::new(full_sync());
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    let state = client.v1().me().drive().delta().delta_state().unwrap();
+    assert_eq!(DELTA_URL, state.url());
+    assert_eq!(DELTA_URL, state.next_url());
+
+    let mut sync = client.delta_sync(state);
+    let events: Vec<DeltaEvent> = sync.by_ref().map(|event| event.unwrap()).collect();
+    assert_eq!(
+        vec![
+            DeltaEvent::Changed(serde_json::json!({ "id": "1" })),
+            DeltaEvent::Removed(
+                serde_json::json!({ "id": "2", "@removed": { "reason": "deleted" } })
+            ),
+            DeltaEvent::Changed(serde_json::json!({ "id": "3" })),
+        ],
+        events
+    );
+    assert_eq!(
+        Some(&DELTA_LINK.to_string()),
+        sync.state().delta_link().as_ref()
+    );
+    assert_eq!(DELTA_LINK, sync.state().next_url());
+
+    let requests = transport.requests();
+    assert_eq!(2, requests.len());
+    assert_eq!(DELTA_URL, requests[0].url);
+    assert_eq!(NEXT_LINK, requests[1].url);
+}
+
+#[test]
+fn delta_sync_resumes_from_file() {
+    let transport = MockTransport::new(full_sync());
+    let client = Graph::with_transport("ACCESS_TOKEN", transport);
+    let mut sync = client.delta_sync(DeltaState::new(DELTA_URL));
+    sync.by_ref().for_each(|event| {
+        event.unwrap();
+    });
+
+    let path = std::env::temp_dir().join("graph-rs-delta-state.json");
+    sync.state().as_file(&path).unwrap();
+    let state = DeltaState::from_file(&path).unwrap();
+    assert_eq!(sync.state(), &state);
+    std::fs::remove_file(&path).unwrap();
+
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({ "@odata.deltaLink": DELTA_LINK, "value": [] }),
+    )]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    assert_eq!(0, client.delta_sync(state).count());
+    assert_eq!(DELTA_LINK, transport.requests()[0].url);
+}
+
+#[test]
+fn delta_sync_resync_on_gone() {
+    let mut responses = vec![MockResponse::json(
+        StatusCode::GONE,
+        serde_json::json!({ "error": { "code": "resyncRequired" } }),
+    )];
+    responses.extend(full_sync());
+    let transport = MockTransport::new(responses);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    let state = {
+        let transport = MockTransport::new(full_sync());
+        let client = Graph::with_transport("ACCESS_TOKEN", transport);
+        let mut sync = client.delta_sync(DeltaState::new(DELTA_URL));
+        sync.by_ref().for_each(|event| {
+            event.unwrap();
+        });
+        sync.into_state()
+    };
+
+    let events: Vec<DeltaEvent> = client
+        .delta_sync(state)
+        .map(|event| event.unwrap())
+        .collect();
+    assert_eq!(DeltaEvent::Resync, events[0]);
+    assert_eq!(4, events.len());
+
+    let requests = transport.requests();
+    assert_eq!(3, requests.len());
+    assert_eq!(DELTA_LINK, requests[0].url);
+    assert_eq!(DELTA_URL, requests[1].url);
+}
+
+#[test]
+fn delta_sync_gone_twice_is_an_error() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::GONE, serde_json::json!({})),
+        MockResponse::json(StatusCode::GONE, serde_json::json!({})),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport);
+    let events: Vec<_> = client.delta_sync(DeltaState::new(DELTA_URL)).collect();
+    assert_eq!(2, events.len());
+    assert!(events[1].is_err());
+}
+
+#[test]
+fn users_delta_url() {
+    let client = Graph::new("");
+    let state = client.v1().users("").delta().delta_state().unwrap();
+    assert!(state.url().ends_with("/users/delta"));
+}
+
+#[test]
+fn delta_state_saves_allowed_headers() {
+    let client = Graph::new("");
+    let state = client
+        .v1()
+        .me()
+        .drive()
+        .delta()
+        .header(
+            HeaderName::from_static("x-api-key"),
+            HeaderValue::from_static("API_KEY"),
+        )
+        .consistency_level_eventual()
+        .prefer(Preference::MaxPageSize(2))
+        .delta_state()
+        .unwrap();
+
+    let names: Vec<&str> = state.headers().keys().map(|name| name.as_str()).collect();
+    assert_eq!(vec!["consistencylevel", "prefer"], names);
+}
+
+#[test]
+fn delta_sync_keeps_request_headers() {
+    let mut responses = full_sync();
+    responses.push(MockResponse::json(StatusCode::GONE, serde_json::json!({})));
+    responses.extend(full_sync());
+    let transport = MockTransport::new(responses);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.add_default_preference(Preference::ImmutableId);
+
+    let state = client
+        .v1()
+        .me()
+        .drive()
+        .delta()
+        .prefer(Preference::MaxPageSize(2))
+        .delta_state()
+        .unwrap();
+
+    // The headers are kept when the state is saved to a file.
+    let path = std::env::temp_dir().join("graph-rs-delta-state-headers.json");
+    state.as_file(&path).unwrap();
+    let state = DeltaState::from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let mut sync = client.delta_sync(state);
+    sync.by_ref().for_each(|event| {
+        event.unwrap();
+    });
+    let state = sync.into_state();
+    let events: Vec<DeltaEvent> = client
+        .delta_sync(state)
+        .map(|event| event.unwrap())
+        .collect();
+    assert_eq!(DeltaEvent::Resync, events[0]);
+
+    let requests = transport.requests();
+    assert_eq!(5, requests.len());
+    assert_eq!(NEXT_LINK, requests[1].url);
+    assert_eq!(DELTA_LINK, requests[2].url);
+    assert_eq!(DELTA_URL, requests[3].url);
+    for request in requests.iter() {
+        let prefer = Prefer::parse(request.headers.get("prefer").unwrap().to_str().unwrap());
+        assert!(prefer.contains(&Preference::MaxPageSize(2)));
+        assert!(prefer.contains(&Preference::ImmutableId));
+        assert_eq!(Some("Bearer ACCESS_TOKEN"), request.bearer());
+    }
+}
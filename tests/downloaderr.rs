@@ -34,14 +34,14 @@ fn download_config_file_exists() {
             let result = download_client.send();
 
             if let Err(err) = result {
-                match err {
+                match err.failure() {
                     GraphFailure::GraphRsError(err) => {
                         match err {
                             GraphRsError::DownloadFileExists { name} => {
                                 if cfg!(target_os = "windows") {
-                                    assert_eq!(name, "./test_files\\downloadtestdoc.txt".to_string());
+                                    assert_eq!(name, "./test_files\\downloadtestdoc.txt");
                                 } else {
-                                    assert_eq!(name, "./test_files/downloadtestdoc.txt".to_string());
+                                    assert_eq!(name, "./test_files/downloadtestdoc.txt");
                                 }
                             },
                             _ => panic!("Incorrect error thrown. Should have been GraphRsError::DownloadFileExists. Got: {:#?}", err)
@@ -71,11 +71,11 @@ fn download_is_err_config_dir_no_exists() {
     let result = download_client.send();
 
     if let Err(err) = result {
-        match err {
+        match err.failure() {
             GraphFailure::GraphRsError(err) => {
                 match err {
                     GraphRsError::DownloadDirNoExists { dir } => {
-                        assert_eq!("./test_files/download_dir".to_string(), dir)
+                        assert_eq!("./test_files/download_dir", dir)
                     },
                     _ => panic!("Incorrect error thrown. Should have been GraphRsError::DownloadDirNoExists. Got: {:#?}", err)
                 }
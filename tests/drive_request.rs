@@ -35,7 +35,7 @@ fn create_delete_folder() {
                 .send();
 
             if let Ok(response) = create_folder_res {
-                let item_id = response.value()["id"].as_str().unwrap();
+                let item_id = response.value().id().as_ref().unwrap();
                 thread::sleep(Duration::from_secs(2));
 
                 let req = client.v1().drives(id).drive().delete(item_id).send();
@@ -66,8 +66,8 @@ fn list_versions_get_item() {
                 .send();
 
             if let Ok(res) = get_item_res {
-                assert!(res.value()["id"].as_str().is_some());
-                let item_id = res.value()["id"].as_str().unwrap();
+                assert!(res.value().id().is_some());
+                let item_id = res.value().id().as_ref().unwrap();
 
                 let versions_res = client
                     .v1()
@@ -223,7 +223,7 @@ fn drive_update() {
                 .send();
 
             if let Ok(response) = req {
-                assert_eq!(response.value()["name"].as_str(), Some("update_test.docx"));
+                assert_eq!(response.value().name().as_deref(), Some("update_test.docx"));
                 thread::sleep(Duration::from_secs(2));
 
                 let req = client
@@ -240,7 +240,7 @@ fn drive_update() {
 
                 if let Ok(response) = req {
                     assert_eq!(
-                        response.value()["name"].as_str(),
+                        response.value().name().as_deref(),
                         Some("update_test_document.docx")
                     );
                 } else if let Err(e) = req {
@@ -270,8 +270,8 @@ fn drive_upload_new_and_replace_and_delete() {
                 .send();
 
             if let Ok(value) = upload_res {
-                assert!(value.value()["id"].as_str().is_some());
-                let item_id = value.value()["id"].as_str().unwrap();
+                assert!(value.value().id().is_some());
+                let item_id = value.value().id().as_ref().unwrap();
 
                 let mut file = OpenOptions::new()
                     .write(true)
@@ -290,7 +290,7 @@ fn drive_upload_new_and_replace_and_delete() {
                     .send();
 
                 if let Ok(value) = upload_replace {
-                    let item_id2 = value.value()["id"].as_str().unwrap();
+                    let item_id2 = value.value().id().as_ref().unwrap();
                     assert_eq!(item_id, item_id2);
                 } else if let Err(e) = upload_replace {
                     panic!(
@@ -0,0 +1,225 @@
+use from_as::*;
+use graph_rs::prelude::*;
+use reqwest::StatusCode;
+use test_tools::transport::{MockResponse, MockTransport};
+
+static ID: &str = "01BYE5RZ6QN3ZWBTUFOFD3GSPGOHDJD36K";
+
+#[test]
+fn drive_from_file() {
+    let drive = Drive::from_file("test_files/item_test/drive_info.json").unwrap();
+    assert_eq!(
+        Some("b!CbtYWrofwUGBJWnaJkNwoNrBLp_kC3RKklSXPwrdeP3yH8_qmH9xT5Y6RODPNfYI"),
+        drive.id().as_deref()
+    );
+    assert_eq!(Some("documentLibrary"), drive.drive_type().as_deref());
+
+    let owner = drive.owner().as_ref().unwrap().group().as_ref().unwrap();
+    assert_eq!(
+        Some("Company Administrator"),
+        owner.display_name().as_deref()
+    );
+
+    let quota = drive.quota().as_ref().unwrap();
+    assert_eq!(&Some(27_487_790_694_400), quota.total());
+    assert_eq!(&Some(305_435_615), quota.used());
+    assert_eq!(Some("normal"), quota.state().as_deref());
+}
+
+#[test]
+fn drive_item_from_file() {
+    let item = DriveItem::from_file("test_files/item_test/special_photo_folder.json").unwrap();
+    assert_eq!(Some("189302sal4098740fjhlk34"), item.id().as_deref());
+    assert_eq!(Some("Pictures"), item.name().as_deref());
+    assert_eq!(&Some(0), item.size());
+    assert!(item.is_folder());
+    assert!(!item.is_file());
+
+    let folder = item.folder().as_ref().unwrap();
+    assert_eq!(&Some(10), folder.child_count());
+    assert_eq!(
+        Some("takenOrCreatedDateTime"),
+        folder.view().as_ref().unwrap().sort_by().as_deref()
+    );
+
+    let parent = item.parent_reference().as_ref().unwrap();
+    assert_eq!(Some("personal"), parent.drive_type().as_deref());
+    assert_eq!(Some("/drive/root:"), parent.path().as_deref());
+
+    let created_by = item.created_by().as_ref().unwrap();
+    assert_eq!(
+        Some("OneDrive website"),
+        created_by
+            .application()
+            .as_ref()
+            .unwrap()
+            .display_name()
+            .as_deref()
+    );
+    assert_eq!(
+        Some("2018-01-20T01:37:04.4Z"),
+        item.file_system_info()
+            .as_ref()
+            .unwrap()
+            .created_date_time()
+            .as_deref()
+    );
+    assert_eq!(
+        Some("photos"),
+        item.special_folder().as_ref().unwrap().name().as_deref()
+    );
+    assert_eq!(Some("aFNsad93FFFAuMA".to_string()), item.etag());
+    assert_eq!(
+        Some("adDoxNzc4N0U3320938adsly42MzY".to_string()),
+        ETag::c_tag(&item)
+    );
+}
+
+#[test]
+fn drive_item_collection_from_file() {
+    let collection: Collection<DriveItem> =
+        Collection::from_file("test_files/item_test/drive_root_children.json").unwrap();
+    let item = collection.index(0).unwrap();
+    assert_eq!(Some(ID), item.id().as_deref());
+    assert_eq!(Some("Attachments"), item.name().as_deref());
+    assert_eq!(
+        Some("attachments"),
+        item.special_folder().as_ref().unwrap().name().as_deref()
+    );
+    assert_eq!(
+        Some("Megan Bowen"),
+        item.created_by()
+            .as_ref()
+            .unwrap()
+            .user()
+            .as_ref()
+            .unwrap()
+            .display_name()
+            .as_deref()
+    );
+    assert_eq!(
+        Some("2017-08-07T16:16:30Z"),
+        collection.index(1).unwrap().created_date_time().as_deref()
+    );
+}
+
+#[test]
+fn drive_item_skips_missing_fields() {
+    let item: DriveItem = serde_json::from_value(serde_json::json!({
+        "id": ID,
+        "name": "file.txt",
+        "file": { "mimeType": "text/plain", "hashes": { "sha1Hash": "SHA1" } },
+        "@microsoft.graph.downloadUrl": "https://download"
+    }))
+    .unwrap();
+    assert!(item.is_file());
+    assert_eq!(Some("https://download"), item.download_url().as_deref());
+
+    let file = item.file().as_ref().unwrap();
+    assert_eq!(Some("text/plain"), file.mime_type().as_deref());
+    assert_eq!(
+        Some("SHA1"),
+        file.hashes().as_ref().unwrap().sha1_hash().as_deref()
+    );
+
+    assert_eq!(
+        serde_json::json!({
+            "id": ID,
+            "name": "file.txt",
+            "file": { "mimeType": "text/plain", "hashes": { "sha1Hash": "SHA1" } },
+            "@microsoft.graph.downloadUrl": "https://download"
+        }),
+        serde_json::to_value(&item).unwrap()
+    );
+}
+
+#[test]
+fn typed_drive_responses() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(
+            StatusCode::OK,
+            serde_json::json!({
+                "id": "DRIVE_ID",
+                "driveType": "business",
+                "quota": { "remaining": 1024, "state": "nearing" }
+            }),
+        ),
+        MockResponse::json(
+            StatusCode::OK,
+            serde_json::json!({
+                "value": [{ "id": "1", "name": "docs", "folder": { "childCount": 2 } }]
+            }),
+        ),
+        MockResponse::json(
+            StatusCode::OK,
+            serde_json::json!({
+                "value": [{ "id": "0", "small": { "width": 96, "height": 96, "url": "https://small" } }]
+            }),
+        ),
+        MockResponse::json(
+            StatusCode::OK,
+            serde_json::json!({
+                "value": [{
+                    "id": "PERMISSION_ID",
+                    "roles": ["write"],
+                    "link": { "type": "edit", "scope": "organization", "webUrl": "https://link" }
+                }]
+            }),
+        ),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    let drive = client.v1().me().drive().drive().send().unwrap();
+    assert_eq!(Some("business"), drive.value().drive_type().as_deref());
+    assert_eq!(
+        &Some(1024),
+        drive.value().quota().as_ref().unwrap().remaining()
+    );
+
+    let children = client.v1().me().drive().root_children().send().unwrap();
+    let folder = children.value().index(0).unwrap();
+    assert_eq!(Some("docs"), folder.name().as_deref());
+    assert_eq!(&Some(2), folder.folder().as_ref().unwrap().child_count());
+
+    let thumbnails = client.v1().me().drive().thumbnails().send().unwrap();
+    let small = thumbnails
+        .value()
+        .index(0)
+        .unwrap()
+        .small()
+        .as_ref()
+        .unwrap();
+    assert_eq!(&Some(96), small.width());
+    assert_eq!(Some("https://small"), small.url().as_deref());
+
+    let permissions = client
+        .v1()
+        .me()
+        .drive()
+        .list_permissions(ID)
+        .send()
+        .unwrap();
+    let permission = permissions.value().index(0).unwrap();
+    assert_eq!(&Some(vec!["write".to_string()]), permission.roles());
+    let link = permission.link().as_ref().unwrap();
+    assert_eq!(Some("edit"), link.link_type().as_deref());
+    assert_eq!(Some("organization"), link.scope().as_deref());
+
+    let requests = transport.requests();
+    assert_eq!(
+        format!("{}/me/drive/items/{}/permissions", graph_rs::GRAPH_URL, ID),
+        requests[3].url
+    );
+}
+
+#[test]
+fn raw_json_drive_item() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({ "id": ID, "name": "file.txt", "customField": true }),
+    )]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport);
+
+    let value: serde_json::Value = client.v1().me().drive().get_item(ID).json().unwrap();
+    assert_eq!(Some(true), value["customField"].as_bool());
+}
@@ -30,7 +30,7 @@ fn query_mutate() {
         .top("3");
     client.url_ref(|url| {
         assert_eq!(
-            "https://graph.microsoft.com/v1.0/drives/T5Y6RODPNfYICbtYWrofwUGBJWnaJkNwH9x?select=name&top=3",
+            "https://graph.microsoft.com/v1.0/drives/T5Y6RODPNfYICbtYWrofwUGBJWnaJkNwH9x?$select=name&$top=3",
             url.as_str()
         );
     });
@@ -38,7 +38,7 @@ fn query_mutate() {
     let _ = client.v1().drives(RID).drive().root().expand(&["children"]);
     client.url_ref(|url| {
         assert_eq!(
-            "https://graph.microsoft.com/v1.0/drives/T5Y6RODPNfYICbtYWrofwUGBJWnaJkNwH9x/root?expand=children",
+            "https://graph.microsoft.com/v1.0/drives/T5Y6RODPNfYICbtYWrofwUGBJWnaJkNwH9x/root?$expand=children",
             url.as_str()
         );
     });
@@ -1000,3 +1000,23 @@ pub fn drive_item_activities() {
         "/sites/T5Y6RODPNfYICbtYWrofwUGBJWnaJkNwH9x/drive/root:/Documents/item.txt:/activities",
     );
 }
+
+#[test]
+fn async_client_url() {
+    let client = Graph::new_async("");
+    let _ = client.v1().me().drive().get_item(ID);
+    client.url_ref(|url| {
+        assert_eq!(
+            format!("{}/me/drive/items/{}", GRAPH_URL, ID),
+            url.to_string()
+        );
+    });
+
+    let _ = client.v1().drives(RID).drive().delta();
+    client.url_ref(|url| {
+        assert_eq!(
+            format!("{}/drives/{}/root/delta", GRAPH_URL, RID),
+            url.to_string()
+        );
+    });
+}
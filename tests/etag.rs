@@ -0,0 +1,139 @@
+use graph_rs::prelude::*;
+use graph_rs::types::batch::{BatchRequest, BatchStep};
+use reqwest::header::{IF_MATCH, IF_NONE_MATCH};
+use reqwest::StatusCode;
+use test_tools::transport::{MockResponse, MockTransport};
+
+static ETAG: &str = "\"{1C5A5D6E-1B2C-4E3F-9A8B-7C6D5E4F3A2B},2\"";
+static CURRENT_ETAG: &str = "\"{1C5A5D6E-1B2C-4E3F-9A8B-7C6D5E4F3A2B},3\"";
+
+#[test]
+fn etag_from_response() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(
+            StatusCode::OK,
+            serde_json::json!({
+                "id": "ITEM_ID",
+                "eTag": ETAG,
+                "cTag": "\"c:{1C5A5D6E},1\""
+            }),
+        ),
+        MockResponse::json(StatusCode::OK, serde_json::json!({ "id": "ITEM_ID" }))
+            .header("etag", CURRENT_ETAG),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport);
+
+    let response = client.v1().me().drive().get_item("ITEM_ID").send().unwrap();
+    assert_eq!(None, response.odata_etag());
+    assert_eq!(Some(ETAG.to_string()), response.e_tag());
+    assert_eq!(Some("\"c:{1C5A5D6E},1\"".to_string()), response.c_tag());
+    assert_eq!(Some(ETAG.to_string()), response.etag());
+
+    let response = client.v1().me().drive().get_item("ITEM_ID").send().unwrap();
+    assert_eq!(Some(CURRENT_ETAG.to_string()), response.etag());
+
+    let value = serde_json::json!({ "@odata.etag": "W/\"1\"", "eTag": ETAG });
+    assert_eq!(Some("W/\"1\"".to_string()), value.etag());
+}
+
+#[test]
+fn if_match_headers() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::OK, serde_json::json!({})),
+        MockResponse::json(StatusCode::NO_CONTENT, serde_json::json!({})),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    client
+        .v1()
+        .me()
+        .drive()
+        .update("ITEM_ID", &serde_json::json!({ "name": "name" }))
+        .if_match(ETAG)
+        .send()
+        .unwrap();
+    client
+        .v1()
+        .me()
+        .drive()
+        .delete("ITEM_ID")
+        .if_none_match("*")
+        .send()
+        .unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(ETAG, requests[0].headers.get(IF_MATCH).unwrap());
+    assert_eq!("*", requests[1].headers.get(IF_NONE_MATCH).unwrap());
+}
+
+#[test]
+fn precondition_failed() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::PRECONDITION_FAILED,
+        serde_json::json!({
+            "error": { "code": "notAllowed", "message": "ETag does not match current item's value" }
+        }),
+    )
+    .header("etag", CURRENT_ETAG)]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport);
+
+    let response = client
+        .v1()
+        .me()
+        .drive()
+        .move_item(
+            "ITEM_ID",
+            &serde_json::json!({ "parentReference": { "id": "FOLDER_ID" } }),
+        )
+        .if_match(ETAG)
+        .send();
+    match response {
+        Err(GraphFailure::PreconditionFailed(err)) => {
+            assert_eq!(412, err.error.code);
+            assert_eq!(Some(CURRENT_ETAG.to_string()), err.etag);
+            assert_eq!(None, err.current);
+            assert_eq!(
+                Some("ETag does not match current item's value".to_string()),
+                err.error.message()
+            );
+        },
+        other => panic!("Expected PreconditionFailed. Got: {:#?}", other),
+    }
+}
+
+#[test]
+fn precondition_failed_in_batch() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({
+            "responses": [{
+                "id": "1",
+                "status": 412,
+                "headers": { "ETag": CURRENT_ETAG },
+                "body": { "id": "ITEM_ID", "eTag": CURRENT_ETAG }
+            }]
+        }),
+    )]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport);
+    let mut batch = BatchRequest::new();
+    batch.add(
+        BatchStep::patch(
+            "1",
+            "/me/drive/items/ITEM_ID",
+            serde_json::json!({ "name": "name" }),
+        )
+        .header("If-Match", ETAG),
+    );
+
+    let response = client.v1().send_batch(&batch).unwrap();
+    match response.get("1").unwrap().error() {
+        Some(GraphFailure::PreconditionFailed(err)) => {
+            assert_eq!(Some(CURRENT_ETAG.to_string()), err.etag);
+            assert_eq!(
+                Some(&serde_json::json!({ "id": "ITEM_ID", "eTag": CURRENT_ETAG })),
+                err.current.as_ref()
+            );
+        },
+        other => panic!("Expected PreconditionFailed. Got: {:#?}", other),
+    }
+}
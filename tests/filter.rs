@@ -0,0 +1,117 @@
+use graph_rs::prelude::*;
+use graph_rs::url::{Filter, Literal};
+
+#[test]
+fn compare_literals() {
+    assert_eq!("name eq 'report'", Filter::eq("name", "report").to_string());
+    assert_eq!(
+        "name ne 'O''Neil'",
+        Filter::ne("name", "O'Neil").to_string()
+    );
+    assert_eq!("size gt 1024", Filter::gt("size", 1024).to_string());
+    assert_eq!("size le 1.5", Filter::le("size", 1.5).to_string());
+    assert_eq!(
+        "accountEnabled eq true",
+        Filter::eq("accountEnabled", true).to_string()
+    );
+    assert_eq!(
+        "manager eq null",
+        Filter::eq("manager", Literal::Null).to_string()
+    );
+    assert_eq!(
+        "id eq 01234567-89ab-cdef-0123-456789abcdef",
+        Filter::eq(
+            "id",
+            Literal::guid("01234567-89ab-cdef-0123-456789abcdef").unwrap()
+        )
+        .to_string()
+    );
+    assert_eq!(
+        "createdDateTime lt 2019-12-31T23:59:59Z",
+        Filter::lt(
+            "createdDateTime",
+            Literal::date_time_offset("2019-12-31T23:59:59Z").unwrap()
+        )
+        .to_string()
+    );
+    assert_eq!(
+        "birthday ge 2000-01-01",
+        Filter::ge("birthday", Literal::date("2000-01-01").unwrap()).to_string()
+    );
+}
+
+#[test]
+fn invalid_literals() {
+    assert!(Literal::guid("01234567-89ab-cdef-0123-456789abcdeg").is_err());
+    assert!(Literal::guid("' or 1 eq 1").is_err());
+    assert!(Literal::date("2000-1-01").is_err());
+    assert!(Literal::date_time_offset("2019-12-31").is_err());
+    assert!(Literal::date_time_offset("2019-12-31T00:00:00Z or true").is_err());
+}
+
+#[test]
+fn combine_expressions() {
+    let filter = Filter::starts_with("name", "a")
+        .or(Filter::ends_with("name", ".txt"))
+        .and(Filter::gt("size", 0).not());
+    assert_eq!(
+        "(startswith(name,'a') or endswith(name,'.txt')) and not (size gt 0)",
+        filter.to_string()
+    );
+
+    let filter = Filter::eq("a", 1)
+        .and(Filter::eq("b", 2))
+        .or(Filter::eq("c", 3));
+    assert_eq!("(a eq 1 and b eq 2) or c eq 3", filter.to_string());
+
+    let filter = Filter::eq("a", 1)
+        .and(Filter::eq("b", 2))
+        .and(Filter::eq("c", 3));
+    assert_eq!("a eq 1 and b eq 2 and c eq 3", filter.to_string());
+}
+
+#[test]
+fn lambda_expressions() {
+    let filter = Filter::any("members", "m", Filter::eq("m/mail", "a@contoso.com"));
+    assert_eq!(
+        "members/any(m:m/mail eq 'a@contoso.com')",
+        filter.to_string()
+    );
+
+    let filter = Filter::all("emails", "e", Filter::ends_with("e", "@contoso.com"));
+    assert_eq!(
+        "emails/all(e:endswith(e,'@contoso.com'))",
+        filter.to_string()
+    );
+}
+
+#[test]
+fn filter_by_query() {
+    let client = Graph::new("");
+    let _ = client
+        .v1()
+        .me()
+        .drive()
+        .root_children()
+        .filter_by(&Filter::eq("name", "it's & more").and(Filter::gt("size", 10)));
+    client.url_ref(|url| {
+        assert_eq!(
+            "https://graph.microsoft.com/v1.0/me/drive/root/children?$filter=name+eq+%27it%27%27s+%26+more%27+and+size+gt+10",
+            url.as_str()
+        );
+    });
+
+    // The string form is unchanged.
+    let _ = client
+        .v1()
+        .me()
+        .drive()
+        .root_children()
+        .filter(&["name eq 'report'"]);
+    client.url_ref(|url| {
+        assert_eq!(
+            "https://graph.microsoft.com/v1.0/me/drive/root/children?$filter=name+eq+%27report%27",
+            url.as_str()
+        );
+    });
+}
@@ -0,0 +1,198 @@
+use graph_error::GraphResult;
+use graph_rs::futures::Future;
+use graph_rs::header::{HeaderName, HeaderValue};
+use graph_rs::http::{AsyncNext, GraphFuture, GraphRequestBuilder, HttpResponse, Middleware, Next};
+use graph_rs::prelude::*;
+use graph_rs::url::GraphUrl;
+use reqwest::StatusCode;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use test_tools::transport::{MockResponse, MockTransport};
+
+static ID: &str = "b!CbtYWrofwUGBJWnaJkNwoNrBLp_kC3RKklSXPwrdeP3yH8_qmH9xT5Y6RODPNfYI";
+
+struct Order {
+    name: &'static str,
+    calls: Arc<Mutex<Vec<&'static str>>>,
+}
+
+impl Middleware for Order {
+    fn handle(&self, mut request: GraphRequestBuilder, next: Next) -> GraphResult<HttpResponse> {
+        self.calls.lock().unwrap().push(self.name);
+        request.header(
+            HeaderName::from_static("x-middleware"),
+            HeaderValue::from_static(self.name),
+        );
+        next.run(request)
+    }
+}
+
+struct Count(Arc<AtomicUsize>);
+
+impl Middleware for Count {
+    fn handle(&self, request: GraphRequestBuilder, next: Next) -> GraphResult<HttpResponse> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        next.run(request)
+    }
+}
+
+struct Rewrite;
+
+impl Middleware for Rewrite {
+    fn handle(&self, mut request: GraphRequestBuilder, next: Next) -> GraphResult<HttpResponse> {
+        let url = request
+            .url()
+            .to_string()
+            .replace("/me/", "/users/rewritten/");
+        request.set_url(GraphUrl::from_str(url.as_str()).unwrap());
+        let mut response = next.run(request)?;
+        response.headers_mut().insert(
+            HeaderName::from_static("x-rewritten"),
+            HeaderValue::from_static("true"),
+        );
+        Ok(response)
+    }
+}
+
+struct AsyncOnly;
+
+impl Middleware for AsyncOnly {
+    fn handle(&self, _request: GraphRequestBuilder, _next: Next) -> GraphResult<HttpResponse> {
+        panic!("Expected the async request to use handle_async");
+    }
+
+    fn handle_async(
+        self: Arc<Self>,
+        mut request: GraphRequestBuilder,
+        next: AsyncNext,
+    ) -> GraphFuture<HttpResponse> {
+        request.header(
+            HeaderName::from_static("x-middleware"),
+            HeaderValue::from_static("async"),
+        );
+        Box::new(next.run(request).map(|mut response| {
+            response.headers_mut().insert(
+                HeaderName::from_static("x-async"),
+                HeaderValue::from_static("true"),
+            );
+            response
+        }))
+    }
+}
+
+#[test]
+fn middleware_order() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({}),
+    )]);
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.add_middleware(Order {
+        name: "first",
+        calls: Arc::clone(&calls),
+    });
+    client.add_middleware(Order {
+        name: "second",
+        calls: Arc::clone(&calls),
+    });
+
+    client.v1().me().drive().get_item(ID).send().unwrap();
+    assert_eq!(vec!["first", "second"], *calls.lock().unwrap());
+
+    let requests = transport.requests();
+    assert_eq!(
+        Some("second"),
+        requests[0]
+            .headers
+            .get("x-middleware")
+            .and_then(|v| v.to_str().ok())
+    );
+    assert_eq!(Some("Bearer ACCESS_TOKEN"), requests[0].bearer());
+}
+
+#[test]
+fn middleware_rewrite() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({}),
+    )]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.add_middleware(Rewrite);
+
+    let response = client.v1().me().drive().get_item(ID).send().unwrap();
+    assert!(response.headers().contains_key("x-rewritten"));
+    assert_eq!(
+        format!("{}/users/rewritten/drive/items/{}", graph_rs::GRAPH_URL, ID),
+        transport.requests()[0].url
+    );
+}
+
+#[test]
+fn middleware_runs_once_per_request() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::TOO_MANY_REQUESTS, serde_json::json!({}))
+            .header("retry-after", "0"),
+        MockResponse::json(StatusCode::OK, serde_json::json!({})),
+    ]);
+    let count = Arc::new(AtomicUsize::new(0));
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.add_middleware(Count(Arc::clone(&count)));
+
+    client.v1().me().drive().get_item(ID).send().unwrap();
+    assert_eq!(1, count.load(Ordering::SeqCst));
+    assert_eq!(2, transport.requests().len());
+}
+
+#[test]
+fn async_middleware() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({}),
+    )]);
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let client = Graph::new_async_with_transport("ACCESS_TOKEN", transport.clone());
+    client.add_middleware(Order {
+        name: "first",
+        calls: Arc::clone(&calls),
+    });
+    client.add_middleware(Rewrite);
+
+    let response = client.v1().me().drive().get_item(ID).send().wait().unwrap();
+    assert!(response.headers().contains_key("x-rewritten"));
+    assert_eq!(vec!["first"], *calls.lock().unwrap());
+
+    let requests = transport.requests();
+    assert_eq!(
+        format!("{}/users/rewritten/drive/items/{}", graph_rs::GRAPH_URL, ID),
+        requests[0].url
+    );
+    assert_eq!(
+        Some("first"),
+        requests[0]
+            .headers
+            .get("x-middleware")
+            .and_then(|v| v.to_str().ok())
+    );
+}
+
+#[test]
+fn async_middleware_handle_async() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({}),
+    )]);
+    let client = Graph::new_async_with_transport("ACCESS_TOKEN", transport.clone());
+    client.add_middleware(AsyncOnly);
+
+    let response = client.v1().me().drive().get_item(ID).send().wait().unwrap();
+    assert!(response.headers().contains_key("x-async"));
+    assert_eq!(
+        Some("async"),
+        transport.requests()[0]
+            .headers
+            .get("x-middleware")
+            .and_then(|v| v.to_str().ok())
+    );
+}
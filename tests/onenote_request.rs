@@ -142,7 +142,7 @@ fn onenote_create_page_invalid_ext() {
         .send();
 
     if let Err(err) = response {
-        match err {
+        match err.failure() {
             GraphFailure::GraphRsError(err) => match err {
                 GraphRsError::InvalidFileExtension { requires, found } => {
                     assert_eq!("html", requires);
@@ -176,7 +176,7 @@ fn onenote_sections_create_page_invalid_ext() {
         .send();
 
     if let Err(err) = response {
-        match err {
+        match err.failure() {
             GraphFailure::GraphRsError(err) => match err {
                 GraphRsError::InvalidFileExtension { requires, found } => {
                     assert_eq!("html", requires);
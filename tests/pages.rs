@@ -0,0 +1,135 @@
+use graph_rs::prelude::*;
+use reqwest::StatusCode;
+use test_tools::transport::{MockResponse, MockTransport};
+
+static NEXT_LINK: &str = "https://graph.microsoft.com/v1.0/me/drive/root/children?$skiptoken=page2";
+
+fn page(names: &[&str], next_link: Option<&str>) -> MockResponse {
+    let value: Vec<serde_json::Value> = names
+        .iter()
+        .map(|name| serde_json::json!({ "name": name }))
+        .collect();
+    let mut body = serde_json::json!({ "value": value });
+    if let Some(next_link) = next_link {
+        body["@odata.nextLink"] = serde_json::Value::String(next_link.to_string());
+    }
+    MockResponse::json(StatusCode::OK, body)
+}
+
+fn two_pages() -> MockTransport {
+    MockTransport::new(vec![page(&["a", "b"], Some(NEXT_LINK)), page(&["c"], None)])
+}
+
+#[test]
+fn pages_follow_next_links() {
+    let transport = two_pages();
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    let pages: Vec<_> = client.v1().me().drive().root_children().pages().collect();
+    assert_eq!(2, pages.len());
+    assert_eq!(2, pages[0].as_ref().unwrap().value().len());
+    assert_eq!(1, pages[1].as_ref().unwrap().value().len());
+
+    let requests = transport.requests();
+    assert_eq!(2, requests.len());
+    assert_eq!(NEXT_LINK, requests[1].url);
+    assert_eq!(Some("Bearer ACCESS_TOKEN"), requests[1].bearer());
+}
+
+#[test]
+fn items_are_flattened() {
+    let client = Graph::with_transport("ACCESS_TOKEN", two_pages());
+    let names: Vec<String> = client
+        .v1()
+        .me()
+        .drive()
+        .root_children()
+        .items()
+        .map(|item| item.unwrap().name().clone().unwrap())
+        .collect();
+    assert_eq!(vec!["a", "b", "c"], names);
+}
+
+#[test]
+fn max_pages_and_items() {
+    let transport = two_pages();
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    let pages = client
+        .v1()
+        .me()
+        .drive()
+        .root_children()
+        .pages()
+        .max_pages(1)
+        .count();
+    assert_eq!(1, pages);
+    assert_eq!(1, transport.requests().len());
+
+    let transport = two_pages();
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    let items = client
+        .v1()
+        .me()
+        .drive()
+        .root_children()
+        .items()
+        .max_items(2)
+        .count();
+    assert_eq!(2, items);
+    assert_eq!(1, transport.requests().len());
+}
+
+#[test]
+fn pages_stop_after_error() {
+    let transport = MockTransport::new(vec![
+        page(&["a"], Some(NEXT_LINK)),
+        MockResponse::json(StatusCode::NOT_FOUND, serde_json::json!({})),
+        page(&["b"], None),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    let items: Vec<_> = client.v1().me().drive().root_children().items().collect();
+    assert_eq!(2, items.len());
+    assert!(items[0].is_ok());
+    assert!(items[1].is_err());
+    assert_eq!(2, transport.requests().len());
+}
+
+#[test]
+fn next_links_keep_request_headers() {
+    let transport = two_pages();
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.add_default_preference(Preference::MaxPageSize(2));
+
+    let pages: Vec<_> = client
+        .v1()
+        .me()
+        .drive()
+        .root_children()
+        .count(true)
+        .client_request_id("CLIENT_REQUEST_ID")
+        .pages()
+        .collect();
+    assert_eq!(2, pages.len());
+
+    let requests = transport.requests();
+    assert_eq!(NEXT_LINK, requests[1].url);
+    for request in requests.iter() {
+        assert_eq!(
+            Some("eventual"),
+            request
+                .headers
+                .get("consistencylevel")
+                .and_then(|v| v.to_str().ok())
+        );
+        assert_eq!(
+            Some("odata.maxpagesize=2"),
+            request.headers.get("prefer").and_then(|v| v.to_str().ok())
+        );
+        assert_eq!(Some("Bearer ACCESS_TOKEN"), request.bearer());
+    }
+    // Each page is a new request with its own client-request-id.
+    assert_ne!(
+        requests[0].headers.get("client-request-id"),
+        requests[1].headers.get("client-request-id")
+    );
+}
@@ -0,0 +1,142 @@
+use graph_rs::prelude::*;
+use graph_rs::types::prefer::PREFER;
+use reqwest::StatusCode;
+use test_tools::transport::{MockRequest, MockResponse, MockTransport};
+
+static NEXT_LINK: &str = "https://graph.microsoft.com/v1.0/me/messages?$skiptoken=page2";
+
+fn prefer(request: &MockRequest) -> Option<&str> {
+    request.headers.get(PREFER).and_then(|v| v.to_str().ok())
+}
+
+fn ok() -> MockResponse {
+    MockResponse::json(StatusCode::OK, serde_json::json!({ "value": [] }))
+}
+
+#[test]
+fn parse_and_display() {
+    let prefer = Prefer::parse(
+        "odata.maxpagesize=50, outlook.timezone=\"Pacific Standard Time\", \
+         outlook.body-content-type=\"text\", respond-async, IdType=\"ImmutableId\", \
+         return=minimal, odata.track-changes",
+    );
+    assert_eq!(
+        &[
+            Preference::MaxPageSize(50),
+            Preference::TimeZone("Pacific Standard Time".into()),
+            Preference::BodyContentType(BodyContentType::Text),
+            Preference::RespondAsync,
+            Preference::ImmutableId,
+            Preference::ReturnMinimal,
+            Preference::Other("odata.track-changes".into()),
+        ],
+        prefer.preferences()
+    );
+    assert_eq!(prefer, Prefer::parse(prefer.to_string().as_str()));
+}
+
+#[test]
+fn add_replaces_same_name() {
+    let mut prefer = Prefer::new();
+    prefer
+        .add(Preference::ReturnMinimal)
+        .add(Preference::MaxPageSize(10))
+        .add(Preference::ReturnRepresentation);
+    assert_eq!(
+        "return=representation, odata.maxpagesize=10",
+        prefer.to_string()
+    );
+}
+
+#[test]
+fn request_preferences() {
+    let transport = MockTransport::new(vec![ok()]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    client
+        .v1()
+        .me()
+        .mail()
+        .messages()
+        .list()
+        .prefer(Preference::MaxPageSize(25))
+        .prefer(Preference::BodyContentType(BodyContentType::Text))
+        .send()
+        .unwrap();
+
+    assert_eq!(
+        Some("odata.maxpagesize=25, outlook.body-content-type=\"text\""),
+        prefer(&transport.requests()[0])
+    );
+}
+
+#[test]
+fn default_preferences() {
+    let transport = MockTransport::new(vec![ok(), ok()]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.add_default_preference(Preference::ImmutableId);
+    client.add_default_preference(Preference::TimeZone("UTC".into()));
+
+    client.v1().me().mail().messages().list().send().unwrap();
+    client
+        .v1()
+        .me()
+        .mail()
+        .messages()
+        .list()
+        .prefer(Preference::TimeZone("Pacific Standard Time".into()))
+        .send()
+        .unwrap();
+
+    let requests = transport.requests();
+    assert_eq!(
+        Some("IdType=\"ImmutableId\", outlook.timezone=\"UTC\""),
+        prefer(&requests[0])
+    );
+    assert_eq!(
+        Some("IdType=\"ImmutableId\", outlook.timezone=\"Pacific Standard Time\""),
+        prefer(&requests[1])
+    );
+}
+
+#[test]
+fn next_links_keep_preferences() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(
+            StatusCode::OK,
+            serde_json::json!({ "value": [{}], "@odata.nextLink": NEXT_LINK }),
+        ),
+        ok(),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    let pages: Vec<_> = client
+        .v1()
+        .me()
+        .mail()
+        .messages()
+        .list()
+        .prefer(Preference::MaxPageSize(1))
+        .pages()
+        .collect();
+    assert_eq!(2, pages.len());
+
+    let requests = transport.requests();
+    assert_eq!(NEXT_LINK, requests[1].url);
+    assert_eq!(Some("odata.maxpagesize=1"), prefer(&requests[1]));
+}
+
+#[test]
+fn preference_applied() {
+    let transport = MockTransport::new(vec![ok().header(
+        "preference-applied",
+        "odata.maxpagesize=25, IdType=\"ImmutableId\"",
+    )]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport);
+
+    let response = client.v1().me().mail().messages().list().send().unwrap();
+    let applied = response.preference_applied();
+    assert!(applied.contains(&Preference::MaxPageSize(25)));
+    assert!(applied.contains(&Preference::ImmutableId));
+    assert!(!applied.contains(&Preference::ReturnMinimal));
+}
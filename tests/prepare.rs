@@ -0,0 +1,99 @@
+use graph_rs::http::{PreparedRequest, REDACTED};
+use graph_rs::prelude::*;
+use reqwest::StatusCode;
+use test_tools::transport::{MockResponse, MockTransport};
+
+static ID: &str = "b!CbtYWrofwUGBJWnaJkNwoNrBLp_kC3RKklSXPwrdeP3yH8_qmH9xT5Y6RODPNfYI";
+
+fn prepare_update(client: &Graph) -> PreparedRequest {
+    client
+        .v1()
+        .me()
+        .drive()
+        .update(ID, &serde_json::json!({ "name": "new name" }))
+        .prepare()
+        .unwrap()
+}
+
+#[test]
+fn prepare_does_not_send() {
+    let transport = MockTransport::new(vec![]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    let prepared = prepare_update(&client);
+
+    assert_eq!("PATCH", prepared.method());
+    assert_eq!(
+        &format!("{}/me/drive/items/{}", graph_rs::GRAPH_URL, ID),
+        prepared.url()
+    );
+    assert_eq!(
+        Some(&"application/json".to_string()),
+        prepared.headers().get("content-type")
+    );
+    assert_eq!(
+        Some(&REDACTED.to_string()),
+        prepared.headers().get("authorization")
+    );
+    assert_eq!(Some("{\"name\":\"new name\"}"), prepared.body_text());
+    assert!(transport.requests().is_empty());
+}
+
+#[test]
+fn prepared_request_redacts_token() {
+    let client = Graph::with_transport("ACCESS_TOKEN", MockTransport::new(vec![]));
+    let prepared = prepare_update(&client);
+
+    let json = serde_json::to_string(&prepared).unwrap();
+    assert!(!json.contains("ACCESS_TOKEN"));
+    let deserialized: PreparedRequest = serde_json::from_str(json.as_str()).unwrap();
+    assert_eq!(prepared, deserialized);
+}
+
+#[test]
+fn send_prepared() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({ "name": "new name" }),
+    )]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    let prepared = prepare_update(&client);
+
+    let response: GraphResponse<serde_json::Value> = client.send_prepared(&prepared).unwrap();
+    assert_eq!("new name", response.value()["name"]);
+
+    let requests = transport.requests();
+    assert_eq!(1, requests.len());
+    assert_eq!(reqwest::Method::PATCH, requests[0].method);
+    assert_eq!(prepared.url(), &requests[0].url);
+    assert_eq!(Some("Bearer ACCESS_TOKEN"), requests[0].bearer());
+}
+
+#[test]
+fn prepare_adds_pipeline_headers() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({ "name": "new name" }),
+    )]);
+    let client = GraphClientBuilder::new()
+        .user_agent("my-app/1.0")
+        .transport(transport.clone())
+        .build("ACCESS_TOKEN")
+        .unwrap();
+    let prepared = prepare_update(&client);
+
+    let header = |name: &str| prepared.headers().get(name).map(|v| v.as_str());
+    assert_eq!(Some("my-app/1.0"), header("user-agent"));
+    assert_eq!(Some("gzip, deflate"), header("accept-encoding"));
+    assert_eq!(Some("true"), header("return-client-request-id"));
+    let id = header("client-request-id").unwrap();
+    assert!(transport.requests().is_empty());
+
+    let _: GraphResponse<serde_json::Value> = client.send_prepared(&prepared).unwrap();
+    assert_eq!(
+        Some(id),
+        transport.requests()[0]
+            .headers
+            .get("client-request-id")
+            .and_then(|v| v.to_str().ok())
+    );
+}
@@ -0,0 +1,172 @@
+use graph_rs::prelude::*;
+use graph_rs::url::{Expand, Filter, GraphUrl};
+use reqwest::StatusCode;
+use std::str::FromStr;
+use test_tools::transport::{MockResponse, MockTransport};
+
+static BASE: &str = "https://graph.microsoft.com/v1.0/me/drive/root/children";
+
+#[test]
+fn system_query_options() {
+    let client = Graph::new("");
+    let _ = client
+        .v1()
+        .me()
+        .drive()
+        .root_children()
+        .select(&["id", "name"])
+        .order_by(&["name desc", "size"])
+        .skip("10")
+        .top("5")
+        .skip_token("X'4453707402'");
+    client.url_ref(|url| {
+        assert_eq!(
+            format!(
+                "{}?$select=id%2Cname&$orderby=name+desc%2Csize&$skip=10&$top=5&$skiptoken=X%274453707402%27",
+                BASE
+            ),
+            url.as_str()
+        );
+    });
+}
+
+#[test]
+fn nested_expand() {
+    let client = Graph::new("");
+    let _ = client.v1().me().drive().root_children().expand_with(&[
+        Expand::new("children")
+            .select(&["id"])
+            .expand(Expand::new("thumbnails").select(&["large"])),
+        Expand::from("permissions"),
+    ]);
+    client.url_ref(|url| {
+        let url = url.to_url();
+        let expand: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+        assert_eq!(
+            vec![(
+                "$expand".to_string(),
+                "children($select=id;$expand=thumbnails($select=large)),permissions".to_string()
+            )],
+            expand
+        );
+    });
+
+    let expand = Expand::new("members")
+        .filter(Filter::eq("accountEnabled", true))
+        .order_by(&["displayName"])
+        .top(3);
+    assert_eq!(
+        "members($filter=accountEnabled eq true;$orderby=displayName;$top=3)",
+        expand.to_string()
+    );
+}
+
+#[test]
+fn count_adds_consistency_level() {
+    let client = Graph::new("");
+    let prepared = client
+        .v1()
+        .me()
+        .drive()
+        .root_children()
+        .count(true)
+        .prepare()
+        .unwrap();
+    assert_eq!(format!("{}?$count=true", BASE), prepared.url().as_str());
+    assert_eq!(
+        Some(&"eventual".to_string()),
+        prepared.headers().get("consistencylevel")
+    );
+
+    let prepared = client
+        .v1()
+        .me()
+        .drive()
+        .root_children()
+        .count(false)
+        .prepare()
+        .unwrap();
+    assert_eq!(format!("{}?$count=false", BASE), prepared.url().as_str());
+    assert!(prepared.headers().get("consistencylevel").is_none());
+}
+
+#[test]
+#[allow(deprecated)]
+fn graph_url_count() {
+    let mut url = GraphUrl::from_str(BASE).unwrap();
+    url.count_items(true);
+    assert_eq!(format!("{}?$count=true", BASE), url.as_str());
+
+    let mut url = GraphUrl::from_str(BASE).unwrap();
+    url.count("true");
+    assert_eq!(format!("{}?$count=true", BASE), url.as_str());
+}
+
+#[test]
+fn search_is_quoted() {
+    let client = Graph::new("");
+    let prepared = client
+        .v1()
+        .me()
+        .drive()
+        .root_children()
+        .search("pizza")
+        .prepare()
+        .unwrap();
+    assert_eq!(
+        format!("{}?$search=%22pizza%22", BASE),
+        prepared.url().as_str()
+    );
+    assert_eq!(
+        Some(&"eventual".to_string()),
+        prepared.headers().get("consistencylevel")
+    );
+
+    let _ = client
+        .v1()
+        .me()
+        .drive()
+        .root_children()
+        .search("\"displayName:a\" OR \"mail:a\"");
+    client.url_ref(|url| {
+        let url = url.to_url();
+        let (key, value) = url.query_pairs().next().unwrap();
+        assert_eq!("$search", key);
+        assert_eq!("\"displayName:a\" OR \"mail:a\"", value);
+    });
+}
+
+#[test]
+fn consistency_level_on_delta_next_links() {
+    let next_link = format!(
+        "{}/groups/delta?$search=%22pizza%22&$skiptoken=token",
+        graph_rs::GRAPH_URL
+    );
+    let transport = MockTransport::new(vec![
+        MockResponse::json(
+            StatusCode::OK,
+            serde_json::json!({ "@odata.nextLink": next_link, "value": [] }),
+        ),
+        MockResponse::json(StatusCode::OK, serde_json::json!({ "value": [] })),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    let recv = client.v1().groups("").delta().search("pizza").send();
+    loop {
+        if let Delta::Done(err) = recv.recv().unwrap() {
+            assert!(err.is_none());
+            break;
+        }
+    }
+
+    let requests = transport.requests();
+    assert_eq!(2, requests.len());
+    assert_eq!(next_link, requests[1].url);
+    assert_eq!(
+        Some("eventual"),
+        requests[1]
+            .headers
+            .get("consistencylevel")
+            .and_then(|v| v.to_str().ok())
+    );
+}
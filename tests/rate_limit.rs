@@ -0,0 +1,153 @@
+use graph_rs::futures::{future, Future};
+use graph_rs::http::{
+    GraphRequestBuilder, HttpResponse, HttpTransport, Middleware, Pipeline, RateLimiter, Workload,
+};
+use graph_rs::prelude::*;
+use reqwest::StatusCode;
+use std::io::Cursor;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::channel;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use test_tools::transport::{MockResponse, MockTransport};
+use url::Url;
+
+/// Takes a while to respond and records the most requests in flight at once.
+#[derive(Default)]
+struct SlowTransport {
+    in_flight: AtomicUsize,
+    max_in_flight: Mutex<usize>,
+}
+
+impl HttpTransport for SlowTransport {
+    fn send(&self, request: GraphRequestBuilder) -> GraphResult<HttpResponse> {
+        let current = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        let mut max = self.max_in_flight.lock().unwrap();
+        *max = (*max).max(current);
+        drop(max);
+        thread::sleep(Duration::from_millis(50));
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        Ok(HttpResponse::new(
+            StatusCode::OK,
+            request.to_url(),
+            Default::default(),
+            Cursor::new(b"{}".to_vec()),
+        ))
+    }
+}
+
+fn ok_responses(count: usize) -> MockTransport {
+    MockTransport::new(
+        (0..count)
+            .map(|_| MockResponse::json(StatusCode::OK, serde_json::json!({})))
+            .collect(),
+    )
+}
+
+#[test]
+fn workload_from_url() {
+    let workload = |path: &str| {
+        Workload::from_url(
+            &Url::parse(&format!("https://graph.microsoft.com/v1.0/{}", path)).unwrap(),
+        )
+    };
+    assert_eq!(Workload::Drive, workload("me/drive/root/children"));
+    assert_eq!(Workload::Drive, workload("users/ID/drive"));
+    assert_eq!(Workload::Drive, workload("sites/root"));
+    assert_eq!(Workload::Mail, workload("me/messages"));
+    assert_eq!(
+        Workload::Mail,
+        workload("users/ID/mailFolders/inbox/messages")
+    );
+    assert_eq!(Workload::Directory, workload("users/ID"));
+    assert_eq!(Workload::Directory, workload("groups"));
+    assert_eq!(Workload::Other, workload("me/settings"));
+}
+
+#[test]
+fn rate_limit_queues_requests() {
+    let client = Graph::with_transport("ACCESS_TOKEN", ok_responses(3));
+    client.set_rate_limiter(RateLimiter::new().rate_limit(1, Duration::from_millis(100)));
+
+    let start = Instant::now();
+    for _ in 0..3 {
+        client.v1().me().get().send().unwrap();
+    }
+    assert!(start.elapsed() >= Duration::from_millis(190));
+}
+
+#[test]
+fn workload_rate_limit_only_applies_to_workload() {
+    let client = Graph::with_transport("ACCESS_TOKEN", ok_responses(3));
+    client.set_rate_limiter(RateLimiter::new().workload_rate_limit(
+        Workload::Drive,
+        1,
+        Duration::from_secs(60),
+    ));
+
+    let start = Instant::now();
+    client.v1().me().drive().drive().send().unwrap();
+    client.v1().me().get().send().unwrap();
+    client.v1().users("ID").get().send().unwrap();
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn max_concurrent_requests() {
+    let transport = Arc::new(SlowTransport::default());
+    let client = Arc::new(Graph::with_transport(
+        "ACCESS_TOKEN",
+        ArcTransport(Arc::clone(&transport)),
+    ));
+    client.set_rate_limiter(RateLimiter::new().max_concurrent(2));
+
+    let handles: Vec<_> = (0..6)
+        .map(|_| {
+            let client = Arc::clone(&client);
+            thread::spawn(move || client.v1().me().get().send().unwrap())
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert!(*transport.max_in_flight.lock().unwrap() <= 2);
+}
+
+#[test]
+fn async_max_concurrent_requests() {
+    let transport = Arc::new(SlowTransport::default());
+    let client =
+        Graph::new_async_with_transport("ACCESS_TOKEN", ArcTransport(Arc::clone(&transport)));
+    client.set_rate_limiter(RateLimiter::new().max_concurrent(2));
+
+    let requests: Vec<_> = (0..6).map(|_| client.v1().me().get().send()).collect();
+    future::join_all(requests).wait().unwrap();
+    assert!(*transport.max_in_flight.lock().unwrap() <= 2);
+}
+
+#[test]
+fn max_concurrent_holds_permit_until_body_is_read() {
+    let limiter: Arc<dyn Middleware> = Arc::new(RateLimiter::new().max_concurrent(1));
+    let pipeline = Arc::new(Pipeline::new(vec![limiter], Arc::new(ok_responses(2))));
+    let mut first = pipeline.send(GraphRequestBuilder::default()).unwrap();
+
+    let (sender, receiver) = channel();
+    let second = Arc::clone(&pipeline);
+    thread::spawn(move || {
+        let response = second.send(GraphRequestBuilder::default());
+        sender.send(response.is_ok()).unwrap();
+    });
+    assert!(receiver.recv_timeout(Duration::from_millis(200)).is_err());
+
+    assert_eq!("{}", first.text().unwrap());
+    assert!(receiver.recv_timeout(Duration::from_secs(5)).unwrap());
+}
+
+struct ArcTransport(Arc<SlowTransport>);
+
+impl HttpTransport for ArcTransport {
+    fn send(&self, request: GraphRequestBuilder) -> GraphResult<HttpResponse> {
+        self.0.send(request)
+    }
+}
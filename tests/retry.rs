@@ -0,0 +1,193 @@
+use graph_error::GraphFailure;
+use graph_rs::futures::{Future, Stream};
+use graph_rs::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+use graph_rs::http::RetryPolicy;
+use graph_rs::prelude::*;
+use reqwest::StatusCode;
+use std::time::Duration;
+use test_tools::transport::{MockResponse, MockTransport};
+
+static ID: &str = "b!CbtYWrofwUGBJWnaJkNwoNrBLp_kC3RKklSXPwrdeP3yH8_qmH9xT5Y6RODPNfYI";
+
+fn fast_policy(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy::new(
+        max_attempts,
+        Duration::from_millis(1),
+        Duration::from_millis(4),
+    )
+}
+
+#[test]
+fn retry_after_header() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::TOO_MANY_REQUESTS, serde_json::json!({}))
+            .header("retry-after", "0"),
+        MockResponse::json(StatusCode::OK, serde_json::json!({ "id": ID })),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    let response = client.v1().me().drive().get_item(ID).send().unwrap();
+    assert_eq!(Some(ID), response.value().id().as_deref());
+    assert_eq!(2, transport.requests().len());
+}
+
+#[test]
+fn retry_backoff_max_attempts() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::SERVICE_UNAVAILABLE, serde_json::json!({})),
+        MockResponse::json(StatusCode::GATEWAY_TIMEOUT, serde_json::json!({})),
+        MockResponse::json(StatusCode::SERVICE_UNAVAILABLE, serde_json::json!({})),
+        MockResponse::json(StatusCode::OK, serde_json::json!({})),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.set_retry_policy(fast_policy(3));
+
+    match client.v1().me().drive().get_item(ID).send() {
+        Err(GraphFailure::GraphError(err)) => assert_eq!(503, err.code),
+        other => panic!("Expected a GraphError. Got: {:#?}", other),
+    }
+    assert_eq!(3, transport.requests().len());
+}
+
+#[test]
+fn no_retry_for_other_errors() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::BAD_REQUEST, serde_json::json!({})),
+        MockResponse::json(StatusCode::OK, serde_json::json!({})),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    assert!(client.v1().me().drive().get_item(ID).send().is_err());
+    assert_eq!(1, transport.requests().len());
+}
+
+#[test]
+fn retry_policy_none() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::TOO_MANY_REQUESTS,
+        serde_json::json!({}),
+    )
+    .header("retry-after", "0")]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.set_retry_policy(RetryPolicy::none());
+
+    assert!(client.v1().me().drive().get_item(ID).send().is_err());
+    assert_eq!(1, transport.requests().len());
+}
+
+#[test]
+fn async_retry_after_header() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::TOO_MANY_REQUESTS, serde_json::json!({}))
+            .header("retry-after", "0"),
+        MockResponse::json(StatusCode::OK, serde_json::json!({ "id": ID })),
+    ]);
+    let client = Graph::new_async_with_transport("ACCESS_TOKEN", transport.clone());
+
+    let response = client.v1().me().drive().get_item(ID).send().wait().unwrap();
+    assert_eq!(Some(ID), response.value().id().as_deref());
+    assert_eq!(2, transport.requests().len());
+}
+
+#[test]
+fn async_retry_next_links() {
+    let next_link = format!("{}/me/drive/root/delta?token=1", graph_rs::GRAPH_URL);
+    let transport = MockTransport::new(vec![
+        MockResponse::json(
+            StatusCode::OK,
+            serde_json::json!({ "@odata.nextLink": next_link, "value": [] }),
+        ),
+        MockResponse::json(StatusCode::SERVICE_UNAVAILABLE, serde_json::json!({})),
+        MockResponse::json(StatusCode::OK, serde_json::json!({ "value": [] })),
+    ]);
+    let client = Graph::new_async_with_transport("ACCESS_TOKEN", transport.clone());
+    client.set_retry_policy(fast_policy(2));
+
+    // Retry delays run on the tokio timer.
+    let mut runtime = tokio::runtime::Runtime::new().unwrap();
+    let pages = runtime
+        .block_on(client.v1().me().drive().delta().send().collect())
+        .unwrap();
+    assert_eq!(2, pages.len());
+    assert_eq!(3, transport.requests().len());
+}
+
+#[test]
+fn retry_delay() {
+    let mut policy = fast_policy(4);
+    policy.set_jitter(false);
+    let headers = HeaderMap::new();
+    assert_eq!(Duration::from_millis(1), policy.delay(1, &headers));
+    assert_eq!(Duration::from_millis(2), policy.delay(2, &headers));
+    assert_eq!(Duration::from_millis(4), policy.delay(3, &headers));
+    assert_eq!(Duration::from_millis(4), policy.delay(10, &headers));
+
+    let mut headers = HeaderMap::new();
+    headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+    assert_eq!(
+        Duration::from_secs(7),
+        RetryPolicy::default().delay(1, &headers)
+    );
+    // Retry-After is not capped at the max delay.
+    assert_eq!(Duration::from_secs(7), policy.delay(1, &headers));
+}
+
+#[test]
+fn retry_after_longer_than_max_delay() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::TOO_MANY_REQUESTS, serde_json::json!({}))
+            .header("retry-after", "86400"),
+        MockResponse::json(StatusCode::OK, serde_json::json!({})),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    match client.v1().me().drive().get_item(ID).send() {
+        Err(GraphFailure::GraphError(err)) => assert_eq!(429, err.code),
+        other => panic!("Expected a GraphError. Got: {:#?}", other),
+    }
+    assert_eq!(1, transport.requests().len());
+}
+
+#[test]
+fn no_retry_post_on_gateway_timeout() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::GATEWAY_TIMEOUT, serde_json::json!({})),
+        MockResponse::json(StatusCode::ACCEPTED, serde_json::json!({})),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    client.set_retry_policy(fast_policy(3));
+
+    let response = client
+        .v1()
+        .me()
+        .drive()
+        .copy(ID, &serde_json::json!({ "name": "copy.txt" }))
+        .send();
+    match response {
+        Err(GraphFailure::GraphError(err)) => assert_eq!(504, err.code),
+        other => panic!("Expected a GraphError. Got: {:#?}", other),
+    }
+    assert_eq!(1, transport.requests().len());
+}
+
+#[test]
+fn retry_post_on_gateway_timeout() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::GATEWAY_TIMEOUT, serde_json::json!({})),
+        MockResponse::json(StatusCode::ACCEPTED, serde_json::json!({}))
+            .header("location", "https://api.example.com/monitor"),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+    let mut policy = fast_policy(3);
+    policy.set_retry_gateway_timeout(true);
+    client.set_retry_policy(policy);
+
+    client
+        .v1()
+        .me()
+        .drive()
+        .copy(ID, &serde_json::json!({ "name": "copy.txt" }))
+        .send()
+        .unwrap();
+    assert_eq!(2, transport.requests().len());
+}
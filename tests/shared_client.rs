@@ -0,0 +1,76 @@
+use graph_rs::prelude::*;
+use graph_rs::{GRAPH_URL, GRAPH_URL_BETA};
+use std::sync::Arc;
+use std::thread;
+
+static RID: &str = "T5Y6RODPNfYICbtYWrofwUGBJWnaJkNwH9x";
+static ID: &str = "b!CbtYWrofwUGBJWnaJkNwoNrBLp_kC3RKklSXPwrdeP3yH8_qmH9xT5Y6RODPNfYI";
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn graph_is_send_sync() {
+    assert_send_sync::<Graph>();
+    assert_send_sync::<AsyncGraph>();
+}
+
+#[test]
+fn concurrent_request_chains() {
+    let client = Graph::new("");
+    let drive = client.v1().me().drive();
+    let messages = client.beta().users(RID).mail().messages();
+
+    let get_item = drive.get_item(ID);
+    let list = messages.list();
+    let root = drive.root();
+
+    get_item.url_ref(|url| {
+        assert_eq!(
+            format!("{}/me/drive/items/{}", GRAPH_URL, ID),
+            url.to_string()
+        );
+    });
+    list.url_ref(|url| {
+        assert_eq!(
+            format!("{}/users/{}/messages", GRAPH_URL_BETA, RID),
+            url.to_string()
+        );
+    });
+    root.url_ref(|url| {
+        assert_eq!(format!("{}/me/drive/root", GRAPH_URL), url.to_string());
+    });
+}
+
+#[test]
+fn shared_across_threads() {
+    let client = Arc::new(Graph::new(""));
+
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let client = Arc::clone(&client);
+            thread::spawn(move || {
+                let id = format!("{}{}", ID, i);
+                let request = if i % 2 == 0 {
+                    client.v1().drives(RID).drive().get_item(id.as_str())
+                } else {
+                    client.beta().drives(RID).drive().get_item(id.as_str())
+                };
+                let host = if i % 2 == 0 {
+                    GRAPH_URL
+                } else {
+                    GRAPH_URL_BETA
+                };
+                request.url_ref(|url| {
+                    assert_eq!(
+                        format!("{}/drives/{}/items/{}", host, RID, id),
+                        url.to_string()
+                    );
+                });
+            })
+        })
+        .collect();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,160 @@
+use flate2::write::GzEncoder;
+use graph_rs::futures::Future;
+use graph_rs::http::{RequestEvent, TelemetrySink};
+use graph_rs::prelude::*;
+use reqwest::StatusCode;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use test_tools::transport::{MockResponse, MockTransport};
+use url::Url;
+
+#[derive(Clone, Default)]
+struct Events(Arc<Mutex<Vec<RequestEvent>>>);
+
+impl TelemetrySink for Events {
+    fn on_request(&self, event: &RequestEvent) {
+        self.0.lock().unwrap().push(event.clone());
+    }
+}
+
+#[test]
+fn telemetry_event_per_request() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::TOO_MANY_REQUESTS, serde_json::json!({}))
+            .header("retry-after", "0"),
+        MockResponse::json(StatusCode::OK, serde_json::json!({ "id": "ITEM_ID" }))
+            .header("request-id", "REQUEST_ID")
+            .header("client-request-id", "CLIENT_REQUEST_ID")
+            .header("x-ms-ags-diagnostic", "{\"ServerInfo\":{}}")
+            .header("content-length", "17"),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport);
+    let events = Events::default();
+    client.add_telemetry_sink(events.clone());
+
+    client
+        .v1()
+        .me()
+        .drive()
+        .get_item("01BYE5RZ6QN3ZWBTUFOFD3GSPGOHDJD36K")
+        .send()
+        .unwrap();
+
+    let events = events.0.lock().unwrap();
+    assert_eq!(1, events.len());
+    let event = &events[0];
+    assert_eq!("GET", event.method());
+    assert_eq!("/me/drive/items/{id}", event.path());
+    assert_eq!(&Some(200), event.status());
+    assert_eq!(&1, event.retries());
+    assert_eq!(&Some(17), event.response_bytes());
+    assert_eq!(&Some("REQUEST_ID".to_string()), event.request_id());
+    assert_eq!(
+        &Some("CLIENT_REQUEST_ID".to_string()),
+        event.client_request_id()
+    );
+    assert!(event.diagnostic().is_some());
+    assert!(event.error().is_none());
+}
+
+#[test]
+fn telemetry_compressed_response_bytes() {
+    let mut response = MockResponse::json(StatusCode::OK, serde_json::json!({ "id": "ITEM_ID" }))
+        .header("content-encoding", "gzip");
+    let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&response.body).unwrap();
+    response.body = encoder.finish().unwrap();
+    let len = response.body.len();
+    let response = response.header("content-length", len.to_string().as_str());
+
+    let client = Graph::with_transport("ACCESS_TOKEN", MockTransport::new(vec![response]));
+    let events = Events::default();
+    client.add_telemetry_sink(events.clone());
+
+    let item = client
+        .v1()
+        .me()
+        .drive()
+        .get_item("01BYE5RZ6QN3ZWBTUFOFD3GSPGOHDJD36K")
+        .send()
+        .unwrap();
+    assert_eq!(Some("ITEM_ID"), item.value().id().as_deref());
+
+    let events = events.0.lock().unwrap();
+    assert_eq!(&Some(len as u64), events[0].response_bytes());
+}
+
+#[test]
+fn telemetry_request_bytes_and_errors() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::NOT_FOUND,
+        serde_json::json!({}),
+    )]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport);
+    let events = Events::default();
+    client.add_telemetry_sink(events.clone());
+
+    let body = serde_json::json!({ "name": "name" });
+    let _ = client.v1().me().drive().update("ITEM_ID", &body).send();
+    // The mock transport has no more responses so this request fails.
+    let _ = client.v1().me().get().send();
+
+    let events = events.0.lock().unwrap();
+    assert_eq!(2, events.len());
+    assert_eq!(&Some(404), events[0].status());
+    assert_eq!(
+        &Some(body.to_string().len() as u64),
+        events[0].request_bytes()
+    );
+    assert_eq!(&None, events[1].status());
+    assert!(events[1].error().is_some());
+}
+
+#[test]
+fn async_telemetry_event_per_request() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::SERVICE_UNAVAILABLE, serde_json::json!({}))
+            .header("retry-after", "0"),
+        MockResponse::json(StatusCode::OK, serde_json::json!({ "id": "ITEM_ID" }))
+            .header("request-id", "REQUEST_ID"),
+    ]);
+    let client = Graph::new_async_with_transport("ACCESS_TOKEN", transport);
+    let events = Events::default();
+    client.add_telemetry_sink(events.clone());
+
+    client
+        .v1()
+        .me()
+        .drive()
+        .get_item("01BYE5RZ6QN3ZWBTUFOFD3GSPGOHDJD36K")
+        .send()
+        .wait()
+        .unwrap();
+
+    let events = events.0.lock().unwrap();
+    assert_eq!(1, events.len());
+    assert_eq!("/me/drive/items/{id}", events[0].path());
+    assert_eq!(&Some(200), events[0].status());
+    assert_eq!(&1, events[0].retries());
+    assert_eq!(&Some("REQUEST_ID".to_string()), events[0].request_id());
+    assert!(events[0].client_request_id().is_some());
+}
+
+#[test]
+fn path_template() {
+    let path = |url: &str| RequestEvent::path_template(&Url::parse(url).unwrap());
+    assert_eq!(
+        "/users/{id}/messages",
+        path(
+            "https://graph.microsoft.com/v1.0/users/48d31887-5fad-4d73-a9f5-3c356e68a038/messages"
+        )
+    );
+    assert_eq!(
+        "/me/drive/root/children",
+        path("https://graph.microsoft.com/beta/me/drive/root/children?$top=3")
+    );
+    assert_eq!(
+        "/drives/{id}/items/{id}",
+        path("https://graph.microsoft.com/v1.0/drives/b!abc/items/ITEM_ID_1")
+    );
+}
@@ -0,0 +1,125 @@
+use graph_error::GraphResult;
+use graph_oauth::oauth::{AccessToken, GrantType, OAuth, OAuthTokenProvider, TokenProvider};
+use graph_rs::futures::Future;
+use graph_rs::prelude::*;
+use reqwest::StatusCode;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use test_tools::transport::{MockResponse, MockTransport};
+
+static ID: &str = "b!CbtYWrofwUGBJWnaJkNwoNrBLp_kC3RKklSXPwrdeP3yH8_qmH9xT5Y6RODPNfYI";
+
+struct Rotating {
+    refreshed: Arc<AtomicUsize>,
+    can_refresh: bool,
+}
+
+impl TokenProvider for Rotating {
+    fn bearer_token(&self) -> GraphResult<String> {
+        Ok(format!("token-{}", self.refreshed.load(Ordering::SeqCst)))
+    }
+
+    fn refresh(&self) -> GraphResult<Option<String>> {
+        if !self.can_refresh {
+            return Ok(None);
+        }
+        let count = self.refreshed.fetch_add(1, Ordering::SeqCst) + 1;
+        Ok(Some(format!("token-{}", count)))
+    }
+}
+
+#[test]
+fn refresh_on_unauthorized() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::UNAUTHORIZED, serde_json::json!({})),
+        MockResponse::json(StatusCode::OK, serde_json::json!({})),
+    ]);
+    let refreshed = Arc::new(AtomicUsize::new(0));
+    let client = Graph::with_transport("", transport.clone());
+    client.set_token_provider(Rotating {
+        refreshed: Arc::clone(&refreshed),
+        can_refresh: true,
+    });
+
+    client.v1().me().drive().get_item(ID).send().unwrap();
+    let requests = transport.requests();
+    assert_eq!(2, requests.len());
+    assert_eq!(Some("Bearer token-0"), requests[0].bearer());
+    assert_eq!(Some("Bearer token-1"), requests[1].bearer());
+    assert_eq!(1, refreshed.load(Ordering::SeqCst));
+
+    // The refreshed token is used for the next request.
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({}),
+    )]);
+    let client = Graph::with_transport("", transport.clone());
+    client.set_token_provider(Rotating {
+        refreshed,
+        can_refresh: true,
+    });
+    client.v1().me().drive().get_item(ID).send().unwrap();
+    assert_eq!(Some("Bearer token-1"), transport.requests()[0].bearer());
+}
+
+#[test]
+fn unauthorized_without_refresh() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::UNAUTHORIZED, serde_json::json!({})),
+        MockResponse::json(StatusCode::OK, serde_json::json!({})),
+    ]);
+    let client = Graph::with_transport("", transport.clone());
+    client.set_token_provider(Rotating {
+        refreshed: Arc::new(AtomicUsize::new(0)),
+        can_refresh: false,
+    });
+
+    assert!(client.v1().me().drive().get_item(ID).send().is_err());
+    assert_eq!(1, transport.requests().len());
+}
+
+#[test]
+fn async_refresh_on_unauthorized() {
+    let transport = MockTransport::new(vec![
+        MockResponse::json(StatusCode::UNAUTHORIZED, serde_json::json!({})),
+        MockResponse::json(StatusCode::OK, serde_json::json!({})),
+    ]);
+    let refreshed = Arc::new(AtomicUsize::new(0));
+    let client = Graph::new_async_with_transport("", transport.clone());
+    client.set_token_provider(Rotating {
+        refreshed: Arc::clone(&refreshed),
+        can_refresh: true,
+    });
+
+    client.v1().me().drive().get_item(ID).send().wait().unwrap();
+    let requests = transport.requests();
+    assert_eq!(2, requests.len());
+    assert_eq!(Some("Bearer token-0"), requests[0].bearer());
+    assert_eq!(Some("Bearer token-1"), requests[1].bearer());
+    assert_eq!(1, refreshed.load(Ordering::SeqCst));
+}
+
+#[test]
+fn oauth_provider_uses_unexpired_token() {
+    let mut oauth = OAuth::new();
+    oauth.access_token(AccessToken::new(
+        "Bearer",
+        3600,
+        "Files.Read",
+        "ACCESS_TOKEN",
+    ));
+    let provider = OAuthTokenProvider::new(oauth, GrantType::AuthorizationCode);
+    assert_eq!("ACCESS_TOKEN", provider.bearer_token().unwrap());
+
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({}),
+    )]);
+    let client = Graph::with_transport("", transport.clone());
+    client.set_token_provider(provider);
+    client.v1().me().drive().get_item(ID).send().unwrap();
+    assert_eq!(
+        Some("Bearer ACCESS_TOKEN"),
+        transport.requests()[0].bearer()
+    );
+}
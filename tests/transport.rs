@@ -0,0 +1,254 @@
+use graph_error::{GraphFailure, GraphResult};
+use graph_rs::futures::{future, stream, Future, Stream};
+use graph_rs::header::HeaderMap;
+use graph_rs::http::{
+    AsyncNextSession, GraphFuture, GraphRequestBuilder, HttpResponse, HttpTransport, Session,
+};
+use graph_rs::prelude::*;
+use graph_rs::GRAPH_URL;
+use reqwest::{Method, StatusCode};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use test_tools::transport::{MockResponse, MockTransport};
+
+static RID: &str = "T5Y6RODPNfYICbtYWrofwUGBJWnaJkNwH9x";
+static ID: &str = "b!CbtYWrofwUGBJWnaJkNwoNrBLp_kC3RKklSXPwrdeP3yH8_qmH9xT5Y6RODPNfYI";
+
+#[test]
+fn transport_send() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({ "id": ID, "name": "item" }),
+    )]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    let response = client.v1().me().drive().get_item(ID).send().unwrap();
+    assert_eq!(Some("item"), response.value().name().as_deref());
+
+    let requests = transport.requests();
+    assert_eq!(1, requests.len());
+    assert_eq!(Method::GET, requests[0].method);
+    assert_eq!(
+        format!("{}/me/drive/items/{}", GRAPH_URL, ID),
+        requests[0].url
+    );
+    assert_eq!(Some("Bearer ACCESS_TOKEN"), requests[0].bearer());
+}
+
+#[test]
+fn transport_error() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::NOT_FOUND,
+        serde_json::json!({ "error": { "code": "itemNotFound", "message": "Item not found" } }),
+    )]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport);
+
+    match client.v1().me().drive().get_item(ID).send() {
+        Err(GraphFailure::GraphError(err)) => assert_eq!(404, err.code),
+        other => panic!("Expected a GraphError. Got: {:#?}", other),
+    }
+}
+
+#[test]
+fn transport_delta_next_links() {
+    let next_link = format!("{}/groups/delta?$skiptoken=token", GRAPH_URL);
+    let transport = MockTransport::new(vec![
+        MockResponse::json(
+            StatusCode::OK,
+            serde_json::json!({ "@odata.nextLink": next_link, "value": [ { "id": "1" } ] }),
+        ),
+        MockResponse::json(
+            StatusCode::OK,
+            serde_json::json!({ "value": [ { "id": "2" } ] }),
+        ),
+    ]);
+    let client = Graph::with_transport("ACCESS_TOKEN", transport.clone());
+
+    let recv = client.v1().groups(RID).delta().send();
+    let mut pages = 0;
+    loop {
+        match recv.recv().unwrap() {
+            Delta::Next(response) => {
+                pages += 1;
+                assert_eq!(Some(1), response.value().value().map(|v| v.len()));
+            },
+            Delta::Done(err) => {
+                assert!(err.is_none());
+                break;
+            },
+        }
+    }
+
+    assert_eq!(2, pages);
+    let requests = transport.requests();
+    assert_eq!(format!("{}/groups/delta", GRAPH_URL), requests[0].url);
+    assert_eq!(next_link, requests[1].url);
+    assert_eq!(Some("Bearer ACCESS_TOKEN"), requests[1].bearer());
+}
+
+#[test]
+fn async_transport_send() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({ "id": ID, "name": "item" }),
+    )]);
+    let client = Graph::new_async_with_transport("ACCESS_TOKEN", transport.clone());
+
+    let response = client.v1().me().drive().get_item(ID).send().wait().unwrap();
+    assert_eq!(Some("item"), response.value().name().as_deref());
+
+    let requests = transport.requests();
+    assert_eq!(1, requests.len());
+    assert_eq!(Method::GET, requests[0].method);
+    assert_eq!(
+        format!("{}/me/drive/items/{}", GRAPH_URL, ID),
+        requests[0].url
+    );
+    assert_eq!(Some("Bearer ACCESS_TOKEN"), requests[0].bearer());
+}
+
+// Only sends async requests. The body is returned in small chunks.
+struct StreamTransport;
+
+impl HttpTransport for StreamTransport {
+    fn send(&self, _request: GraphRequestBuilder) -> GraphResult<HttpResponse> {
+        panic!("Expected the async request to use send_async");
+    }
+
+    fn send_async(self: Arc<Self>, request: GraphRequestBuilder) -> GraphFuture<HttpResponse> {
+        let body = serde_json::to_vec(&serde_json::json!({ "id": ID, "name": "item" })).unwrap();
+        let chunks: Vec<Result<Vec<u8>, GraphFailure>> =
+            body.chunks(8).map(|chunk| Ok(chunk.to_vec())).collect();
+        Box::new(future::ok(HttpResponse::from_stream(
+            StatusCode::OK,
+            request.to_url(),
+            HeaderMap::new(),
+            stream::iter_result(chunks),
+        )))
+    }
+}
+
+#[test]
+fn async_transport_send_async() {
+    let client = Graph::new_async_with_transport("ACCESS_TOKEN", StreamTransport);
+
+    let response = client.v1().me().drive().get_item(ID).send().wait().unwrap();
+    assert_eq!(Some("item"), response.value().name().as_deref());
+}
+
+#[test]
+fn async_transport_error() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::NOT_FOUND,
+        serde_json::json!({ "error": { "code": "itemNotFound", "message": "Item not found" } }),
+    )]);
+    let client = Graph::new_async_with_transport("ACCESS_TOKEN", transport);
+
+    match client.v1().me().drive().get_item(ID).send().wait() {
+        Err(GraphFailure::GraphError(err)) => assert_eq!(404, err.code),
+        other => panic!("Expected a GraphError. Got: {:#?}", other),
+    }
+}
+
+#[test]
+fn async_transport_delta_next_links() {
+    let next_link = format!("{}/groups/delta?$skiptoken=token", GRAPH_URL);
+    let transport = MockTransport::new(vec![
+        MockResponse::json(
+            StatusCode::OK,
+            serde_json::json!({ "@odata.nextLink": next_link, "value": [ { "id": "1" } ] }),
+        ),
+        MockResponse::json(
+            StatusCode::OK,
+            serde_json::json!({ "value": [ { "id": "2" } ] }),
+        ),
+    ]);
+    let client = Graph::new_async_with_transport("ACCESS_TOKEN", transport.clone());
+
+    let pages = client
+        .v1()
+        .groups(RID)
+        .delta()
+        .send()
+        .collect()
+        .wait()
+        .unwrap();
+    assert_eq!(2, pages.len());
+    assert_eq!(Some(1), pages[1].value().value().map(|v| v.len()));
+
+    let requests = transport.requests();
+    assert_eq!(format!("{}/groups/delta", GRAPH_URL), requests[0].url);
+    assert_eq!(next_link, requests[1].url);
+    assert_eq!(Some("Bearer ACCESS_TOKEN"), requests[1].bearer());
+}
+
+#[test]
+fn async_transport_download() {
+    let transport = MockTransport::new(vec![MockResponse::json(
+        StatusCode::OK,
+        serde_json::json!({ "id": ID }),
+    )
+    .header(
+        "content-disposition",
+        "attachment;filename=\"async_download.json\"",
+    )]);
+    let client = Graph::new_async_with_transport("ACCESS_TOKEN", transport.clone());
+
+    let dir = "./test_files/async_download";
+    let path = client
+        .v1()
+        .me()
+        .drive()
+        .download(ID, dir)
+        .send()
+        .wait()
+        .unwrap();
+    assert_eq!(Path::new(dir).join("async_download.json"), path);
+    let value: serde_json::Value = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+    fs::remove_dir_all(dir).unwrap();
+    assert_eq!(ID, value["id"]);
+    assert_eq!(
+        format!("{}/me/drive/items/{}/content", GRAPH_URL, ID),
+        transport.requests()[0].url
+    );
+}
+
+#[test]
+fn async_transport_upload_session() {
+    let upload_url = "https://upload.example.com/session";
+    let transport = MockTransport::new(vec![
+        MockResponse::json(
+            StatusCode::OK,
+            serde_json::json!({ "uploadUrl": upload_url }),
+        ),
+        MockResponse::json(StatusCode::CREATED, serde_json::json!({ "id": ID })),
+    ]);
+    let client = Graph::new_async_with_transport("ACCESS_TOKEN", transport.clone());
+
+    let session = client
+        .v1()
+        .me()
+        .drive()
+        .upload_session(
+            ":/upload_session_file.txt:",
+            "./test_files/item_test/drive_info.json",
+            &Session::default(),
+        )
+        .send()
+        .wait()
+        .unwrap();
+    let steps = session.collect().wait().unwrap();
+    assert_eq!(1, steps.len());
+    match &steps[0] {
+        AsyncNextSession::Done(response) => assert_eq!(Some(ID), response.value()["id"].as_str()),
+        AsyncNextSession::Next(_) => panic!("Expected the upload session to be done"),
+    }
+
+    let requests = transport.requests();
+    assert_eq!(2, requests.len());
+    assert_eq!(Some("Bearer ACCESS_TOKEN"), requests[0].bearer());
+    assert_eq!(upload_url, requests[1].url);
+    assert_eq!(Method::PUT, requests[1].method);
+    assert_eq!(None, requests[1].bearer());
+}